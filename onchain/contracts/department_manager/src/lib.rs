@@ -7,11 +7,31 @@
 //! and organizations. Supports department creation, employee assignment,
 //! employee removal, and department-level reporting.
 //!
+//! Every failure is returned as a [`DepartmentError`] code.
+//!
 //! # Role Model
 //! - **Admin**: Deploys and initializes the contract (one-time).
 //! - **Org Owner**: Any authenticated address that creates an organization.
 //!   Only the org owner may create departments within the org and manage
-//!   all employee assignments within that org.
+//!   all employee assignments within that org. Ownership can be handed over
+//!   with `propose_org_owner` followed by `accept_org_ownership`.
+//! - **Org Admin**: Optional addresses added by the org owner (at most
+//!   `MAX_ORG_ADMINS`). Share every owner right except managing admins,
+//!   transferring ownership, setting the payroll contract and deleting the
+//!   org.
+//! - **Department Manager**: Optional per-department address set by the org
+//!   owner. May assign and remove employees within the departments it
+//!   manages (optionally including their sub-departments), but cannot create
+//!   or move departments.
+//! - **Granted roles**: `grant_role` gives an address one [`Role`] per org.
+//!   `Admin` makes it an org admin; `Manager` lets it create departments
+//!   and assign and remove employees within one department subtree;
+//!   `Viewer` is reserved for read gating and gives no extra rights. The
+//!   org owner is the implicit `Owner` and keeps every right.
+//! - **Payroll Contract**: Optional per-org address set by the org owner.
+//!   May record spend against department budgets, like the owner. Its
+//!   agreements can be linked to departments and synced into them with
+//!   `sync_from_payroll`.
 //!
 //! # Storage Layout (for integrators)
 //! | Key                                  | Value               | Description                       |
@@ -20,23 +40,229 @@
 //! | `Initialized`                        | `bool`              | One-time init guard               |
 //! | `NextOrgId`                          | `u128`              | Auto-increment org ID counter     |
 //! | `NextDeptId`                         | `u128`              | Auto-increment dept ID counter    |
-//! | `Organization(org_id)`               | `Organization`      | Org record                        |
-//! | `Department(dept_id)`                | `Department`        | Department record                 |
+//! | `OrganizationV2(org_id)`             | `Organization`      | Org record                        |
+//! | `DepartmentV2(dept_id)`              | `Department`        | Department record                 |
 //! | `OrgDepartments(org_id)`             | `Vec<u128>`         | All dept IDs in an org            |
 //! | `DepartmentChildren(parent_dept_id)` | `Vec<u128>`         | Child dept IDs                    |
+//! | `DeptNameIndexV2(org_id, name)`      | `u128`              | Active dept holding a name        |
 //! | `EmployeeInDepartment(dept_id, addr)`| `()`               | Membership flag                   |
 //! | `EmployeeDepartment(addr, org_id)`   | `u128`              | Employee → current dept in org    |
 //! | `DepartmentEmployees(dept_id)`       | `Vec<Address>`      | All employees in a dept           |
+//! | `DepartmentEmployeeCount(dept_id)`   | `u32`               | Number of employees in a dept     |
+//! | `OrgEmployees(org_id)`               | `Vec<Address>`      | All assigned employees in an org  |
+//! | `EmployeeOrgs(addr)`                 | `Vec<u128>`         | Orgs an employee is assigned in   |
+//! | `EmployeeProfile(org_id, addr)`      | `EmployeeProfile`   | Title, start date, metadata       |
+//! | `EmployeeInactive(org_id, addr)`     | `()`                | Inactive (e.g. on leave) flag     |
+//! | `EmployeeTransfers(addr)`            | `Vec<TransferRecord>` | Latest cross-org transfers      |
+//! | `PendingOrgOwner(org_id)`            | `Address`           | Proposed next org owner           |
+//! | `OrgAdmins(org_id)`                  | `Vec<Address>`      | Admins sharing owner rights       |
+//! | `OrgRole(org_id, addr)`              | `RoleGrant`         | Manager or viewer role            |
+//! | `OrgSettings(org_id)`                | `OrgSettings`       | Per-org limits (max depth)        |
+//! | `DepartmentManager(dept_id)`         | `Address`           | Delegated assignment rights       |
+//! | `ManagerIncludesChildren(dept_id)`   | `bool`              | Manager also covers sub-depts     |
+//! | `DepartmentHead(dept_id)`            | `Address`           | Head of department (an employee)  |
+//! | `DepartmentCapacity(dept_id)`        | `u32`               | Max employees in a dept           |
+//! | `DepartmentSnapshots(dept_id)`       | `Vec<(u64, u32)>`   | Latest daily headcount snapshots  |
+//! | `ArchivedDepartment(dept_id)`        | `()`                | Archived (closed) flag            |
+//! | `DepartmentCostCenter(dept_id)`      | `Symbol`            | Accounting cost-center code       |
+//! | `CostCenterIndex(org_id, code)`      | `u128`              | Dept holding a cost-center code   |
+//! | `PayrollContract(org_id)`            | `Address`           | May record department spend       |
+//! | `DepartmentAgreement(dept_id)`       | `u128`              | Linked payroll agreement          |
+//! | `DepartmentBudget(dept_id, token)`   | `DepartmentBudget`  | Allocation, spend, overspend flag |
+//! | `DepartmentKeys(dept_id)`            | `Vec<StorageKey>`   | Optional dept entries that exist  |
+//! | `OrgBudgets(org_id)`                 | `Vec<(u128, Address)>` | Departments and tokens with a budget |
+//! | `OrgRoleHolders(org_id)`             | `Vec<Address>`      | Addresses with an `OrgRole` grant |
+//!
+//! Records written before names became `String`s stay under the legacy
+//! `Organization(org_id)`, `Department(dept_id)` and
+//! `DeptNameIndex(org_id, Symbol)` keys; departments from before names were
+//! unique have no name index entry at all. They are read transparently and
+//! moved to the keys above by `migrate_organization` / `migrate_department`
+//! or the next update of the record, which also reserves the name of an
+//! active department.
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Bytes, Env,
+    IntoVal, String, SymbolStr, TryFromVal, Val, Vec,
+};
 
-/// Maximum allowed depth of the department hierarchy (root = depth 0).
+/// Maximum allowed depth of the department hierarchy (root = depth 0), and
+/// the default and highest value of an org's `max_depth` setting.
 /// A department at depth MAX_DEPTH cannot have children.
 pub const MAX_DEPTH: u32 = 10;
 
+/// Maximum number of employees in one `assign_employees_batch` call, to stay
+/// within per-transaction instruction limits.
+pub const MAX_BATCH_SIZE: u32 = 50;
+
+/// Maximum number of departments in one `create_departments_bulk` call,
+/// enough to set up a typical org tree at once while keeping the call's
+/// ledger footprint bounded.
+pub const MAX_BULK_DEPARTMENTS: u32 = 25;
+
+/// Maximum number of entries returned by one `get_org_employees` or
+/// `get_department_employees_page` call.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Maximum number of transfer records kept per employee; older ones are
+/// dropped (the `emp_xfer` events keep the full history).
+pub const MAX_TRANSFER_HISTORY: u32 = 50;
+
+/// Maximum number of employees plus child departments a department may have
+/// to be merged by `merge_departments`. Each one writes up to three ledger
+/// entries, and a transaction may write at most 50.
+pub const MAX_MERGE_SIZE: u32 = 12;
+
+/// Maximum number of departments an org may have to be deleted by
+/// `delete_organization`. Each one reads at least three ledger entries, and
+/// a transaction may touch at most 100.
+pub const MAX_ORG_DELETE_DEPARTMENTS: u32 = 10;
+
+/// Maximum number of ledger entries one `delete_organization` call removes.
+/// An empty department without optional settings accounts for four, and a
+/// transaction may write at most 50.
+pub const MAX_ORG_DELETE_ENTRIES: u32 = 50;
+
+/// Maximum number of department budgets (one per department and token) per
+/// organization, so their index stays small.
+pub const MAX_ORG_BUDGETS: u32 = 20;
+
+/// Maximum number of `Manager` and `Viewer` grants per organization, so
+/// their index stays small.
+pub const MAX_ORG_ROLES: u32 = 20;
+
+/// Maximum length in bytes of an organization or department name.
+pub const MAX_NAME_LENGTH: u32 = 64;
+
+/// Number of headcount snapshots kept per department (e.g. two years of
+/// month-end numbers); older ones are evicted.
+pub const MAX_SNAPSHOTS: u32 = 24;
+
+/// Length of a day in seconds; snapshots taken in the same day replace each
+/// other.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Maximum number of levels below the root that
+/// `get_department_report_recursive` descends.
+pub const MAX_REPORT_DEPTH: u32 = 5;
+
+/// Maximum number of departments one `get_department_report_recursive` call
+/// visits, to stay within per-transaction instruction limits.
+pub const MAX_REPORT_DEPARTMENTS: u32 = 50;
+
+/// Maximum number of departments one `get_department_subtree` call returns.
+/// Each one reads its record and child list, and a transaction may touch at
+/// most 100 ledger entries.
+pub const MAX_SUBTREE_NODES: u32 = 40;
+
+/// Maximum number of employees one `sync_from_payroll` call adds. Each one
+/// writes up to three new ledger entries.
+pub const MAX_PAYROLL_SYNC: u32 = 10;
+
+/// Maximum number of admins per organization, so authorization checks stay
+/// cheap.
+pub const MAX_ORG_ADMINS: u32 = 10;
+
 #[contract]
 pub struct DepartmentManagerContract;
 
+/// Errors returned by the contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DepartmentError {
+    /// The contract has not been initialized.
+    NotInitialized = 1,
+    /// `initialize` was already called.
+    AlreadyInitialized = 2,
+    /// The organization does not exist.
+    OrgNotFound = 3,
+    /// The department does not exist.
+    DeptNotFound = 4,
+    /// The caller is not the org owner.
+    NotOrgOwner = 5,
+    /// The parent department does not exist.
+    ParentNotFound = 6,
+    /// The parent department belongs to another org.
+    ParentNotInOrg = 7,
+    /// The department belongs to another org.
+    DeptNotInOrg = 8,
+    /// The department would be deeper than the org's `max_depth`.
+    MaxDepthExceeded = 9,
+    /// The move would place a department under its own descendant.
+    CycleDetected = 10,
+    /// The employee has no department in the org.
+    EmployeeNotFound = 11,
+    /// The caller neither owns the org nor manages the department.
+    NotOrgOwnerOrManager = 12,
+    /// The batch is empty or longer than `MAX_BATCH_SIZE`.
+    InvalidBatchSize = 13,
+    /// No ownership transfer has been proposed.
+    NoPendingOwner = 14,
+    /// The caller is not the proposed owner.
+    NotPendingOwner = 15,
+    /// The department is archived.
+    DeptArchived = 16,
+    /// The department is not archived.
+    DeptNotArchived = 17,
+    /// The department (or a cascaded descendant) still has employees.
+    DeptHasEmployees = 18,
+    /// The department has active children and `cascade` is off.
+    DeptHasActiveChildren = 19,
+    /// The department has child departments.
+    DeptHasChildren = 20,
+    /// A budget amount is out of range.
+    InvalidAmount = 21,
+    /// No budget exists for the department and token.
+    BudgetNotSet = 22,
+    /// Spend would exceed the allocation and overspend is not allowed.
+    BudgetExceeded = 23,
+    /// The caller neither owns the org nor is its payroll contract.
+    NotOrgOwnerOrPayroll = 24,
+    /// A parent chain is longer than `MAX_DEPTH + 1`.
+    CorruptedHierarchy = 25,
+    /// The address is not an employee of the department.
+    NotDepartmentEmployee = 26,
+    /// The organization already has `MAX_ORG_ADMINS` admins.
+    AdminLimitReached = 27,
+    /// The address is already an admin of the organization.
+    AlreadyOrgAdmin = 28,
+    /// The address is not an admin of the organization.
+    NotOrgAdmin = 29,
+    /// An active department in the organization already uses the name.
+    DuplicateDepartmentName = 30,
+    /// The requested `max_depth` is above `MAX_DEPTH`.
+    InvalidMaxDepth = 31,
+    /// Another department in the organization already uses the cost-center code.
+    DuplicateCostCenter = 32,
+    /// A bulk spec's parent index does not point to an earlier spec.
+    InvalidParentIndex = 33,
+    /// The department has reached its employee capacity.
+    DepartmentFull = 34,
+    /// A transfer must go to a different organization.
+    SameOrgTransfer = 35,
+    /// The source department has too many employees and child departments
+    /// to merge in one call.
+    MergeTooLarge = 36,
+    /// The organization has too many departments or entries to delete in
+    /// one call.
+    OrgTooLarge = 37,
+    /// A name is empty or longer than `MAX_NAME_LENGTH` bytes.
+    InvalidName = 38,
+    /// The role cannot be granted: `Owner`, a grant to the org owner, or a
+    /// scope that does not match the role.
+    InvalidRole = 39,
+    /// The address holds no role in the organization.
+    NoRole = 40,
+    /// The department has no linked payroll agreement, or its org has no
+    /// payroll contract.
+    AgreementNotLinked = 41,
+    /// The organization already has `MAX_ORG_ROLES` role grants.
+    RoleLimitReached = 42,
+    /// The organization already has `MAX_ORG_BUDGETS` department budgets.
+    BudgetLimitReached = 43,
+}
+
 /// Storage keys for the contract
 #[contracttype]
 #[derive(Clone)]
@@ -49,20 +275,89 @@ enum StorageKey {
     NextOrgId,
     /// Next department ID counter (global)
     NextDeptId,
-    /// Organization data: org_id -> Organization
+    /// Legacy organization data: org_id -> LegacyOrganization
     Organization(u128),
-    /// Department data: dept_id -> Department
+    /// Legacy department data: dept_id -> LegacyDepartment
     Department(u128),
+    /// Organization data: org_id -> Organization
+    OrganizationV2(u128),
+    /// Department data: dept_id -> Department
+    DepartmentV2(u128),
     /// All department IDs under an organization: org_id -> Vec<u128>
     OrgDepartments(u128),
     /// Child department IDs: parent_dept_id -> Vec<u128>
     DepartmentChildren(u128),
+    /// Legacy name index of `LegacyDepartment`s: (org_id, name) -> dept_id
+    DeptNameIndex(u128, soroban_sdk::Symbol),
+    /// Active department holding a name within an org: (org_id, name) -> dept_id
+    DeptNameIndexV2(u128, String),
     /// Employee membership flag: (dept_id, employee_address) -> ()
     EmployeeInDepartment(u128, Address),
     /// Current department for an employee in an org: (employee, org_id) -> dept_id
     EmployeeDepartment(Address, u128),
     /// List of employee addresses in a department: dept_id -> Vec<Address>
     DepartmentEmployees(u128),
+    /// Number of employees in a department, kept so it can be read without
+    /// loading the list: dept_id -> u32
+    DepartmentEmployeeCount(u128),
+    /// Every employee assigned to some department of an org, once: org_id -> Vec<Address>
+    OrgEmployees(u128),
+    /// Every org in which an employee is assigned to some department: employee -> Vec<u128>
+    EmployeeOrgs(Address),
+    /// Profile of an employee in an org: (org_id, employee) -> EmployeeProfile
+    EmployeeProfile(u128, Address),
+    /// Inactive flag of an employee in an org, active when absent: (org_id, employee) -> ()
+    EmployeeInactive(u128, Address),
+    /// Latest cross-org transfers of an employee, oldest first: employee -> Vec<TransferRecord>
+    EmployeeTransfers(Address),
+    /// Proposed next owner of an organization: org_id -> Address
+    PendingOrgOwner(u128),
+    /// Admins sharing the owner's rights: org_id -> Vec<Address>
+    OrgAdmins(u128),
+    /// Manager or viewer role of an address: (org_id, address) -> RoleGrant
+    OrgRole(u128, Address),
+    /// Per-org settings, defaults apply when absent: org_id -> OrgSettings
+    OrgSettings(u128),
+    /// Manager with delegated assignment rights: dept_id -> Address
+    DepartmentManager(u128),
+    /// Whether the department's manager also manages its descendants: dept_id -> bool
+    ManagerIncludesChildren(u128),
+    /// Head of department for reporting lines, always one of its employees: dept_id -> Address
+    DepartmentHead(u128),
+    /// Maximum number of employees, unlimited when absent: dept_id -> u32
+    DepartmentCapacity(u128),
+    /// Latest headcount snapshots, oldest first: dept_id -> Vec<(timestamp, employee_count)>
+    DepartmentSnapshots(u128),
+    /// Archived flag, kept outside `Department` so its layout is unchanged: dept_id -> ()
+    ArchivedDepartment(u128),
+    /// Accounting cost-center code, kept outside `Department` so its layout
+    /// is unchanged: dept_id -> Symbol
+    DepartmentCostCenter(u128),
+    /// Department holding a cost-center code within an org: (org_id, code) -> dept_id
+    CostCenterIndex(u128, soroban_sdk::Symbol),
+    /// Contract allowed to record department spend: org_id -> Address
+    PayrollContract(u128),
+    /// Payroll agreement whose employees belong in the department: dept_id -> agreement_id
+    DepartmentAgreement(u128),
+    /// Budget per department and token: (dept_id, token) -> DepartmentBudget
+    DepartmentBudget(u128, Address),
+    /// Optional entries of a department that exist (settings, child list,
+    /// employee count), so deletion does not have to probe for them; absent
+    /// for departments created before it: dept_id -> Vec<StorageKey>
+    DepartmentKeys(u128),
+    /// Departments and tokens with a budget: org_id -> Vec<(dept_id, token)>
+    OrgBudgets(u128),
+    /// Addresses holding an `OrgRole` grant: org_id -> Vec<Address>
+    OrgRoleHolders(u128),
+}
+
+/// Budget of one department in one token
+#[contracttype]
+#[derive(Clone)]
+struct DepartmentBudget {
+    allocated: i128,
+    spent: i128,
+    allow_overspend: bool,
 }
 
 /// Organization record
@@ -70,7 +365,7 @@ enum StorageKey {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Organization {
     pub id: u128,
-    pub name: soroban_sdk::Symbol,
+    pub name: String,
     pub owner: Address,
     pub created_at: u64,
 }
@@ -81,11 +376,146 @@ pub struct Organization {
 pub struct Department {
     pub id: u128,
     pub org_id: u128,
-    pub name: soroban_sdk::Symbol,
+    pub name: String,
     pub parent_id: Option<u128>,
     pub created_at: u64,
 }
 
+/// Organization record as stored before names became `String`s
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct LegacyOrganization {
+    id: u128,
+    name: soroban_sdk::Symbol,
+    owner: Address,
+    created_at: u64,
+}
+
+/// Department record as stored before names became `String`s
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct LegacyDepartment {
+    id: u128,
+    org_id: u128,
+    name: soroban_sdk::Symbol,
+    parent_id: Option<u128>,
+    created_at: u64,
+}
+
+/// Per-organization settings
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgSettings {
+    /// Deepest allowed department depth (top-level = 0), at most `MAX_DEPTH`.
+    pub max_depth: u32,
+}
+
+/// Role of an address within an organization
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// The org owner; implicit, cannot be granted.
+    Owner,
+    /// Manages departments and employees org-wide.
+    Admin,
+    /// Manages departments and employees within one department subtree.
+    Manager,
+    /// No extra rights yet; reserved for read gating.
+    Viewer,
+}
+
+/// A role together with the department subtree it applies to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrant {
+    pub role: Role,
+    /// Root of the managed subtree; `Some` exactly for `Manager`.
+    pub scope_department: Option<u128>,
+}
+
+/// Profile of an employee within an organization
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmployeeProfile {
+    pub title: soroban_sdk::Symbol,
+    pub start_date: u64,
+    pub metadata: Bytes,
+}
+
+/// A cross-organization transfer of an employee
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferRecord {
+    pub from_org_id: u128,
+    pub from_department_id: u128,
+    pub to_org_id: u128,
+    pub to_department_id: u128,
+    pub timestamp: u64,
+}
+
+/// Department-level report returned by `get_department_report`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepartmentReport {
+    pub employee_count: u32,
+    /// `active_count + inactive_count == employee_count`.
+    pub active_count: u32,
+    pub inactive_count: u32,
+    pub child_department_ids: Vec<u128>,
+    pub employees: Vec<Address>,
+    pub head: Option<Address>,
+    /// `None` when the department has no capacity.
+    pub capacity: Option<u32>,
+    /// Free slots below the capacity, `None` when there is no capacity.
+    pub remaining_slots: Option<u32>,
+    /// Whether the department is archived (see `is_department_archived`).
+    pub archived: bool,
+}
+
+/// Data of the `("org_crtd", org_id)` event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrgCreatedEvent {
+    pub org_id: u128,
+    pub owner: Address,
+    pub timestamp: u64,
+}
+
+/// Data of the `("dept_crtd", dept_id)` event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepartmentCreatedEvent {
+    pub org_id: u128,
+    pub department_id: u128,
+    pub parent_id: Option<u128>,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+/// Data of the `("emp_asgnd", dept_id)` event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmployeeAssignedEvent {
+    pub org_id: u128,
+    pub department_id: u128,
+    pub employee: Address,
+    /// Department the employee was moved out of, if any.
+    pub previous_department: Option<u128>,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
+/// Data of the `("emp_rmvd", dept_id)` event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmployeeRemovedEvent {
+    pub org_id: u128,
+    pub department_id: u128,
+    pub employee: Address,
+    pub caller: Address,
+    pub timestamp: u64,
+}
+
 #[contractimpl]
 impl DepartmentManagerContract {
     // -------------------------------------------------------------------------
@@ -97,16 +527,18 @@ impl DepartmentManagerContract {
     /// # Arguments
     /// * `admin` - Address that will be the admin (must authenticate).
     ///
-    /// # Panics
-    /// Panics with `"Already initialized"` if called more than once.
-    pub fn initialize(env: Env, admin: Address) {
+    /// # Errors
+    /// `AlreadyInitialized` if called more than once.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), DepartmentError> {
         admin.require_auth();
         let initialized: bool = env
             .storage()
             .persistent()
             .get(&StorageKey::Initialized)
             .unwrap_or(false);
-        assert!(!initialized, "Already initialized");
+        if initialized {
+            return Err(DepartmentError::AlreadyInitialized);
+        }
         env.storage().persistent().set(&StorageKey::Admin, &admin);
         env.storage()
             .persistent()
@@ -117,6 +549,68 @@ impl DepartmentManagerContract {
         env.storage()
             .persistent()
             .set(&StorageKey::NextDeptId, &1u128);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------------
+    // Migration
+    // -------------------------------------------------------------------------
+
+    /// Moves an organization record written before names became `String`s
+    /// to its current storage key. Legacy records are read transparently, so
+    /// this only tidies storage. Anyone may call it; the record's contents do
+    /// not change.
+    ///
+    /// # Arguments
+    /// * `org_id` - The organization ID.
+    ///
+    /// # Returns
+    /// `true` if a legacy record was migrated, `false` if it was current.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    pub fn migrate_organization(env: Env, org_id: u128) -> Result<bool, DepartmentError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&StorageKey::OrganizationV2(org_id))
+        {
+            return Ok(false);
+        }
+        let org = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        Self::store_org(&env, &org);
+        Ok(true)
+    }
+
+    /// Moves a department record written before names became `String`s to
+    /// its current storage key, and its name to the current name index.
+    /// Until then the department works as usual, but its name is neither
+    /// reserved against new departments nor found by
+    /// `get_department_by_name`. Anyone may call it; the record's contents
+    /// do not change.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    ///
+    /// # Returns
+    /// `true` if a legacy record was migrated, `false` if it was current.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `DuplicateDepartmentName` – a newer active department took the name;
+    ///   archive or delete one of them first.
+    pub fn migrate_department(env: Env, department_id: u128) -> Result<bool, DepartmentError> {
+        if env
+            .storage()
+            .persistent()
+            .has(&StorageKey::DepartmentV2(department_id))
+        {
+            return Ok(false);
+        }
+        let dept =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        Self::store_department(&env, &dept)?;
+        Ok(true)
     }
 
     // -------------------------------------------------------------------------
@@ -127,16 +621,24 @@ impl DepartmentManagerContract {
     ///
     /// # Arguments
     /// * `owner` - Caller (must authenticate); becomes org owner.
-    /// * `name`  - Symbol name for the organization.
+    /// * `name`  - Name of the organization, 1 to `MAX_NAME_LENGTH` bytes.
     ///
     /// # Returns
     /// The new organization ID (starts at 1, increments by 1).
     ///
+    /// # Errors
+    /// - `InvalidName` – name is empty or too long.
+    ///
     /// # Events
-    /// Publishes `("org_created", org_id)` on success.
-    pub fn create_organization(env: Env, owner: Address, name: soroban_sdk::Symbol) -> u128 {
+    /// Publishes `("org_crtd", org_id)` with an `OrgCreatedEvent`.
+    pub fn create_organization(
+        env: Env,
+        owner: Address,
+        name: String,
+    ) -> Result<u128, DepartmentError> {
         owner.require_auth();
-        Self::require_initialized(&env);
+        Self::require_initialized(&env)?;
+        Self::validate_name(&name)?;
         let next_id: u128 = env
             .storage()
             .persistent()
@@ -153,509 +655,3038 @@ impl DepartmentManagerContract {
         };
         env.storage()
             .persistent()
-            .set(&StorageKey::Organization(next_id), &org);
+            .set(&StorageKey::OrganizationV2(next_id), &org);
         let empty: Vec<u128> = Vec::new(&env);
         env.storage()
             .persistent()
             .set(&StorageKey::OrgDepartments(next_id), &empty);
 
-        env.events()
-            .publish((symbol_short!("org_crtd"), next_id), next_id);
+        env.events().publish(
+            (symbol_short!("org_crtd"), next_id),
+            OrgCreatedEvent {
+                org_id: next_id,
+                owner,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        next_id
+        Ok(next_id)
     }
 
-    /// Returns the organization record.
+    /// Returns the organization record, or `None` if the ID does not exist.
     ///
     /// # Arguments
     /// * `org_id` - The organization ID.
+    pub fn get_organization(env: Env, org_id: u128) -> Option<Organization> {
+        Self::load_org(&env, org_id)
+    }
+
+    /// Proposes a new owner for an organization. The transfer completes when
+    /// the proposed owner calls `accept_org_ownership`; until then the current
+    /// owner keeps all rights. A later proposal replaces an earlier one.
+    ///
+    /// # Arguments
+    /// * `current_owner` - Must be the **org owner** (must authenticate).
+    /// * `org_id`        - Organization ID.
+    /// * `new_owner`     - Address that may accept ownership.
     ///
-    /// # Panics
-    /// Panics with `"Organization not found"` if the ID does not exist.
-    pub fn get_organization(env: Env, org_id: u128) -> Organization {
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – caller is not the org owner.
+    ///
+    /// # Events
+    /// Publishes `("own_prop", org_id)` with the proposed owner.
+    pub fn propose_org_owner(
+        env: Env,
+        current_owner: Address,
+        org_id: u128,
+        new_owner: Address,
+    ) -> Result<(), DepartmentError> {
+        current_owner.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if org.owner != current_owner {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+
         env.storage()
             .persistent()
-            .get(&StorageKey::Organization(org_id))
-            .expect("Organization not found")
-    }
+            .set(&StorageKey::PendingOrgOwner(org_id), &new_owner);
 
-    // -------------------------------------------------------------------------
-    // Departments (Org Owner operations)
-    // -------------------------------------------------------------------------
+        env.events()
+            .publish((symbol_short!("own_prop"), org_id), new_owner);
+        Ok(())
+    }
 
-    /// Creates a department under an organization.
-    ///
-    /// The department can be:
-    /// - **Top-level**: `parent_id = None`
-    /// - **Nested**: `parent_id = Some(parent_dept_id)` (parent must be in same org)
+    /// Completes an ownership transfer started with `propose_org_owner`.
     ///
     /// # Arguments
-    /// * `caller`    - Must be the **org owner** (must authenticate).
+    /// * `new_owner` - Must be the proposed owner (must authenticate).
     /// * `org_id`    - Organization ID.
-    /// * `name`      - Symbol name for the department.
-    /// * `parent_id` - Optional parent department ID; `None` for top-level.
-    ///
-    /// # Returns
-    /// The new department ID (global counter, starts at 1).
     ///
-    /// # Panics
-    /// - `"Organization not found"` – org_id does not exist.
-    /// - `"Not organization owner"` – caller is not the org owner.
-    /// - `"Parent department not found"` – parent_id does not exist.
-    /// - `"Parent must be in same org"` – parent belongs to a different org.
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NoPendingOwner` – no transfer has been proposed.
+    /// - `NotPendingOwner` – caller is not the proposed owner.
     ///
     /// # Events
-    /// Publishes `("dept_crtd", dept_id)` on success.
-    pub fn create_department(
+    /// Publishes `("own_xfer", org_id)` with `(old_owner, new_owner)`.
+    pub fn accept_org_ownership(
         env: Env,
-        caller: Address,
+        new_owner: Address,
         org_id: u128,
-        name: soroban_sdk::Symbol,
-        parent_id: Option<u128>,
-    ) -> u128 {
-        caller.require_auth();
-        Self::require_initialized(&env);
-        let org: Organization = env
+    ) -> Result<(), DepartmentError> {
+        new_owner.require_auth();
+        Self::require_initialized(&env)?;
+        let mut org: Organization =
+            Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        let pending: Address = env
             .storage()
             .persistent()
-            .get(&StorageKey::Organization(org_id))
-            .expect("Organization not found");
-        assert!(org.owner == caller, "Not organization owner");
-
-        if let Some(pid) = parent_id {
-            let parent: Department = env
-                .storage()
-                .persistent()
-                .get(&StorageKey::Department(pid))
-                .expect("Parent department not found");
-            assert!(parent.org_id == org_id, "Parent must be in same org");
-            // child depth = parent depth + 1; must not exceed MAX_DEPTH
-            assert!(Self::dept_depth(&env, pid) + 1 <= MAX_DEPTH, "Max hierarchy depth exceeded");
+            .get(&StorageKey::PendingOrgOwner(org_id))
+            .ok_or(DepartmentError::NoPendingOwner)?;
+        if pending != new_owner {
+            return Err(DepartmentError::NotPendingOwner);
         }
 
-        let next_id: u128 = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::NextDeptId)
-            .unwrap_or(1);
+        let old_owner = org.owner.clone();
+        org.owner = new_owner.clone();
+        Self::store_org(&env, &org);
         env.storage()
             .persistent()
-            .set(&StorageKey::NextDeptId, &(next_id + 1));
+            .remove(&StorageKey::PendingOrgOwner(org_id));
 
-        let dept = Department {
-            id: next_id,
-            org_id,
-            name,
-            parent_id,
-            created_at: env.ledger().timestamp(),
-        };
-        env.storage()
-            .persistent()
-            .set(&StorageKey::Department(next_id), &dept);
+        env.events()
+            .publish((symbol_short!("own_xfer"), org_id), (old_owner, new_owner));
+        Ok(())
+    }
 
-        // Register dept under org
-        let mut org_depts: Vec<u128> = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::OrgDepartments(org_id))
-            .unwrap_or_else(|| Vec::new(&env));
-        org_depts.push_back(next_id);
+    /// Returns the proposed next owner of an organization, if any.
+    ///
+    /// # Arguments
+    /// * `org_id` - The organization ID.
+    pub fn get_pending_org_owner(env: Env, org_id: u128) -> Option<Address> {
         env.storage()
             .persistent()
-            .set(&StorageKey::OrgDepartments(org_id), &org_depts);
+            .get(&StorageKey::PendingOrgOwner(org_id))
+    }
 
-        // Register as child of parent dept if nested
-        if let Some(pid) = parent_id {
-            let mut children: Vec<u128> = env
-                .storage()
-                .persistent()
-                .get(&StorageKey::DepartmentChildren(pid))
-                .unwrap_or_else(|| Vec::new(&env));
-            children.push_back(next_id);
-            env.storage()
-                .persistent()
-                .set(&StorageKey::DepartmentChildren(pid), &children);
+    /// Adds an admin to an organization. Admins may do everything the owner
+    /// can except manage admins, transfer ownership, set the payroll
+    /// contract and delete the org. Replaces any role granted with
+    /// `grant_role`.
+    ///
+    /// # Arguments
+    /// * `owner`  - Must be the **org owner** (must authenticate).
+    /// * `org_id` - Organization ID.
+    /// * `admin`  - Address to add.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – caller is not the org owner.
+    /// - `AlreadyOrgAdmin` – admin is already in the list.
+    /// - `AdminLimitReached` – the org already has `MAX_ORG_ADMINS` admins.
+    ///
+    /// # Events
+    /// Publishes `("adm_add", org_id)` with the admin.
+    pub fn add_org_admin(
+        env: Env,
+        owner: Address,
+        org_id: u128,
+        admin: Address,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if org.owner != owner {
+            return Err(DepartmentError::NotOrgOwner);
         }
 
-        // Initialize empty employee list for this department
-        let empty_employees: Vec<Address> = Vec::new(&env);
-        env.storage()
+        let key = StorageKey::OrgAdmins(org_id);
+        let mut admins: Vec<Address> = env
+            .storage()
             .persistent()
-            .set(&StorageKey::DepartmentEmployees(next_id), &empty_employees);
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if admins.contains(&admin) {
+            return Err(DepartmentError::AlreadyOrgAdmin);
+        }
+        if admins.len() >= MAX_ORG_ADMINS {
+            return Err(DepartmentError::AdminLimitReached);
+        }
+        admins.push_back(admin.clone());
+        env.storage().persistent().set(&key, &admins);
+        Self::remove_role_grant(&env, org_id, &admin);
 
         env.events()
-            .publish((symbol_short!("dept_crtd"), next_id), next_id);
-
-        next_id
+            .publish((symbol_short!("adm_add"), org_id), admin);
+        Ok(())
     }
 
-    /// Returns the department record.
+    /// Removes an admin from an organization. Its rights end immediately.
     ///
     /// # Arguments
-    /// * `department_id` - The department ID.
+    /// * `owner`  - Must be the **org owner** (must authenticate).
+    /// * `org_id` - Organization ID.
+    /// * `admin`  - Address to remove.
     ///
-    /// # Panics
-    /// Panics with `"Department not found"` if the ID does not exist.
-    pub fn get_department(env: Env, department_id: u128) -> Department {
-        env.storage()
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – caller is not the org owner.
+    /// - `NotOrgAdmin` – admin is not in the list.
+    ///
+    /// # Events
+    /// Publishes `("adm_rmvd", org_id)` with the admin.
+    pub fn remove_org_admin(
+        env: Env,
+        owner: Address,
+        org_id: u128,
+        admin: Address,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if org.owner != owner {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+
+        let key = StorageKey::OrgAdmins(org_id);
+        let mut admins: Vec<Address> = env
+            .storage()
             .persistent()
-            .get(&StorageKey::Department(department_id))
-            .expect("Department not found")
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let idx = admins
+            .first_index_of(&admin)
+            .ok_or(DepartmentError::NotOrgAdmin)?;
+        admins.remove(idx);
+        env.storage().persistent().set(&key, &admins);
+
+        env.events()
+            .publish((symbol_short!("adm_rmvd"), org_id), admin);
+        Ok(())
     }
 
-    /// Returns all department IDs (top-level and nested) under an organization.
+    /// Returns the admins of an organization.
     ///
     /// # Arguments
     /// * `org_id` - The organization ID.
-    pub fn get_org_departments(env: Env, org_id: u128) -> Vec<u128> {
+    pub fn get_org_admins(env: Env, org_id: u128) -> Vec<Address> {
         env.storage()
             .persistent()
-            .get(&StorageKey::OrgDepartments(org_id))
-            .unwrap_or_else(|| Vec::new(&env))
+            .get(&StorageKey::OrgAdmins(org_id))
+            .unwrap_or(Vec::new(&env))
     }
 
-    /// Returns the direct child department IDs of a given department.
+    /// Returns `true` if `address` is an admin of the organization.
     ///
     /// # Arguments
-    /// * `department_id` - The parent department ID.
-    pub fn get_child_departments(env: Env, department_id: u128) -> Vec<u128> {
-        env.storage()
-            .persistent()
-            .get(&StorageKey::DepartmentChildren(department_id))
-            .unwrap_or_else(|| Vec::new(&env))
+    /// * `org_id`  - The organization ID.
+    /// * `address` - Address to check.
+    pub fn is_org_admin(env: Env, org_id: u128, address: Address) -> bool {
+        Self::is_admin(&env, org_id, &address)
     }
 
-    // -------------------------------------------------------------------------
-    // Employee Assignment (Org Owner operations)
-    // -------------------------------------------------------------------------
-
-    /// Assigns an employee address to a department within an organization.
+    /// Grants `grantee` a role in an organization, replacing any role it
+    /// already holds.
     ///
-    /// If the employee is already assigned to another department in the same
-    /// org, they are **automatically removed** from the old department first
-    /// (re-assignment/move semantics).
+    /// - `Admin` adds `grantee` to the org admins (as `add_org_admin`).
+    /// - `Manager` lets `grantee` create departments and assign and remove
+    ///   employees within the subtree rooted at `scope_department`.
+    /// - `Viewer` gives no extra rights for now.
     ///
     /// # Arguments
-    /// * `caller`        - Must be the **org owner** (must authenticate).
-    /// * `org_id`        - Organization ID.
-    /// * `department_id` - Target department ID (must belong to `org_id`).
-    /// * `employee`      - Employee address to assign.
+    /// * `granter`          - The **org owner** or an org admin (must
+    ///                        authenticate). Only the owner may grant `Admin`
+    ///                        or change an admin's role.
+    /// * `org_id`           - Organization ID.
+    /// * `grantee`          - Address receiving the role.
+    /// * `role`             - `Admin`, `Manager` or `Viewer`.
+    /// * `scope_department` - Root of the managed subtree; required for
+    ///                        `Manager`, `None` otherwise.
     ///
-    /// # Panics
-    /// - `"Organization not found"` – org_id does not exist.
-    /// - `"Not organization owner"` – caller is not the org owner.
-    /// - `"Department not found"` – department_id does not exist.
-    /// - `"Department not in this org"` – dept belongs to a different org.
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – granter is neither the org owner nor an org admin,
+    ///   or an admin grants `Admin` or changes an admin's role.
+    /// - `InvalidRole` – role is `Owner`, grantee is the org owner, or
+    ///   scope_department does not match the role.
+    /// - `DeptNotFound` / `DeptNotInOrg` – scope_department is not a
+    ///   department of the org.
+    /// - `AdminLimitReached` – the org already has `MAX_ORG_ADMINS` admins.
+    /// - `RoleLimitReached` – the org already has `MAX_ORG_ROLES` manager and
+    ///   viewer grants.
     ///
     /// # Events
-    /// Publishes `("emp_asgnd", employee)` on success.
-    pub fn assign_employee_to_department(
+    /// Publishes `("role_set", org_id)` with `(grantee, RoleGrant)`.
+    pub fn grant_role(
         env: Env,
-        caller: Address,
+        granter: Address,
         org_id: u128,
-        department_id: u128,
-        employee: Address,
-    ) {
-        caller.require_auth();
-        Self::require_initialized(&env);
-        let org: Organization = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::Organization(org_id))
-            .expect("Organization not found");
-        assert!(org.owner == caller, "Not organization owner");
-
-        let dept: Department = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::Department(department_id))
-            .expect("Department not found");
-        assert!(dept.org_id == org_id, "Department not in this org");
-
-        // Remove from previous department in this org, if any
-        if let Some(old_dept) = env
+        grantee: Address,
+        role: Role,
+        scope_department: Option<u128>,
+    ) -> Result<(), DepartmentError> {
+        granter.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(&env, &org, &granter) {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+        let admins_key = StorageKey::OrgAdmins(org_id);
+        let mut admins: Vec<Address> = env
             .storage()
             .persistent()
-            .get::<_, u128>(&StorageKey::EmployeeDepartment(employee.clone(), org_id))
-        {
-            Self::remove_employee_from_dept_internal(&env, old_dept, &employee);
+            .get(&admins_key)
+            .unwrap_or(Vec::new(&env));
+        let admin_index = admins.first_index_of(&grantee);
+        if (role == Role::Admin || admin_index.is_some()) && org.owner != granter {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+        if grantee == org.owner {
+            return Err(DepartmentError::InvalidRole);
+        }
+        match (role, scope_department) {
+            (Role::Manager, Some(scope)) => {
+                let dept: Department =
+                    Self::load_department(&env, scope).ok_or(DepartmentError::DeptNotFound)?;
+                if dept.org_id != org_id {
+                    return Err(DepartmentError::DeptNotInOrg);
+                }
+            }
+            (Role::Admin, None) | (Role::Viewer, None) => {}
+            _ => return Err(DepartmentError::InvalidRole),
         }
 
-        env.storage().persistent().set(
-            &StorageKey::EmployeeInDepartment(department_id, employee.clone()),
-            &(),
-        );
-        env.storage().persistent().set(
-            &StorageKey::EmployeeDepartment(employee.clone(), org_id),
-            &department_id,
-        );
-
-        let mut employees: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::DepartmentEmployees(department_id))
-            .unwrap_or_else(|| Vec::new(&env));
-        employees.push_back(employee.clone());
-        env.storage()
-            .persistent()
-            .set(&StorageKey::DepartmentEmployees(department_id), &employees);
+        let grant = RoleGrant {
+            role,
+            scope_department,
+        };
+        if role == Role::Admin {
+            if admin_index.is_none() {
+                if admins.len() >= MAX_ORG_ADMINS {
+                    return Err(DepartmentError::AdminLimitReached);
+                }
+                admins.push_back(grantee.clone());
+                env.storage().persistent().set(&admins_key, &admins);
+            }
+            Self::remove_role_grant(&env, org_id, &grantee);
+        } else {
+            Self::set_role_grant(&env, org_id, &grantee, &grant)?;
+            if let Some(idx) = admin_index {
+                admins.remove(idx);
+                env.storage().persistent().set(&admins_key, &admins);
+            }
+        }
 
         env.events()
-            .publish((symbol_short!("emp_asgnd"), department_id), employee);
+            .publish((symbol_short!("role_set"), org_id), (grantee, grant));
+        Ok(())
     }
 
-    /// Removes (un-assigns) an employee from their current department in an org.
-    ///
-    /// After calling this, `get_employee_department` returns `None` for the
-    /// employee in that org.
+    /// Revokes the role of `grantee` in an organization. Its rights end
+    /// immediately.
     ///
     /// # Arguments
-    /// * `caller`   - Must be the **org owner** (must authenticate).
-    /// * `org_id`   - Organization ID.
-    /// * `employee` - Employee address to remove.
+    /// * `granter` - The **org owner** or an org admin (must authenticate).
+    ///               Only the owner may revoke an admin.
+    /// * `org_id`  - Organization ID.
+    /// * `grantee` - Address losing its role.
     ///
-    /// # Panics
-    /// - `"Organization not found"` – org_id does not exist.
-    /// - `"Not organization owner"` – caller is not the org owner.
-    /// - `"Employee not assigned in this org"` – employee has no assignment.
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – granter is neither the org owner nor an org admin,
+    ///   or an admin revokes an admin.
+    /// - `NoRole` – grantee holds no revocable role.
     ///
     /// # Events
-    /// Publishes `("emp_rmvd", employee)` on success.
-    pub fn remove_employee_from_department(
+    /// Publishes `("role_rvkd", org_id)` with the grantee.
+    pub fn revoke_role(
         env: Env,
-        caller: Address,
+        granter: Address,
         org_id: u128,
-        employee: Address,
-    ) {
-        caller.require_auth();
-        Self::require_initialized(&env);
-        let org: Organization = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::Organization(org_id))
-            .expect("Organization not found");
-        assert!(org.owner == caller, "Not organization owner");
+        grantee: Address,
+    ) -> Result<(), DepartmentError> {
+        granter.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(&env, &org, &granter) {
+            return Err(DepartmentError::NotOrgOwner);
+        }
 
-        let dept_id: u128 = env
+        let admins_key = StorageKey::OrgAdmins(org_id);
+        let mut admins: Vec<Address> = env
             .storage()
             .persistent()
-            .get::<_, u128>(&StorageKey::EmployeeDepartment(employee.clone(), org_id))
-            .expect("Employee not assigned in this org");
-
-        Self::remove_employee_from_dept_internal(&env, dept_id, &employee);
-
-        env.storage()
-            .persistent()
-            .remove(&StorageKey::EmployeeDepartment(employee.clone(), org_id));
+            .get(&admins_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(idx) = admins.first_index_of(&grantee) {
+            if org.owner != granter {
+                return Err(DepartmentError::NotOrgOwner);
+            }
+            admins.remove(idx);
+            env.storage().persistent().set(&admins_key, &admins);
+        } else {
+            if !env
+                .storage()
+                .persistent()
+                .has(&StorageKey::OrgRole(org_id, grantee.clone()))
+            {
+                return Err(DepartmentError::NoRole);
+            }
+            Self::remove_role_grant(&env, org_id, &grantee);
+        }
 
         env.events()
-            .publish((symbol_short!("emp_rmvd"), dept_id), employee);
+            .publish((symbol_short!("role_rvkd"), org_id), grantee);
+        Ok(())
     }
 
-    // -------------------------------------------------------------------------
-    // Reporting (read-only, no auth required)
-    // -------------------------------------------------------------------------
-
-    /// Returns the list of employee addresses assigned to a department.
+    /// Returns the role of `address` in an organization: `Owner` for the org
+    /// owner, `Admin` for org admins, otherwise the granted role, if any.
+    /// Returns `None` for unknown orgs.
     ///
     /// # Arguments
-    /// * `department_id` - The department ID.
-    pub fn get_department_employees(env: Env, department_id: u128) -> Vec<Address> {
+    /// * `org_id`  - The organization ID.
+    /// * `address` - Address to check.
+    pub fn get_role(env: Env, org_id: u128, address: Address) -> Option<RoleGrant> {
+        let org: Organization = Self::load_org(&env, org_id)?;
+        if org.owner == address {
+            return Some(RoleGrant {
+                role: Role::Owner,
+                scope_department: None,
+            });
+        }
+        if Self::is_admin(&env, org_id, &address) {
+            return Some(RoleGrant {
+                role: Role::Admin,
+                scope_department: None,
+            });
+        }
         env.storage()
             .persistent()
-            .get(&StorageKey::DepartmentEmployees(department_id))
-            .unwrap_or_else(|| Vec::new(&env))
+            .get(&StorageKey::OrgRole(org_id, address))
     }
 
-    /// Returns the department ID the employee is currently in within an org,
-    /// or `None` if they are not assigned to any department in that org.
+    /// Sets the deepest department depth allowed in an organization. Existing
+    /// deeper departments are kept, but nothing new can be created or moved
+    /// below the limit.
     ///
     /// # Arguments
-    /// * `employee` - Employee address.
-    /// * `org_id`   - Organization ID.
-    pub fn get_employee_department(env: Env, employee: Address, org_id: u128) -> Option<u128> {
+    /// * `caller`    - Must be the **org owner** or an org admin
+    ///                 (must authenticate).
+    /// * `org_id`    - Organization ID.
+    /// * `max_depth` - New limit, `0` (top-level only) to `MAX_DEPTH`.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `InvalidMaxDepth` – max_depth is above `MAX_DEPTH`.
+    ///
+    /// # Events
+    /// Publishes `("depth_set", org_id)` with the new limit.
+    pub fn set_org_max_depth(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        max_depth: u32,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(&env, &org, &caller) {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+        if max_depth > MAX_DEPTH {
+            return Err(DepartmentError::InvalidMaxDepth);
+        }
+
+        let mut settings = Self::org_settings(&env, org_id);
+        settings.max_depth = max_depth;
         env.storage()
             .persistent()
-            .get(&StorageKey::EmployeeDepartment(employee, org_id))
+            .set(&StorageKey::OrgSettings(org_id), &settings);
+
+        env.events()
+            .publish((symbol_short!("depth_set"), org_id), max_depth);
+        Ok(())
     }
 
-    /// Returns a department-level report:
-    /// `(employee_count, child_department_ids, employee_addresses)`.
+    /// Returns the settings of an organization, with defaults for anything
+    /// never set.
     ///
     /// # Arguments
-    /// * `department_id` - The department ID.
-    pub fn get_department_report(env: Env, department_id: u128) -> (u32, Vec<u128>, Vec<Address>) {
-        let employees: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::DepartmentEmployees(department_id))
-            .unwrap_or_else(|| Vec::new(&env));
-        let children: Vec<u128> = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::DepartmentChildren(department_id))
-            .unwrap_or_else(|| Vec::new(&env));
-        (employees.len(), children, employees)
+    /// * `org_id` - The organization ID.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    pub fn get_org_settings(env: Env, org_id: u128) -> Result<OrgSettings, DepartmentError> {
+        if Self::load_org(&env, org_id).is_none() {
+            return Err(DepartmentError::OrgNotFound);
+        }
+        Ok(Self::org_settings(&env, org_id))
     }
 
-    /// Reparents a department to a new parent (or makes it top-level).
+    /// Deletes an organization together with all of its departments, their
+    /// budgets and the org's role grants, e.g. a test or abandoned org. Every
+    /// department must be empty; remove the employees first.
+    ///
+    /// Only entries known to exist are touched: optional department entries
+    /// are listed in `DepartmentKeys`, budgets in `OrgBudgets` and role
+    /// grants in `OrgRoleHolders`.
     ///
     /// # Arguments
-    /// * `caller`     - Must be the **org owner** (must authenticate).
-    /// * `dept_id`    - Department to move.
-    /// * `new_parent` - `Some(parent_dept_id)` or `None` for top-level.
+    /// * `owner`  - Must be the **org owner** (must authenticate); org admins
+    ///              may not delete the org.
+    /// * `org_id` - Organization to delete.
+    ///
+    /// # Returns
+    /// The number of storage entries removed.
     ///
-    /// # Panics
-    /// - `"Organization not found"` – org not found.
-    /// - `"Not organization owner"` – caller is not the org owner.
-    /// - `"Department not found"` – dept_id does not exist.
-    /// - `"Parent department not found"` – new_parent does not exist.
-    /// - `"Parent must be in same org"` – new parent is in a different org.
-    /// - `"Max hierarchy depth exceeded"` – new depth would exceed MAX_DEPTH.
-    /// - `"Cycle detected"` – new parent is a descendant of dept_id.
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – caller is not the org owner.
+    /// - `OrgTooLarge` – the org has more than `MAX_ORG_DELETE_DEPARTMENTS`
+    ///   departments or `MAX_ORG_DELETE_ENTRIES` entries; delete some
+    ///   departments with `delete_department` or revoke roles first.
+    /// - `DeptHasEmployees` – a department still has employees.
     ///
     /// # Events
-    /// Publishes `("dept_mvd", dept_id)` on success.
-    pub fn update_department(
+    /// Publishes `("org_del", org_id)` with the number of removed entries.
+    pub fn delete_organization(
         env: Env,
-        caller: Address,
-        dept_id: u128,
-        new_parent: Option<u128>,
-    ) {
-        caller.require_auth();
-        Self::require_initialized(&env);
-
-        let mut dept: Department = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::Department(dept_id))
-            .expect("Department not found");
-
-        let org: Organization = env
-            .storage()
-            .persistent()
-            .get(&StorageKey::Organization(dept.org_id))
-            .expect("Organization not found");
-        assert!(org.owner == caller, "Not organization owner");
+        owner: Address,
+        org_id: u128,
+    ) -> Result<u32, DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if org.owner != owner {
+            return Err(DepartmentError::NotOrgOwner);
+        }
 
-        if let Some(pid) = new_parent {
-            let parent: Department = env
-                .storage()
-                .persistent()
-                .get(&StorageKey::Department(pid))
-                .expect("Parent department not found");
-            assert!(parent.org_id == dept.org_id, "Parent must be in same org");
-            // child depth = parent depth + 1; must not exceed MAX_DEPTH
-            assert!(Self::dept_depth(&env, pid) + 1 <= MAX_DEPTH, "Max hierarchy depth exceeded");
-            assert!(!Self::has_cycle(&env, dept_id, pid), "Cycle detected");
+        let mut keys: Vec<StorageKey> = Vec::new(&env);
+        let org_key = StorageKey::OrganizationV2(org_id);
+        if env.storage().persistent().has(&org_key) {
+            keys.push_back(org_key);
+        } else {
+            keys.push_back(StorageKey::Organization(org_id));
         }
 
-        // Remove dept_id from old parent's children list
-        if let Some(old_pid) = dept.parent_id {
-            let mut old_children: Vec<u128> = env
-                .storage()
-                .persistent()
-                .get(&StorageKey::DepartmentChildren(old_pid))
-                .unwrap_or_else(|| Vec::new(&env));
-            let mut i = 0u32;
-            while i < old_children.len() {
-                if old_children.get(i) == Some(dept_id) {
-                    old_children.remove(i);
-                    break;
+        let departments_key = StorageKey::OrgDepartments(org_id);
+        let departments: Option<Vec<u128>> = env.storage().persistent().get(&departments_key);
+        if let Some(departments) = departments {
+            if departments.len() > MAX_ORG_DELETE_DEPARTMENTS {
+                return Err(DepartmentError::OrgTooLarge);
+            }
+            for id in departments.iter() {
+                let employees_key = StorageKey::DepartmentEmployees(id);
+                let employees: Option<Vec<Address>> =
+                    env.storage().persistent().get(&employees_key);
+                if let Some(employees) = employees {
+                    if !employees.is_empty() {
+                        return Err(DepartmentError::DeptHasEmployees);
+                    }
+                    keys.push_back(employees_key);
                 }
-                i += 1;
+                Self::push_department_entries(&env, id, &mut keys);
             }
-            env.storage()
-                .persistent()
-                .set(&StorageKey::DepartmentChildren(old_pid), &old_children);
+            keys.push_back(departments_key);
         }
 
-        // Add dept_id to new parent's children list
-        if let Some(pid) = new_parent {
-            let mut new_children: Vec<u128> = env
-                .storage()
-                .persistent()
-                .get(&StorageKey::DepartmentChildren(pid))
-                .unwrap_or_else(|| Vec::new(&env));
-            new_children.push_back(dept_id);
-            env.storage()
-                .persistent()
-                .set(&StorageKey::DepartmentChildren(pid), &new_children);
+        let budgets_key = StorageKey::OrgBudgets(org_id);
+        let budgets: Option<Vec<(u128, Address)>> = env.storage().persistent().get(&budgets_key);
+        if let Some(budgets) = budgets {
+            for (id, token) in budgets.iter() {
+                keys.push_back(StorageKey::DepartmentBudget(id, token));
+            }
+            keys.push_back(budgets_key);
+        }
+        let holders_key = StorageKey::OrgRoleHolders(org_id);
+        let holders: Option<Vec<Address>> = env.storage().persistent().get(&holders_key);
+        if let Some(holders) = holders {
+            for holder in holders.iter() {
+                keys.push_back(StorageKey::OrgRole(org_id, holder));
+            }
+            keys.push_back(holders_key);
+        }
+        // A fixed handful of org-wide entries, probed once per org.
+        for key in [
+            StorageKey::OrgEmployees(org_id),
+            StorageKey::OrgAdmins(org_id),
+            StorageKey::OrgSettings(org_id),
+            StorageKey::PendingOrgOwner(org_id),
+            StorageKey::PayrollContract(org_id),
+        ] {
+            if env.storage().persistent().has(&key) {
+                keys.push_back(key);
+            }
         }
 
-        dept.parent_id = new_parent;
-        env.storage()
-            .persistent()
-            .set(&StorageKey::Department(dept_id), &dept);
+        if keys.len() > MAX_ORG_DELETE_ENTRIES {
+            return Err(DepartmentError::OrgTooLarge);
+        }
+        for key in keys.iter() {
+            env.storage().persistent().remove(&key);
+        }
 
+        let removed = keys.len();
         env.events()
-            .publish((symbol_short!("dept_mvd"), dept_id), dept_id);
+            .publish((symbol_short!("org_del"), org_id), removed);
+        Ok(removed)
     }
 
     // -------------------------------------------------------------------------
-    // Internal helpers
+    // Departments (Org Owner operations)
     // -------------------------------------------------------------------------
 
-    /// Returns the depth of `dept_id` in the hierarchy (root = 0).
-    fn dept_depth(env: &Env, dept_id: u128) -> u32 {
-        let mut depth = 0u32;
-        let mut current = dept_id;
-        loop {
-            let dept: Department = match env
-                .storage()
-                .persistent()
-                .get(&StorageKey::Department(current))
-            {
-                Some(d) => d,
-                None => break,
-            };
-            match dept.parent_id {
-                None => break,
-                Some(pid) => {
-                    depth += 1;
-                    current = pid;
-                }
-            }
+    /// Creates a department under an organization.
+    ///
+    /// The department can be:
+    /// - **Top-level**: `parent_id = None`
+    /// - **Nested**: `parent_id = Some(parent_dept_id)` (parent must be in same org)
+    ///
+    /// # Arguments
+    /// * `caller`    - The **org owner**, an org admin, or a `Manager` whose
+    ///                 scope contains `parent_id` (must authenticate).
+    /// * `org_id`    - Organization ID.
+    /// * `name`      - Name of the department, 1 to `MAX_NAME_LENGTH` bytes
+    ///                 and unique among the org's active departments.
+    /// * `parent_id` - Optional parent department ID; `None` for top-level.
+    ///
+    /// # Returns
+    /// The new department ID (global counter, starts at 1).
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner, an org admin nor a
+    ///   manager of parent_id.
+    /// - `ParentNotFound` – parent_id does not exist.
+    /// - `ParentNotInOrg` – parent belongs to a different org.
+    /// - `MaxDepthExceeded` – the department would be deeper than the org's
+    ///   `max_depth`.
+    /// - `DeptArchived` – the parent is archived.
+    /// - `DuplicateDepartmentName` – an active department in the org already
+    ///   uses `name`.
+    /// - `InvalidName` – name is empty or too long.
+    ///
+    /// # Events
+    /// Publishes `("dept_crtd", dept_id)` with a `DepartmentCreatedEvent`.
+    pub fn create_department(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        name: String,
+        parent_id: Option<u128>,
+    ) -> Result<u128, DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(&env, &org, &caller)
+            && !parent_id.is_some_and(|p| Self::in_manager_scope(&env, org_id, &caller, p))
+        {
+            return Err(DepartmentError::NotOrgOwner);
         }
-        depth
+
+        Self::create_department_internal(&env, caller, org_id, name, parent_id)
     }
 
-    /// Returns `true` if making `candidate` the parent of `dept_id` would
-    /// create a cycle (i.e., `candidate` is already a descendant of `dept_id`).
-    fn has_cycle(env: &Env, dept_id: u128, candidate: u128) -> bool {
-        // Walk up from candidate; if we reach dept_id, it's a cycle.
-        let mut current = candidate;
-        loop {
-            if current == dept_id {
-                return true;
-            }
-            let dept: Department = match env
-                .storage()
-                .persistent()
-                .get(&StorageKey::Department(current))
-            {
-                Some(d) => d,
-                None => return false,
+    /// Creates several departments of an organization in one call, e.g. to
+    /// set up a new org. Each spec is `(name, parent_index)`, where
+    /// `parent_index` is the position of an earlier spec in the same call,
+    /// or `None` for a top-level department. All departments are created or
+    /// none is.
+    ///
+    /// # Arguments
+    /// * `caller` - Must be the **org owner** or an org admin
+    ///              (must authenticate).
+    /// * `org_id` - Organization ID.
+    /// * `specs`  - 1 to `MAX_BULK_DEPARTMENTS` `(name, parent_index)` pairs.
+    ///
+    /// # Returns
+    /// The new department IDs, in the order of `specs`.
+    ///
+    /// # Errors
+    /// - `InvalidBatchSize` – specs is empty or longer than
+    ///   `MAX_BULK_DEPARTMENTS`.
+    /// - `InvalidParentIndex` – a parent_index does not point to an earlier
+    ///   spec.
+    /// - Any `create_department` error.
+    ///
+    /// # Events
+    /// Publishes `("dept_crtd", dept_id)` for each department.
+    pub fn create_departments_bulk(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        specs: Vec<(String, Option<u32>)>,
+    ) -> Result<Vec<u128>, DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(&env, &org, &caller) {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+        if specs.is_empty() || specs.len() > MAX_BULK_DEPARTMENTS {
+            return Err(DepartmentError::InvalidBatchSize);
+        }
+
+        let mut ids: Vec<u128> = Vec::new(&env);
+        for (name, parent_index) in specs.iter() {
+            let parent_id = match parent_index {
+                Some(i) => Some(ids.get(i).ok_or(DepartmentError::InvalidParentIndex)?),
+                None => None,
             };
-            match dept.parent_id {
-                None => return false,
-                Some(pid) => current = pid,
-            }
+            let id =
+                Self::create_department_internal(&env, caller.clone(), org_id, name, parent_id)?;
+            ids.push_back(id);
         }
+        Ok(ids)
     }
 
-    /// Removes an employee from a department's employee list and membership flag.
-    /// Does NOT update `EmployeeDepartment` – caller must handle that.
-    fn remove_employee_from_dept_internal(env: &Env, department_id: u128, employee: &Address) {
+    /// Returns the department record, or `None` if the ID does not exist.
+    /// The cost-center code is returned by `get_cost_center`.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department(env: Env, department_id: u128) -> Option<Department> {
+        Self::load_department(&env, department_id)
+    }
+
+    /// Returns the active department of an organization with the given name.
+    /// Archived and deleted departments are not found.
+    ///
+    /// # Arguments
+    /// * `org_id` - The organization ID.
+    /// * `name`   - Department name.
+    pub fn get_department_by_name(env: Env, org_id: u128, name: String) -> Option<u128> {
         env.storage()
             .persistent()
-            .remove(&StorageKey::EmployeeInDepartment(
-                department_id,
-                employee.clone(),
-            ));
+            .get(&StorageKey::DeptNameIndexV2(org_id, name))
+    }
 
-        let mut employees: Vec<Address> = env
-            .storage()
+    /// Returns all department IDs (top-level and nested) under an organization.
+    ///
+    /// # Arguments
+    /// * `org_id` - The organization ID.
+    pub fn get_org_departments(env: Env, org_id: u128) -> Vec<u128> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::OrgDepartments(org_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the direct child department IDs of a given department.
+    ///
+    /// # Arguments
+    /// * `department_id` - The parent department ID.
+    pub fn get_child_departments(env: Env, department_id: u128) -> Vec<u128> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentChildren(department_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns the chain of department IDs from the top-level ancestor down
+    /// to `department_id` (inclusive), e.g. for breadcrumbs. A top-level
+    /// department yields a path of length 1.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id or an ancestor does not exist.
+    /// - `CorruptedHierarchy` – the chain is longer than `MAX_DEPTH + 1`.
+    pub fn get_department_path(
+        env: Env,
+        department_id: u128,
+    ) -> Result<Vec<u128>, DepartmentError> {
+        let mut path: Vec<u128> = Vec::new(&env);
+        let mut current = Some(department_id);
+        while let Some(id) = current {
+            if path.len() > MAX_DEPTH {
+                return Err(DepartmentError::CorruptedHierarchy);
+            }
+            let dept: Department =
+                Self::load_department(&env, id).ok_or(DepartmentError::DeptNotFound)?;
+            path.push_front(id);
+            current = dept.parent_id;
+        }
+        Ok(path)
+    }
+
+    /// Returns the depth of a department (top-level = 0).
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    ///
+    /// # Errors
+    /// Same as `get_department_path`.
+    pub fn get_department_depth(env: Env, department_id: u128) -> Result<u32, DepartmentError> {
+        Ok(Self::get_department_path(env, department_id)?.len() - 1)
+    }
+
+    // -------------------------------------------------------------------------
+    // Employee Assignment (Org Owner operations)
+    // -------------------------------------------------------------------------
+
+    /// Assigns an employee address to a department within an organization.
+    ///
+    /// If the employee is already assigned to another department in the same
+    /// org, they are **automatically removed** from the old department first
+    /// (re-assignment/move semantics).
+    ///
+    /// # Arguments
+    /// * `caller`        - The **org owner**, an org admin, or a manager of
+    ///                     the target department and of the employee's
+    ///                     current one (must authenticate).
+    /// * `org_id`        - Organization ID.
+    /// * `department_id` - Target department ID (must belong to `org_id`).
+    /// * `employee`      - Employee address to assign.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `DeptNotInOrg` – dept belongs to a different org.
+    /// - `DeptArchived` – the department no longer accepts employees.
+    /// - `NotOrgOwnerOrManager` – caller may not
+    ///   manage the target or current department.
+    /// - `DepartmentFull` – the department has reached its capacity.
+    ///
+    /// Assigning an employee to the department they are already in is a
+    /// no-op.
+    ///
+    /// # Events
+    /// Publishes `("emp_asgnd", department_id)` with an `EmployeeAssignedEvent`.
+    pub fn assign_employee_to_department(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        department_id: u128,
+        employee: Address,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+
+        let dept: Department =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        if dept.org_id != org_id {
+            return Err(DepartmentError::DeptNotInOrg);
+        }
+        if Self::is_archived(&env, department_id) {
+            return Err(DepartmentError::DeptArchived);
+        }
+        Self::require_can_manage(&env, &caller, &org, department_id)?;
+
+        Self::assign_employee_internal(&env, &caller, &org, department_id, employee)?;
+        Ok(())
+    }
+
+    /// Assigns several employees to one department in a single atomic call.
+    ///
+    /// The department is validated once; each employee then gets the same
+    /// move semantics as `assign_employee_to_department`. Any failure reverts
+    /// the whole batch.
+    ///
+    /// # Arguments
+    /// * `caller`        - Same rights as for `assign_employee_to_department`.
+    /// * `org_id`        - Organization ID.
+    /// * `department_id` - Target department ID (must belong to `org_id`).
+    /// * `employees`     - 1 to `MAX_BATCH_SIZE` employee addresses.
+    ///
+    /// # Errors
+    /// - `InvalidBatchSize` – `employees` is empty or longer than `MAX_BATCH_SIZE`.
+    /// - Any error of `assign_employee_to_department`.
+    ///
+    /// # Events
+    /// Publishes `("emp_asgnd", department_id)` with an `EmployeeAssignedEvent`
+    /// per employee.
+    pub fn assign_employees_batch(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        department_id: u128,
+        employees: Vec<Address>,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        if employees.is_empty() || employees.len() > MAX_BATCH_SIZE {
+            return Err(DepartmentError::InvalidBatchSize);
+        }
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+
+        let dept: Department =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        if dept.org_id != org_id {
+            return Err(DepartmentError::DeptNotInOrg);
+        }
+        if Self::is_archived(&env, department_id) {
+            return Err(DepartmentError::DeptArchived);
+        }
+        Self::require_can_manage(&env, &caller, &org, department_id)?;
+
+        for employee in employees.iter() {
+            Self::assign_employee_internal(&env, &caller, &org, department_id, employee)?;
+        }
+        Ok(())
+    }
+
+    /// Removes (un-assigns) an employee from their current department in an org.
+    ///
+    /// After calling this, `get_employee_department` returns `None` for the
+    /// employee in that org.
+    ///
+    /// # Arguments
+    /// * `caller`   - The **org owner**, an org admin, or a manager of the
+    ///                employee's current department (must authenticate).
+    /// * `org_id`   - Organization ID.
+    /// * `employee` - Employee address to remove.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `EmployeeNotFound` – employee has no assignment.
+    /// - `NotOrgOwnerOrManager` – caller may not
+    ///   manage the employee's department.
+    ///
+    /// # Events
+    /// Publishes `("emp_rmvd", dept_id)` with an `EmployeeRemovedEvent`.
+    pub fn remove_employee_from_department(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        employee: Address,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+
+        let dept_id: u128 = env
+            .storage()
+            .persistent()
+            .get::<_, u128>(&StorageKey::EmployeeDepartment(employee.clone(), org_id))
+            .ok_or(DepartmentError::EmployeeNotFound)?;
+        Self::require_can_manage(&env, &caller, &org, dept_id)?;
+
+        Self::remove_employee_from_org_internal(&env, caller, org_id, dept_id, employee);
+        Ok(())
+    }
+
+    /// Moves an employee from their department in one org to a department of
+    /// another org, e.g. between companies of a group, and records the
+    /// transfer in the employee's history.
+    ///
+    /// The employee leaves the source org as with
+    /// `remove_employee_from_department` (profile and status are dropped)
+    /// and joins the destination as with `assign_employee_to_department`.
+    ///
+    /// # Arguments
+    /// * `caller`           - The **org owner** or an org admin of **both**
+    ///                        orgs (must authenticate).
+    /// * `from_org_id`      - Source organization ID.
+    /// * `to_org_id`        - Destination organization ID.
+    /// * `to_department_id` - Destination department (must belong to `to_org_id`).
+    /// * `employee`         - Employee address.
+    ///
+    /// # Errors
+    /// - `SameOrgTransfer` – both org IDs are equal.
+    /// - `OrgNotFound` – either org does not exist.
+    /// - `NotOrgOwner` – caller is neither the owner nor an admin of both orgs.
+    /// - `EmployeeNotFound` – employee has no assignment in the source org.
+    /// - `DeptNotFound` / `DeptNotInOrg` / `DeptArchived` / `DepartmentFull` –
+    ///   the destination department cannot take the employee.
+    ///
+    /// # Events
+    /// Publishes `("emp_rmvd", from_dept)` and `("emp_asgnd", to_dept)` as for
+    /// removal and assignment, then `("emp_xfer", from_org_id)` with
+    /// `(employee, TransferRecord)`.
+    pub fn transfer_employee(
+        env: Env,
+        caller: Address,
+        from_org_id: u128,
+        to_org_id: u128,
+        to_department_id: u128,
+        employee: Address,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        if from_org_id == to_org_id {
+            return Err(DepartmentError::SameOrgTransfer);
+        }
+        let from_org: Organization =
+            Self::load_org(&env, from_org_id).ok_or(DepartmentError::OrgNotFound)?;
+        let to_org: Organization =
+            Self::load_org(&env, to_org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(&env, &from_org, &caller)
+            || !Self::is_owner_or_admin(&env, &to_org, &caller)
+        {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+
+        let from_department_id: u128 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::EmployeeDepartment(
+                employee.clone(),
+                from_org_id,
+            ))
+            .ok_or(DepartmentError::EmployeeNotFound)?;
+        let to_dept: Department =
+            Self::load_department(&env, to_department_id).ok_or(DepartmentError::DeptNotFound)?;
+        if to_dept.org_id != to_org_id {
+            return Err(DepartmentError::DeptNotInOrg);
+        }
+        if Self::is_archived(&env, to_department_id) {
+            return Err(DepartmentError::DeptArchived);
+        }
+
+        Self::remove_employee_from_org_internal(
+            &env,
+            caller.clone(),
+            from_org_id,
+            from_department_id,
+            employee.clone(),
+        );
+        Self::assign_employee_internal(&env, &caller, &to_org, to_department_id, employee.clone())?;
+
+        let record = TransferRecord {
+            from_org_id,
+            from_department_id,
+            to_org_id,
+            to_department_id,
+            timestamp: env.ledger().timestamp(),
+        };
+        let key = StorageKey::EmployeeTransfers(employee.clone());
+        let mut history: Vec<TransferRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        history.push_back(record.clone());
+        if history.len() > MAX_TRANSFER_HISTORY {
+            history.pop_front();
+        }
+        env.storage().persistent().set(&key, &history);
+
+        env.events()
+            .publish((symbol_short!("emp_xfer"), from_org_id), (employee, record));
+        Ok(())
+    }
+
+    /// Returns up to `limit` of the employee's latest cross-org transfers,
+    /// newest first. At most `MAX_TRANSFER_HISTORY` are kept.
+    ///
+    /// # Arguments
+    /// * `employee` - Employee address.
+    /// * `limit`    - Maximum entries to return, capped at `MAX_PAGE_SIZE`.
+    pub fn get_employee_transfer_history(
+        env: Env,
+        employee: Address,
+        limit: u32,
+    ) -> Vec<TransferRecord> {
+        let history: Vec<TransferRecord> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::EmployeeTransfers(employee))
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut latest = Vec::new(&env);
+        for record in history.iter().rev().take(limit.min(MAX_PAGE_SIZE) as usize) {
+            latest.push_back(record);
+        }
+        latest
+    }
+
+    /// Creates or replaces the profile of an employee in an org.
+    ///
+    /// The profile is kept when the employee moves between departments and
+    /// deleted by `remove_employee_from_department`.
+    ///
+    /// # Arguments
+    /// * `caller`     - The **org owner**, an org admin, or a manager of the
+    ///                  employee's current department (must authenticate).
+    /// * `org_id`     - Organization ID.
+    /// * `employee`   - Employee address (must be assigned in the org).
+    /// * `title`      - Job title.
+    /// * `start_date` - Start date as a ledger timestamp.
+    /// * `metadata`   - Free-form data, e.g. a hash of off-chain details.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `EmployeeNotFound` – employee has no assignment in the org.
+    /// - `NotOrgOwnerOrManager` – caller may not manage the employee's department.
+    ///
+    /// # Events
+    /// Publishes `("prof_set", org_id)` with the employee address.
+    pub fn set_employee_profile(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        employee: Address,
+        title: soroban_sdk::Symbol,
+        start_date: u64,
+        metadata: Bytes,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        let dept_id: u128 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::EmployeeDepartment(employee.clone(), org_id))
+            .ok_or(DepartmentError::EmployeeNotFound)?;
+        Self::require_can_manage(&env, &caller, &org, dept_id)?;
+
+        let profile = EmployeeProfile {
+            title,
+            start_date,
+            metadata,
+        };
+        env.storage().persistent().set(
+            &StorageKey::EmployeeProfile(org_id, employee.clone()),
+            &profile,
+        );
+
+        env.events()
+            .publish((symbol_short!("prof_set"), org_id), employee);
+        Ok(())
+    }
+
+    /// Returns the profile of an employee in an org, if any.
+    ///
+    /// # Arguments
+    /// * `org_id`   - The organization ID.
+    /// * `employee` - The employee address.
+    pub fn get_employee_profile(
+        env: Env,
+        org_id: u128,
+        employee: Address,
+    ) -> Option<EmployeeProfile> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::EmployeeProfile(org_id, employee))
+    }
+
+    /// Marks an employee active or inactive (e.g. on leave) in an org
+    /// without changing their department. Employees are active by default.
+    ///
+    /// The status is kept when the employee moves between departments and
+    /// reset by `remove_employee_from_department`.
+    ///
+    /// # Arguments
+    /// * `caller`   - The **org owner**, an org admin, or a manager of the
+    ///                employee's current department (must authenticate).
+    /// * `org_id`   - Organization ID.
+    /// * `employee` - Employee address (must be assigned in the org).
+    /// * `active`   - New status.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `EmployeeNotFound` – employee has no assignment in the org.
+    /// - `NotOrgOwnerOrManager` – caller may not manage the employee's department.
+    ///
+    /// # Events
+    /// Publishes `("emp_stat", org_id)` with `(employee, active)`.
+    pub fn set_employee_status(
+        env: Env,
+        caller: Address,
+        org_id: u128,
+        employee: Address,
+        active: bool,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        let dept_id: u128 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::EmployeeDepartment(employee.clone(), org_id))
+            .ok_or(DepartmentError::EmployeeNotFound)?;
+        Self::require_can_manage(&env, &caller, &org, dept_id)?;
+
+        let key = StorageKey::EmployeeInactive(org_id, employee.clone());
+        if active {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &());
+        }
+
+        env.events()
+            .publish((symbol_short!("emp_stat"), org_id), (employee, active));
+        Ok(())
+    }
+
+    /// Returns `true` unless the employee has been marked inactive in the
+    /// org. Does not check that the employee is assigned.
+    ///
+    /// # Arguments
+    /// * `org_id`   - The organization ID.
+    /// * `employee` - The employee address.
+    pub fn is_employee_active(env: Env, org_id: u128, employee: Address) -> bool {
+        !env.storage()
+            .persistent()
+            .has(&StorageKey::EmployeeInactive(org_id, employee))
+    }
+
+    /// Sets (`Some`) or clears (`None`) the manager of a department.
+    ///
+    /// A manager may call `assign_employee_to_department` and
+    /// `remove_employee_from_department` for the department; with
+    /// `set_manager_includes_children` also for its sub-departments.
+    ///
+    /// # Arguments
+    /// * `owner`         - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department ID.
+    /// * `manager`       - New manager, or `None` to revoke.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `OrgNotFound` – the department's org does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    ///
+    /// # Events
+    /// Publishes `("mgr_set", department_id)` with the new manager.
+    pub fn set_department_manager(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        manager: Option<Address>,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+
+        let key = StorageKey::DepartmentManager(department_id);
+        match &manager {
+            Some(m) => Self::set_department_entry(&env, department_id, key, m),
+            None => Self::remove_department_entry(&env, department_id, key),
+        }
+
+        env.events()
+            .publish((symbol_short!("mgr_set"), department_id), manager);
+        Ok(())
+    }
+
+    /// Sets whether a department's manager also manages all of its
+    /// sub-departments. Defaults to `false`.
+    ///
+    /// # Arguments
+    /// * `owner`            - Must be the **org owner** or an org admin
+    ///                        (must authenticate).
+    /// * `department_id`    - Department ID.
+    /// * `include_children` - Whether rights extend to descendants.
+    ///
+    /// # Errors
+    /// Same as `set_department_manager`.
+    pub fn set_manager_includes_children(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        include_children: bool,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+
+        Self::set_department_entry(
+            &env,
+            department_id,
+            StorageKey::ManagerIncludesChildren(department_id),
+            &include_children,
+        );
+        Ok(())
+    }
+
+    /// Returns the manager of a department, if any.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_manager(env: Env, department_id: u128) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentManager(department_id))
+    }
+
+    /// Returns whether a department's manager also manages its sub-departments.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_manager_includes_children(env: Env, department_id: u128) -> bool {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::ManagerIncludesChildren(department_id))
+            .unwrap_or(false)
+    }
+
+    /// Designates the head of a department for reporting lines. Unlike a
+    /// manager, the head gets no permissions.
+    ///
+    /// The head is cleared automatically when it leaves the department.
+    ///
+    /// # Arguments
+    /// * `owner`         - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department ID.
+    /// * `head`          - An employee of the department.
+    ///
+    /// # Errors
+    /// - Same as `set_department_manager`.
+    /// - `NotDepartmentEmployee` – `head` is not assigned to the department.
+    ///
+    /// # Events
+    /// Publishes `("head_set", department_id)` with the head.
+    pub fn set_department_head(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        head: Address,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+        if !env
+            .storage()
+            .persistent()
+            .has(&StorageKey::EmployeeInDepartment(
+                department_id,
+                head.clone(),
+            ))
+        {
+            return Err(DepartmentError::NotDepartmentEmployee);
+        }
+
+        Self::set_department_entry(
+            &env,
+            department_id,
+            StorageKey::DepartmentHead(department_id),
+            &head,
+        );
+
+        env.events()
+            .publish((symbol_short!("head_set"), department_id), head);
+        Ok(())
+    }
+
+    /// Returns the head of a department, if any.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_head(env: Env, department_id: u128) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentHead(department_id))
+    }
+
+    /// Sets (`Some`) or clears (`None`) the maximum number of employees of a
+    /// department. Lowering it below the current headcount keeps everyone
+    /// but blocks new assignments until enough employees leave.
+    ///
+    /// # Arguments
+    /// * `owner`         - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department ID.
+    /// * `max_employees` - Capacity, or `None` for unlimited.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    ///
+    /// # Events
+    /// Publishes `("cap_set", department_id)` with the capacity.
+    pub fn set_department_capacity(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        max_employees: Option<u32>,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+
+        let key = StorageKey::DepartmentCapacity(department_id);
+        match max_employees {
+            Some(cap) => Self::set_department_entry(&env, department_id, key, &cap),
+            None => Self::remove_department_entry(&env, department_id, key),
+        }
+
+        env.events()
+            .publish((symbol_short!("cap_set"), department_id), max_employees);
+        Ok(())
+    }
+
+    /// Returns the employee capacity of a department, or `None` if unlimited.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_capacity(env: Env, department_id: u128) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentCapacity(department_id))
+    }
+
+    // -------------------------------------------------------------------------
+    // Reporting (read-only, no auth required)
+    // -------------------------------------------------------------------------
+
+    /// Returns the list of employee addresses assigned to a department.
+    ///
+    /// Returns the whole list in one value, so it is only suitable for small
+    /// departments; use `get_department_employees_page` otherwise.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_employees(env: Env, department_id: u128) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployees(department_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns a page of the employees assigned to a department, in
+    /// assignment order.
+    ///
+    /// Only the size of the result is bounded: the roster is stored as a
+    /// single `DepartmentEmployees` entry, so every page still loads the whole
+    /// list.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    /// * `start`         - Index of the first entry to return.
+    /// * `limit`         - Maximum entries to return, capped at `MAX_PAGE_SIZE`.
+    pub fn get_department_employees_page(
+        env: Env,
+        department_id: u128,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        let employees = Self::get_department_employees(env.clone(), department_id);
+        if start >= employees.len() {
+            return Vec::new(&env);
+        }
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(employees.len());
+        employees.slice(start..end)
+    }
+
+    /// Returns the number of employees assigned to a department.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_employee_count(env: Env, department_id: u128) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployeeCount(department_id))
+            .unwrap_or(0)
+    }
+
+    /// Returns the department ID the employee is currently in within an org,
+    /// or `None` if they are not assigned to any department in that org.
+    ///
+    /// # Arguments
+    /// * `employee` - Employee address.
+    /// * `org_id`   - Organization ID.
+    pub fn get_employee_department(env: Env, employee: Address, org_id: u128) -> Option<u128> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::EmployeeDepartment(employee, org_id))
+    }
+
+    /// Returns a page of every employee assigned to a department of the org,
+    /// each listed once in order of first assignment.
+    ///
+    /// # Arguments
+    /// * `org_id` - The organization ID.
+    /// * `start`  - Index of the first entry to return.
+    /// * `limit`  - Maximum entries to return, capped at `MAX_PAGE_SIZE`.
+    pub fn get_org_employees(env: Env, org_id: u128, start: u32, limit: u32) -> Vec<Address> {
+        let roster: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::OrgEmployees(org_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        if start >= roster.len() {
+            return Vec::new(&env);
+        }
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(roster.len());
+        roster.slice(start..end)
+    }
+
+    /// Returns the organizations in which the employee is assigned to a
+    /// department, in order of first assignment.
+    ///
+    /// # Arguments
+    /// * `employee` - Employee address.
+    pub fn get_employee_organizations(env: Env, employee: Address) -> Vec<u128> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::EmployeeOrgs(employee))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns a department-level report: headcount split by status, direct
+    /// children, employees, head, capacity and archived flag.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_report(env: Env, department_id: u128) -> DepartmentReport {
+        let employees: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployees(department_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        let children: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentChildren(department_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        let head: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentHead(department_id));
+        let mut inactive = 0u32;
+        let dept: Option<Department> = Self::load_department(&env, department_id);
+        if let Some(dept) = dept {
+            for employee in employees.iter() {
+                if env
+                    .storage()
+                    .persistent()
+                    .has(&StorageKey::EmployeeInactive(dept.org_id, employee))
+                {
+                    inactive += 1;
+                }
+            }
+        }
+        let capacity: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentCapacity(department_id));
+        DepartmentReport {
+            employee_count: employees.len(),
+            active_count: employees.len() - inactive,
+            inactive_count: inactive,
+            child_department_ids: children,
+            remaining_slots: capacity.map(|cap| cap.saturating_sub(employees.len())),
+            employees,
+            head,
+            capacity,
+            archived: Self::is_archived(&env, department_id),
+        }
+    }
+
+    /// Returns a rollup report over a department and its sub-departments:
+    /// `(total_employees, departments_included)`.
+    ///
+    /// Departments are visited breadth-first, down to `max_depth` levels
+    /// below `department_id` (capped at `MAX_REPORT_DEPTH`) and at most
+    /// `MAX_REPORT_DEPARTMENTS` in total. Callers can compare
+    /// `departments_included` with the hierarchy to detect truncation.
+    ///
+    /// # Arguments
+    /// * `department_id` - Root of the rollup.
+    /// * `max_depth`     - Levels to descend; `0` reports the department alone.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    pub fn get_department_report_recursive(
+        env: Env,
+        department_id: u128,
+        max_depth: u32,
+    ) -> Result<(u32, Vec<u128>), DepartmentError> {
+        if Self::load_department(&env, department_id).is_none() {
+            return Err(DepartmentError::DeptNotFound);
+        }
+        let max_depth = max_depth.min(MAX_REPORT_DEPTH);
+
+        let mut included: Vec<u128> = Vec::new(&env);
+        included.push_back(department_id);
+        let mut total = 0u32;
+        let mut depth = 0u32;
+        let mut level_end = 1u32;
+        let mut i = 0u32;
+        while i < included.len() {
+            if i == level_end {
+                depth += 1;
+                level_end = included.len();
+            }
+            let id = included.get(i).unwrap();
+            let employees: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentEmployees(id))
+                .unwrap_or_else(|| Vec::new(&env));
+            total += employees.len();
+
+            if depth < max_depth {
+                let children: Vec<u128> = env
+                    .storage()
+                    .persistent()
+                    .get(&StorageKey::DepartmentChildren(id))
+                    .unwrap_or_else(|| Vec::new(&env));
+                for child in children.iter() {
+                    if included.len() >= MAX_REPORT_DEPARTMENTS {
+                        break;
+                    }
+                    included.push_back(child);
+                }
+            }
+            i += 1;
+        }
+        Ok((total, included))
+    }
+
+    /// Returns a department and its descendants as full records, e.g. to
+    /// draw an org chart in one call: `(departments, truncated)`.
+    ///
+    /// Departments are visited breadth-first starting with `department_id`
+    /// itself, so every parent precedes its children. `truncated` is `true`
+    /// when descendants were left out because of the node limit.
+    ///
+    /// # Arguments
+    /// * `department_id` - Root of the subtree.
+    /// * `max_nodes`     - Maximum departments to return, capped at
+    ///                     `MAX_SUBTREE_NODES`.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    pub fn get_department_subtree(
+        env: Env,
+        department_id: u128,
+        max_nodes: u32,
+    ) -> Result<(Vec<Department>, bool), DepartmentError> {
+        let root =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        let max_nodes = max_nodes.min(MAX_SUBTREE_NODES);
+        let mut departments: Vec<Department> = Vec::new(&env);
+        if max_nodes == 0 {
+            return Ok((departments, true));
+        }
+        departments.push_back(root);
+
+        let mut truncated = false;
+        let mut i = 0u32;
+        while i < departments.len() && !truncated {
+            let id = departments.get(i).unwrap().id;
+            let children: Vec<u128> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentChildren(id))
+                .unwrap_or_else(|| Vec::new(&env));
+            for child in children.iter() {
+                if departments.len() >= max_nodes {
+                    truncated = true;
+                    break;
+                }
+                if let Some(dept) = Self::load_department(&env, child) {
+                    departments.push_back(dept);
+                }
+            }
+            i += 1;
+        }
+        Ok((departments, truncated))
+    }
+
+    /// Reparents a department to a new parent (or makes it top-level).
+    ///
+    /// # Arguments
+    /// * `caller`     - Must be the **org owner** or an org admin
+    ///                  (must authenticate).
+    /// * `dept_id`    - Department to move.
+    /// * `new_parent` - `Some(parent_dept_id)` or `None` for top-level.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org not found.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `DeptNotFound` – dept_id does not exist.
+    /// - `ParentNotFound` – new_parent does not exist.
+    /// - `ParentNotInOrg` – new parent is in a different org.
+    /// - `MaxDepthExceeded` – the department or one of its descendants would
+    ///   end up deeper than the org's `max_depth`.
+    /// - `CycleDetected` – new parent is a descendant of dept_id.
+    /// - `DeptArchived` – new parent is archived.
+    /// - `DuplicateDepartmentName` – dept_id is an unmigrated legacy record
+    ///   whose name a newer active department took; see `migrate_department`.
+    ///
+    /// # Events
+    /// Publishes `("dept_mvd", dept_id)` on success.
+    pub fn update_department(
+        env: Env,
+        caller: Address,
+        dept_id: u128,
+        new_parent: Option<u128>,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+
+        let mut dept: Department =
+            Self::load_department(&env, dept_id).ok_or(DepartmentError::DeptNotFound)?;
+
+        let org: Organization =
+            Self::load_org(&env, dept.org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(&env, &org, &caller) {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+
+        if let Some(pid) = new_parent {
+            let parent: Department =
+                Self::load_department(&env, pid).ok_or(DepartmentError::ParentNotFound)?;
+            if parent.org_id != dept.org_id {
+                return Err(DepartmentError::ParentNotInOrg);
+            }
+            if Self::is_archived(&env, pid) {
+                return Err(DepartmentError::DeptArchived);
+            }
+            if Self::has_cycle(&env, dept_id, pid) {
+                return Err(DepartmentError::CycleDetected);
+            }
+            // The deepest department of the moved subtree lands at
+            // depth(parent) + 1 + height(subtree); it must stay within the limit.
+            let max_depth = Self::org_settings(&env, dept.org_id).max_depth;
+            if Self::dept_depth(&env, pid) + 1 + Self::subtree_height(&env, dept_id) > max_depth {
+                return Err(DepartmentError::MaxDepthExceeded);
+            }
+        }
+
+        // Remove dept_id from old parent's children list
+        if let Some(old_pid) = dept.parent_id {
+            let mut old_children: Vec<u128> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentChildren(old_pid))
+                .unwrap_or_else(|| Vec::new(&env));
+            let mut i = 0u32;
+            while i < old_children.len() {
+                if old_children.get(i) == Some(dept_id) {
+                    old_children.remove(i);
+                    break;
+                }
+                i += 1;
+            }
+            env.storage()
+                .persistent()
+                .set(&StorageKey::DepartmentChildren(old_pid), &old_children);
+        }
+
+        // Add dept_id to new parent's children list
+        if let Some(pid) = new_parent {
+            let mut new_children: Vec<u128> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentChildren(pid))
+                .unwrap_or_else(|| Vec::new(&env));
+            new_children.push_back(dept_id);
+            Self::set_department_entry(
+                &env,
+                pid,
+                StorageKey::DepartmentChildren(pid),
+                &new_children,
+            );
+        }
+
+        dept.parent_id = new_parent;
+        Self::store_department(&env, &dept)?;
+
+        env.events()
+            .publish((symbol_short!("dept_mvd"), dept_id), dept_id);
+        Ok(())
+    }
+
+    /// Archives (closes) a department. Archived departments keep their record
+    /// and history but no longer accept employees or new child departments.
+    ///
+    /// The department must have no employees. Without `cascade` it must also
+    /// have no active child departments; with `cascade` every active
+    /// descendant is archived too, and none of them may have employees.
+    ///
+    /// # Arguments
+    /// * `caller`        - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department to archive.
+    /// * `cascade`       - Whether to archive active descendants as well.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `DeptArchived` – the department is already archived.
+    /// - `DeptHasEmployees` – an affected department has employees.
+    /// - `DeptHasActiveChildren` – active children and no `cascade`.
+    ///
+    /// # Events
+    /// Publishes `("dept_arch", dept_id)` for each archived department.
+    pub fn archive_department(
+        env: Env,
+        caller: Address,
+        department_id: u128,
+        cascade: bool,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &caller, department_id)?;
+        if Self::is_archived(&env, department_id) {
+            return Err(DepartmentError::DeptArchived);
+        }
+
+        // Collect the department and, when cascading, its active descendants.
+        let mut to_archive: Vec<u128> = Vec::new(&env);
+        to_archive.push_back(department_id);
+        let mut i = 0u32;
+        while i < to_archive.len() {
+            let id = to_archive.get(i).unwrap();
+            let employees: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentEmployees(id))
+                .unwrap_or_else(|| Vec::new(&env));
+            if !employees.is_empty() {
+                return Err(DepartmentError::DeptHasEmployees);
+            }
+
+            let children: Vec<u128> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentChildren(id))
+                .unwrap_or_else(|| Vec::new(&env));
+            for child in children.iter() {
+                if !Self::is_archived(&env, child) {
+                    if !cascade {
+                        return Err(DepartmentError::DeptHasActiveChildren);
+                    }
+                    to_archive.push_back(child);
+                }
+            }
+            i += 1;
+        }
+
+        for id in to_archive.iter() {
+            Self::release_department_name(&env, id);
+            Self::set_department_entry(&env, id, StorageKey::ArchivedDepartment(id), &());
+            env.events().publish((symbol_short!("dept_arch"), id), id);
+        }
+        Ok(())
+    }
+
+    /// Reactivates an archived department so it accepts employees again.
+    /// Archived descendants stay archived.
+    ///
+    /// # Arguments
+    /// * `caller`        - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department to reactivate.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `DeptNotArchived` – the department is active.
+    /// - `DeptArchived` – its parent is archived; reactivate it first.
+    /// - `DuplicateDepartmentName` – another active department took its name
+    ///   while it was archived.
+    ///
+    /// # Events
+    /// Publishes `("dept_reac", dept_id)` on success.
+    pub fn reactivate_department(
+        env: Env,
+        caller: Address,
+        department_id: u128,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &caller, department_id)?;
+        if !Self::is_archived(&env, department_id) {
+            return Err(DepartmentError::DeptNotArchived);
+        }
+
+        let dept: Department =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        if let Some(pid) = dept.parent_id {
+            if Self::is_archived(&env, pid) {
+                return Err(DepartmentError::DeptArchived);
+            }
+        }
+        Self::store_department(&env, &dept)?;
+        let name_key = StorageKey::DeptNameIndexV2(dept.org_id, dept.name);
+        if env.storage().persistent().has(&name_key) {
+            return Err(DepartmentError::DuplicateDepartmentName);
+        }
+
+        env.storage().persistent().set(&name_key, &department_id);
+        Self::remove_department_entry(
+            &env,
+            department_id,
+            StorageKey::ArchivedDepartment(department_id),
+        );
+        env.events()
+            .publish((symbol_short!("dept_reac"), department_id), department_id);
+        Ok(())
+    }
+
+    /// Deletes a department created by mistake. Only allowed while it has no
+    /// employees and no child departments; use `archive_department` to close
+    /// a department that has history.
+    ///
+    /// Removes the department record and its entries in `OrgDepartments` and
+    /// the parent's `DepartmentChildren`, along with its own (empty) employee
+    /// and child lists, manager, archived flag, name index entry,
+    /// cost-center code, headcount snapshots and budgets.
+    ///
+    /// # Arguments
+    /// * `caller`        - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department to delete.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `DeptHasEmployees` – employees are still assigned.
+    /// - `DeptHasChildren` – child departments exist (archived or not).
+    ///
+    /// # Events
+    /// Publishes `("dept_del", dept_id)` on success.
+    pub fn delete_department(
+        env: Env,
+        caller: Address,
+        department_id: u128,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &caller, department_id)?;
+
+        let employees: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployees(department_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        if !employees.is_empty() {
+            return Err(DepartmentError::DeptHasEmployees);
+        }
+        let children: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentChildren(department_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        if !children.is_empty() {
+            return Err(DepartmentError::DeptHasChildren);
+        }
+
+        let dept: Department =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+
+        let org_key = StorageKey::OrgDepartments(dept.org_id);
+        let mut org_depts: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&org_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if let Some(i) = org_depts.first_index_of(department_id) {
+            org_depts.remove(i);
+        }
+        env.storage().persistent().set(&org_key, &org_depts);
+
+        if let Some(pid) = dept.parent_id {
+            let parent_key = StorageKey::DepartmentChildren(pid);
+            let mut siblings: Vec<u128> = env
+                .storage()
+                .persistent()
+                .get(&parent_key)
+                .unwrap_or_else(|| Vec::new(&env));
+            if let Some(i) = siblings.first_index_of(department_id) {
+                siblings.remove(i);
+            }
+            env.storage().persistent().set(&parent_key, &siblings);
+        }
+
+        Self::release_department_name(&env, department_id);
+        let code: Option<soroban_sdk::Symbol> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentCostCenter(department_id));
+        if let Some(code) = code {
+            env.storage()
+                .persistent()
+                .remove(&StorageKey::CostCenterIndex(dept.org_id, code));
+        }
+        for key in [
+            StorageKey::DepartmentV2(department_id),
+            StorageKey::Department(department_id),
+            StorageKey::DepartmentEmployees(department_id),
+            StorageKey::DepartmentEmployeeCount(department_id),
+            StorageKey::DepartmentChildren(department_id),
+            StorageKey::DepartmentManager(department_id),
+            StorageKey::ManagerIncludesChildren(department_id),
+            StorageKey::ArchivedDepartment(department_id),
+            StorageKey::DepartmentCostCenter(department_id),
+            StorageKey::DepartmentCapacity(department_id),
+            StorageKey::DepartmentSnapshots(department_id),
+            StorageKey::DepartmentAgreement(department_id),
+            StorageKey::DepartmentKeys(department_id),
+        ] {
+            env.storage().persistent().remove(&key);
+        }
+
+        let budgets_key = StorageKey::OrgBudgets(dept.org_id);
+        let budgets: Vec<(u128, Address)> = env
+            .storage()
+            .persistent()
+            .get(&budgets_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        let mut kept: Vec<(u128, Address)> = Vec::new(&env);
+        for (id, token) in budgets.iter() {
+            if id == department_id {
+                env.storage()
+                    .persistent()
+                    .remove(&StorageKey::DepartmentBudget(id, token));
+            } else {
+                kept.push_back((id, token));
+            }
+        }
+        if kept.len() != budgets.len() {
+            env.storage().persistent().set(&budgets_key, &kept);
+        }
+
+        env.events()
+            .publish((symbol_short!("dept_del"), department_id), department_id);
+        Ok(())
+    }
+
+    /// Folds one department into another of the same org after a re-org:
+    /// every employee of the source moves to the target, the source's
+    /// children are reparented under the target, and the source is archived.
+    ///
+    /// Larger departments must first be shrunk below `MAX_MERGE_SIZE`, e.g.
+    /// with `assign_employees_batch` into the target.
+    ///
+    /// # Arguments
+    /// * `caller`               - Must be the **org owner** or an org admin
+    ///                            (must authenticate).
+    /// * `source_department_id` - Department to fold in and archive.
+    /// * `target_department_id` - Department receiving employees and children.
+    ///
+    /// # Returns
+    /// The number of employees moved.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – either department does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `DeptNotInOrg` – the departments belong to different orgs.
+    /// - `DeptArchived` – either department is archived.
+    /// - `CycleDetected` – the target is the source or one of its descendants.
+    /// - `MaxDepthExceeded` – a reparented subtree would exceed the org's
+    ///   `max_depth`.
+    /// - `MergeTooLarge` – the source has more than `MAX_MERGE_SIZE`
+    ///   employees plus children.
+    /// - `DepartmentFull` – the employees do not fit in the target's capacity.
+    /// - `DuplicateDepartmentName` – a child is an unmigrated legacy record
+    ///   whose name a newer active department took; see `migrate_department`.
+    ///
+    /// # Events
+    /// Publishes `("emp_asgnd", target)` per employee, `("dept_mvd", child)`
+    /// per child and `("dept_arch", source)`, then `("dept_mrg", source)` with
+    /// `(target_department_id, moved)`.
+    pub fn merge_departments(
+        env: Env,
+        caller: Address,
+        source_department_id: u128,
+        target_department_id: u128,
+    ) -> Result<u32, DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &caller, source_department_id)?;
+
+        let source: Department = Self::load_department(&env, source_department_id)
+            .ok_or(DepartmentError::DeptNotFound)?;
+        let target: Department = Self::load_department(&env, target_department_id)
+            .ok_or(DepartmentError::DeptNotFound)?;
+        if target.org_id != source.org_id {
+            return Err(DepartmentError::DeptNotInOrg);
+        }
+        if Self::is_archived(&env, source_department_id)
+            || Self::is_archived(&env, target_department_id)
+        {
+            return Err(DepartmentError::DeptArchived);
+        }
+        if Self::has_cycle(&env, source_department_id, target_department_id) {
+            return Err(DepartmentError::CycleDetected);
+        }
+
+        let employees: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployees(source_department_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        let children: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentChildren(source_department_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        if employees.len() + children.len() > MAX_MERGE_SIZE {
+            return Err(DepartmentError::MergeTooLarge);
+        }
+
+        // Children move from depth(source) + 1 to depth(target) + 1.
+        let source_depth = Self::dept_depth(&env, source_department_id);
+        let target_depth = Self::dept_depth(&env, target_department_id);
+        if target_depth > source_depth {
+            let max_depth = Self::org_settings(&env, source.org_id).max_depth;
+            for child in children.iter() {
+                if target_depth + 1 + Self::subtree_height(&env, child) > max_depth {
+                    return Err(DepartmentError::MaxDepthExceeded);
+                }
+            }
+        }
+
+        let capacity: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentCapacity(target_department_id));
+        let target_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployeeCount(target_department_id))
+            .unwrap_or(0);
+        if capacity.is_some_and(|cap| target_count + employees.len() > cap) {
+            return Err(DepartmentError::DepartmentFull);
+        }
+
+        let org: Organization =
+            Self::load_org(&env, source.org_id).ok_or(DepartmentError::OrgNotFound)?;
+        for employee in employees.iter() {
+            Self::assign_employee_internal(&env, &caller, &org, target_department_id, employee)?;
+        }
+
+        let mut target_children: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentChildren(target_department_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        for child_id in children.iter() {
+            let mut child: Department =
+                Self::load_department(&env, child_id).ok_or(DepartmentError::DeptNotFound)?;
+            child.parent_id = Some(target_department_id);
+            Self::store_department(&env, &child)?;
+            target_children.push_back(child_id);
+            env.events()
+                .publish((symbol_short!("dept_mvd"), child_id), child_id);
+        }
+        Self::set_department_entry(
+            &env,
+            target_department_id,
+            StorageKey::DepartmentChildren(target_department_id),
+            &target_children,
+        );
+        Self::remove_department_entry(
+            &env,
+            source_department_id,
+            StorageKey::DepartmentChildren(source_department_id),
+        );
+
+        Self::release_department_name(&env, source_department_id);
+        Self::set_department_entry(
+            &env,
+            source_department_id,
+            StorageKey::ArchivedDepartment(source_department_id),
+            &(),
+        );
+        env.events().publish(
+            (symbol_short!("dept_arch"), source_department_id),
+            source_department_id,
+        );
+
+        let moved = employees.len();
+        env.events().publish(
+            (symbol_short!("dept_mrg"), source_department_id),
+            (target_department_id, moved),
+        );
+        Ok(moved)
+    }
+
+    /// Returns whether a department is archived.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn is_department_archived(env: Env, department_id: u128) -> bool {
+        Self::is_archived(&env, department_id)
+    }
+
+    // -------------------------------------------------------------------------
+    // Cost centers
+    // -------------------------------------------------------------------------
+
+    /// Sets (`Some`) or clears (`None`) the accounting cost-center code of a
+    /// department. Codes are unique within an org; clearing one frees it for
+    /// another department.
+    ///
+    /// # Arguments
+    /// * `owner`         - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department ID.
+    /// * `code`          - Cost-center code, or `None` to clear.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `DuplicateCostCenter` – another department in the org uses `code`.
+    ///
+    /// # Events
+    /// Publishes `("cc_set", department_id)` with the code.
+    pub fn set_cost_center(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        code: Option<soroban_sdk::Symbol>,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+        let dept: Department =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+
+        if let Some(new_code) = &code {
+            let holder: Option<u128> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::CostCenterIndex(dept.org_id, new_code.clone()));
+            if holder.is_some_and(|id| id != department_id) {
+                return Err(DepartmentError::DuplicateCostCenter);
+            }
+        }
+
+        let key = StorageKey::DepartmentCostCenter(department_id);
+        let old_code: Option<soroban_sdk::Symbol> = env.storage().persistent().get(&key);
+        if let Some(old_code) = old_code {
+            env.storage()
+                .persistent()
+                .remove(&StorageKey::CostCenterIndex(dept.org_id, old_code));
+        }
+        match &code {
+            Some(new_code) => {
+                Self::set_department_entry(&env, department_id, key, new_code);
+                env.storage().persistent().set(
+                    &StorageKey::CostCenterIndex(dept.org_id, new_code.clone()),
+                    &department_id,
+                );
+            }
+            None => Self::remove_department_entry(&env, department_id, key),
+        }
+
+        env.events()
+            .publish((symbol_short!("cc_set"), department_id), code);
+        Ok(())
+    }
+
+    /// Returns the cost-center code of a department, if any.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_cost_center(env: Env, department_id: u128) -> Option<soroban_sdk::Symbol> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentCostCenter(department_id))
+    }
+
+    /// Returns the department of an organization holding a cost-center code.
+    ///
+    /// # Arguments
+    /// * `org_id` - The organization ID.
+    /// * `code`   - Cost-center code.
+    pub fn get_department_by_cost_center(
+        env: Env,
+        org_id: u128,
+        code: soroban_sdk::Symbol,
+    ) -> Option<u128> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::CostCenterIndex(org_id, code))
+    }
+
+    // -------------------------------------------------------------------------
+    // Headcount snapshots
+    // -------------------------------------------------------------------------
+
+    /// Records the department's current employee count, e.g. at month end,
+    /// so its headcount history is available without an indexer.
+    ///
+    /// The latest `MAX_SNAPSHOTS` are kept. A second snapshot in the same
+    /// (UTC) day replaces the first one instead of adding an entry.
+    ///
+    /// # Arguments
+    /// * `caller`        - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department to snapshot.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    ///
+    /// # Events
+    /// Publishes `("dept_snap", dept_id)` with `(timestamp, employee_count)`.
+    pub fn snapshot_department(
+        env: Env,
+        caller: Address,
+        department_id: u128,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &caller, department_id)?;
+
+        let timestamp = env.ledger().timestamp();
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployeeCount(department_id))
+            .unwrap_or(0);
+        let key = StorageKey::DepartmentSnapshots(department_id);
+        let mut snapshots: Vec<(u64, u32)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if let Some((last, _)) = snapshots.last() {
+            if last / SECONDS_PER_DAY == timestamp / SECONDS_PER_DAY {
+                snapshots.pop_back();
+            }
+        }
+        snapshots.push_back((timestamp, count));
+        if snapshots.len() > MAX_SNAPSHOTS {
+            snapshots.pop_front();
+        }
+        Self::set_department_entry(&env, department_id, key, &snapshots);
+
+        env.events().publish(
+            (symbol_short!("dept_snap"), department_id),
+            (timestamp, count),
+        );
+        Ok(())
+    }
+
+    /// Returns the department's headcount snapshots as
+    /// `(timestamp, employee_count)`, oldest first.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_snapshots(env: Env, department_id: u128) -> Vec<(u64, u32)> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentSnapshots(department_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // -------------------------------------------------------------------------
+    // Budgets
+    // -------------------------------------------------------------------------
+
+    /// Sets (`Some`) or clears (`None`) the payroll contract of an org, which
+    /// may call `record_department_spend` alongside the owner.
+    ///
+    /// # Arguments
+    /// * `owner`    - Must be the **org owner** (must authenticate).
+    /// * `org_id`   - Organization ID.
+    /// * `contract` - Payroll contract address, or `None` to revoke.
+    ///
+    /// # Errors
+    /// - `OrgNotFound` – org_id does not exist.
+    /// - `NotOrgOwner` – caller is not the org owner.
+    pub fn set_payroll_contract(
+        env: Env,
+        owner: Address,
+        org_id: u128,
+        contract: Option<Address>,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        let org: Organization = Self::load_org(&env, org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if org.owner != owner {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+
+        let key = StorageKey::PayrollContract(org_id);
+        match &contract {
+            Some(c) => env.storage().persistent().set(&key, c),
+            None => env.storage().persistent().remove(&key),
+        }
+        Ok(())
+    }
+
+    /// Returns the payroll contract of an org, if any.
+    ///
+    /// # Arguments
+    /// * `org_id` - The organization ID.
+    pub fn get_payroll_contract(env: Env, org_id: u128) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::PayrollContract(org_id))
+    }
+
+    /// Sets the budget allocated to a department in `token`.
+    ///
+    /// Amounts already spent are kept, so raising the allocation mid-period
+    /// adds to the remainder. The allocation may be set below the amount
+    /// spent, in which case the remainder is negative.
+    ///
+    /// # Arguments
+    /// * `owner`         - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department ID.
+    /// * `token`         - Token the budget is denominated in.
+    /// * `amount`        - New allocation (non-negative).
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    /// - `InvalidAmount` – amount is negative.
+    /// - `BudgetLimitReached` – the budget is new and the org already has
+    ///   `MAX_ORG_BUDGETS` budgets.
+    ///
+    /// # Events
+    /// Publishes `("bdgt_set", department_id)` with `(token, amount)`.
+    pub fn set_department_budget(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+        if amount < 0 {
+            return Err(DepartmentError::InvalidAmount);
+        }
+
+        let key = StorageKey::DepartmentBudget(department_id, token.clone());
+        let existing: Option<DepartmentBudget> = env.storage().persistent().get(&key);
+        if existing.is_none() {
+            let dept: Department =
+                Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+            let index_key = StorageKey::OrgBudgets(dept.org_id);
+            let mut budgets: Vec<(u128, Address)> = env
+                .storage()
+                .persistent()
+                .get(&index_key)
+                .unwrap_or_else(|| Vec::new(&env));
+            if budgets.len() >= MAX_ORG_BUDGETS {
+                return Err(DepartmentError::BudgetLimitReached);
+            }
+            budgets.push_back((department_id, token.clone()));
+            env.storage().persistent().set(&index_key, &budgets);
+        }
+        let mut budget = existing.unwrap_or(DepartmentBudget {
+            allocated: 0,
+            spent: 0,
+            allow_overspend: false,
+        });
+        budget.allocated = amount;
+        env.storage().persistent().set(&key, &budget);
+
+        env.events()
+            .publish((symbol_short!("bdgt_set"), department_id), (token, amount));
+        Ok(())
+    }
+
+    /// Sets whether spend may exceed a department's allocation in `token`.
+    /// Defaults to `false`.
+    ///
+    /// # Arguments
+    /// * `owner`           - Must be the **org owner** or an org admin
+    ///                       (must authenticate).
+    /// * `department_id`   - Department ID.
+    /// * `token`           - Token of the budget.
+    /// * `allow_overspend` - Whether `record_department_spend` may exceed it.
+    ///
+    /// # Errors
+    /// - Same as `set_department_budget`.
+    /// - `BudgetNotSet` – no budget exists for the department and token.
+    pub fn set_budget_allow_overspend(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        token: Address,
+        allow_overspend: bool,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+
+        let key = StorageKey::DepartmentBudget(department_id, token);
+        let mut budget: DepartmentBudget = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(DepartmentError::BudgetNotSet)?;
+        budget.allow_overspend = allow_overspend;
+        env.storage().persistent().set(&key, &budget);
+        Ok(())
+    }
+
+    /// Records `amount` of `token` spent by a department.
+    ///
+    /// # Arguments
+    /// * `caller`        - The **org owner**, an org admin or the org's
+    ///                     payroll contract (must authenticate).
+    /// * `department_id` - Department ID.
+    /// * `token`         - Token spent.
+    /// * `amount`        - Amount spent (positive).
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `NotOrgOwnerOrPayroll` – caller may not record spend.
+    /// - `InvalidAmount` – amount is zero or negative.
+    /// - `BudgetNotSet` – no budget exists for the department and token.
+    /// - `BudgetExceeded` – spend would exceed the allocation and
+    ///   overspend is not allowed.
+    ///
+    /// # Events
+    /// Publishes `("bdgt_spnd", department_id)` with `(token, amount)`.
+    pub fn record_department_spend(
+        env: Env,
+        caller: Address,
+        department_id: u128,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let dept: Department =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        let org: Organization =
+            Self::load_org(&env, dept.org_id).ok_or(DepartmentError::OrgNotFound)?;
+        let payroll: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::PayrollContract(dept.org_id));
+        if !Self::is_owner_or_admin(&env, &org, &caller) && payroll != Some(caller) {
+            return Err(DepartmentError::NotOrgOwnerOrPayroll);
+        }
+        if amount <= 0 {
+            return Err(DepartmentError::InvalidAmount);
+        }
+
+        let key = StorageKey::DepartmentBudget(department_id, token.clone());
+        let mut budget: DepartmentBudget = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(DepartmentError::BudgetNotSet)?;
+        let spent = budget
+            .spent
+            .checked_add(amount)
+            .ok_or(DepartmentError::BudgetExceeded)?;
+        if !budget.allow_overspend && spent > budget.allocated {
+            return Err(DepartmentError::BudgetExceeded);
+        }
+        budget.spent = spent;
+        env.storage().persistent().set(&key, &budget);
+
+        env.events()
+            .publish((symbol_short!("bdgt_spnd"), department_id), (token, amount));
+        Ok(())
+    }
+
+    /// Returns `(allocated, spent, remaining)` for a department in `token`,
+    /// all zero if no budget was set. `remaining` is negative after overspend.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    /// * `token`         - Token of the budget.
+    pub fn get_department_budget(
+        env: Env,
+        department_id: u128,
+        token: Address,
+    ) -> (i128, i128, i128) {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, DepartmentBudget>(&StorageKey::DepartmentBudget(department_id, token))
+        {
+            Some(b) => (b.allocated, b.spent, b.allocated - b.spent),
+            None => (0, 0, 0),
+        }
+    }
+
+    /// Returns whether spend may exceed a department's allocation in `token`.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    /// * `token`         - Token of the budget.
+    pub fn get_budget_allow_overspend(env: Env, department_id: u128, token: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, DepartmentBudget>(&StorageKey::DepartmentBudget(department_id, token))
+            .map(|b| b.allow_overspend)
+            .unwrap_or(false)
+    }
+
+    // -------------------------------------------------------------------------
+    // Payroll agreements
+    // -------------------------------------------------------------------------
+
+    /// Links (`Some`) or unlinks (`None`) a department to an agreement of its
+    /// org's payroll contract, whose employees `sync_from_payroll` then adds
+    /// to the department.
+    ///
+    /// # Arguments
+    /// * `owner`         - Must be the **org owner** or an org admin
+    ///                     (must authenticate).
+    /// * `department_id` - Department ID.
+    /// * `agreement_id`  - Agreement ID in the payroll contract, or `None`.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `OrgNotFound` – the department's org does not exist.
+    /// - `NotOrgOwner` – caller is neither the org owner nor an org admin.
+    ///
+    /// # Events
+    /// Publishes `("agr_set", department_id)` with the agreement ID.
+    pub fn set_department_agreement(
+        env: Env,
+        owner: Address,
+        department_id: u128,
+        agreement_id: Option<u128>,
+    ) -> Result<(), DepartmentError> {
+        owner.require_auth();
+        Self::require_initialized(&env)?;
+        Self::require_department_owner(&env, &owner, department_id)?;
+
+        let key = StorageKey::DepartmentAgreement(department_id);
+        match agreement_id {
+            Some(id) => Self::set_department_entry(&env, department_id, key, &id),
+            None => Self::remove_department_entry(&env, department_id, key),
+        }
+
+        env.events()
+            .publish((symbol_short!("agr_set"), department_id), agreement_id);
+        Ok(())
+    }
+
+    /// Returns the payroll agreement linked to a department, if any.
+    ///
+    /// # Arguments
+    /// * `department_id` - The department ID.
+    pub fn get_department_agreement(env: Env, department_id: u128) -> Option<u128> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::DepartmentAgreement(department_id))
+    }
+
+    /// Assigns to a department the employees of its linked payroll agreement
+    /// that have no department in the org yet, as read from the payroll
+    /// contract's `get_agreement_employees`.
+    ///
+    /// Employees already in another department of the org are left where
+    /// they are. At most `MAX_PAYROLL_SYNC` employees are added per call,
+    /// and none beyond the department's capacity; call again until it
+    /// returns `0`.
+    ///
+    /// # Arguments
+    /// * `caller`        - Same rights as for `assign_employee_to_department`.
+    /// * `department_id` - Department ID.
+    ///
+    /// # Returns
+    /// The number of employees added.
+    ///
+    /// # Errors
+    /// - `DeptNotFound` – department_id does not exist.
+    /// - `OrgNotFound` – the department's org does not exist.
+    /// - `DeptArchived` – the department no longer accepts employees.
+    /// - `NotOrgOwnerOrManager` – caller may not manage the department.
+    /// - `AgreementNotLinked` – no agreement is linked or the org has no
+    ///   payroll contract.
+    ///
+    /// # Events
+    /// Publishes `("emp_asgnd", department_id)` per added employee, then
+    /// `("pay_sync", department_id)` with the number added.
+    pub fn sync_from_payroll(
+        env: Env,
+        caller: Address,
+        department_id: u128,
+    ) -> Result<u32, DepartmentError> {
+        caller.require_auth();
+        Self::require_initialized(&env)?;
+        let dept: Department =
+            Self::load_department(&env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        let org: Organization =
+            Self::load_org(&env, dept.org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if Self::is_archived(&env, department_id) {
+            return Err(DepartmentError::DeptArchived);
+        }
+        Self::require_can_manage(&env, &caller, &org, department_id)?;
+        let agreement_id: u128 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentAgreement(department_id))
+            .ok_or(DepartmentError::AgreementNotLinked)?;
+        let payroll: Address = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::PayrollContract(org.id))
+            .ok_or(DepartmentError::AgreementNotLinked)?;
+
+        let employees: Vec<Address> = env.invoke_contract(
+            &payroll,
+            &soroban_sdk::Symbol::new(&env, "get_agreement_employees"),
+            soroban_sdk::vec![&env, agreement_id.into_val(&env)],
+        );
+
+        let mut room = MAX_PAYROLL_SYNC;
+        let capacity: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentCapacity(department_id));
+        if let Some(cap) = capacity {
+            let count: u32 = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentEmployeeCount(department_id))
+                .unwrap_or(0);
+            room = room.min(cap.saturating_sub(count));
+        }
+        let mut added = 0u32;
+        for employee in employees.iter() {
+            if added >= room {
+                break;
+            }
+            if env
+                .storage()
+                .persistent()
+                .has(&StorageKey::EmployeeDepartment(employee.clone(), org.id))
+            {
+                continue;
+            }
+            Self::assign_employee_internal(&env, &caller, &org, department_id, employee)?;
+            added += 1;
+        }
+
+        env.events()
+            .publish((symbol_short!("pay_sync"), department_id), added);
+        Ok(added)
+    }
+
+    // -------------------------------------------------------------------------
+    // Internal helpers
+    // -------------------------------------------------------------------------
+
+    /// Returns the depth of `dept_id` in the hierarchy (root = 0).
+    fn dept_depth(env: &Env, dept_id: u128) -> u32 {
+        let mut depth = 0u32;
+        let mut current = dept_id;
+        loop {
+            let dept: Department = match Self::load_department(env, current) {
+                Some(d) => d,
+                None => break,
+            };
+            match dept.parent_id {
+                None => break,
+                Some(pid) => {
+                    depth += 1;
+                    current = pid;
+                }
+            }
+        }
+        depth
+    }
+
+    /// Returns the number of levels below `dept_id` in its subtree (0 for a
+    /// department without children).
+    fn subtree_height(env: &Env, dept_id: u128) -> u32 {
+        let children: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentChildren(dept_id))
+            .unwrap_or_else(|| Vec::new(env));
+        let mut height = 0u32;
+        for child in children.iter() {
+            height = height.max(1 + Self::subtree_height(env, child));
+        }
+        height
+    }
+
+    /// Returns `true` if making `candidate` the parent of `dept_id` would
+    /// create a cycle (i.e., `candidate` is already a descendant of `dept_id`).
+    fn has_cycle(env: &Env, dept_id: u128, candidate: u128) -> bool {
+        // Walk up from candidate; if we reach dept_id, it's a cycle.
+        let mut current = candidate;
+        loop {
+            if current == dept_id {
+                return true;
+            }
+            let dept: Department = match Self::load_department(env, current) {
+                Some(d) => d,
+                None => return false,
+            };
+            match dept.parent_id {
+                None => return false,
+                Some(pid) => current = pid,
+            }
+        }
+    }
+
+    /// Creates a department after the caller has been authorized; see
+    /// `create_department` for the checks and event.
+    fn create_department_internal(
+        env: &Env,
+        caller: Address,
+        org_id: u128,
+        name: String,
+        parent_id: Option<u128>,
+    ) -> Result<u128, DepartmentError> {
+        if let Some(pid) = parent_id {
+            let parent: Department =
+                Self::load_department(env, pid).ok_or(DepartmentError::ParentNotFound)?;
+            if parent.org_id != org_id {
+                return Err(DepartmentError::ParentNotInOrg);
+            }
+            if Self::is_archived(env, pid) {
+                return Err(DepartmentError::DeptArchived);
+            }
+            // child depth = parent depth + 1; must not exceed the org limit
+            if Self::dept_depth(env, pid) + 1 > Self::org_settings(env, org_id).max_depth {
+                return Err(DepartmentError::MaxDepthExceeded);
+            }
+        }
+
+        Self::validate_name(&name)?;
+        let name_key = StorageKey::DeptNameIndexV2(org_id, name.clone());
+        if env.storage().persistent().has(&name_key) {
+            return Err(DepartmentError::DuplicateDepartmentName);
+        }
+
+        let next_id: u128 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::NextDeptId)
+            .unwrap_or(1);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::NextDeptId, &(next_id + 1));
+
+        let dept = Department {
+            id: next_id,
+            org_id,
+            name,
+            parent_id,
+            created_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&StorageKey::DepartmentV2(next_id), &dept);
+        env.storage().persistent().set(&name_key, &next_id);
+
+        // Register dept under org
+        let mut org_depts: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::OrgDepartments(org_id))
+            .unwrap_or_else(|| Vec::new(env));
+        org_depts.push_back(next_id);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::OrgDepartments(org_id), &org_depts);
+
+        // Register as child of parent dept if nested
+        if let Some(pid) = parent_id {
+            let mut children: Vec<u128> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentChildren(pid))
+                .unwrap_or_else(|| Vec::new(env));
+            children.push_back(next_id);
+            Self::set_department_entry(env, pid, StorageKey::DepartmentChildren(pid), &children);
+        }
+
+        // Initialize empty employee list and optional entry index for this
+        // department
+        let empty_employees: Vec<Address> = Vec::new(env);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::DepartmentEmployees(next_id), &empty_employees);
+        let no_keys: Vec<StorageKey> = Vec::new(env);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::DepartmentKeys(next_id), &no_keys);
+
+        env.events().publish(
+            (symbol_short!("dept_crtd"), next_id),
+            DepartmentCreatedEvent {
+                org_id,
+                department_id: next_id,
+                parent_id,
+                caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(next_id)
+    }
+
+    /// Removes `employee` from `dept_id` and from every org-level index of
+    /// `org_id` (roster, employee orgs, profile, status). The caller must
+    /// already be authorized.
+    fn remove_employee_from_org_internal(
+        env: &Env,
+        caller: Address,
+        org_id: u128,
+        dept_id: u128,
+        employee: Address,
+    ) {
+        Self::remove_employee_from_dept_internal(env, dept_id, &employee);
+
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::EmployeeDepartment(employee.clone(), org_id));
+
+        let mut roster: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::OrgEmployees(org_id))
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(i) = roster.first_index_of(&employee) {
+            roster.remove(i);
+        }
+        env.storage()
+            .persistent()
+            .set(&StorageKey::OrgEmployees(org_id), &roster);
+
+        let orgs_key = StorageKey::EmployeeOrgs(employee.clone());
+        let mut orgs: Vec<u128> = env
+            .storage()
+            .persistent()
+            .get(&orgs_key)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(i) = orgs.first_index_of(org_id) {
+            orgs.remove(i);
+        }
+        if orgs.is_empty() {
+            env.storage().persistent().remove(&orgs_key);
+        } else {
+            env.storage().persistent().set(&orgs_key, &orgs);
+        }
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::EmployeeProfile(org_id, employee.clone()));
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::EmployeeInactive(org_id, employee.clone()));
+
+        env.events().publish(
+            (symbol_short!("emp_rmvd"), dept_id),
+            EmployeeRemovedEvent {
+                org_id,
+                department_id: dept_id,
+                employee,
+                caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Moves `employee` into `department_id`, removing them from their
+    /// previous department in the org. The target department must already be
+    /// validated; `caller` must also be able to manage the previous one.
+    fn assign_employee_internal(
+        env: &Env,
+        caller: &Address,
+        org: &Organization,
+        department_id: u128,
+        employee: Address,
+    ) -> Result<(), DepartmentError> {
+        let org_id = org.id;
+        // Remove from previous department in this org, if any
+        let previous_department = env
+            .storage()
+            .persistent()
+            .get::<_, u128>(&StorageKey::EmployeeDepartment(employee.clone(), org_id));
+        // Already there: keep their roster position and head role.
+        if previous_department == Some(department_id) {
+            return Ok(());
+        }
+        let capacity: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentCapacity(department_id));
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployeeCount(department_id))
+            .unwrap_or(0);
+        if capacity.is_some_and(|cap| count >= cap) {
+            return Err(DepartmentError::DepartmentFull);
+        }
+        if let Some(old_dept) = previous_department {
+            Self::require_can_manage(env, caller, org, old_dept)?;
+            Self::remove_employee_from_dept_internal(env, old_dept, &employee);
+        } else {
+            let mut roster: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::OrgEmployees(org_id))
+                .unwrap_or_else(|| Vec::new(env));
+            roster.push_back(employee.clone());
+            env.storage()
+                .persistent()
+                .set(&StorageKey::OrgEmployees(org_id), &roster);
+
+            let orgs_key = StorageKey::EmployeeOrgs(employee.clone());
+            let mut orgs: Vec<u128> = env
+                .storage()
+                .persistent()
+                .get(&orgs_key)
+                .unwrap_or_else(|| Vec::new(env));
+            orgs.push_back(org_id);
+            env.storage().persistent().set(&orgs_key, &orgs);
+        }
+
+        env.storage().persistent().set(
+            &StorageKey::EmployeeInDepartment(department_id, employee.clone()),
+            &(),
+        );
+        env.storage().persistent().set(
+            &StorageKey::EmployeeDepartment(employee.clone(), org_id),
+            &department_id,
+        );
+
+        let mut employees: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentEmployees(department_id))
+            .unwrap_or_else(|| Vec::new(env));
+        employees.push_back(employee.clone());
+        env.storage()
+            .persistent()
+            .set(&StorageKey::DepartmentEmployees(department_id), &employees);
+        Self::set_department_entry(
+            env,
+            department_id,
+            StorageKey::DepartmentEmployeeCount(department_id),
+            &employees.len(),
+        );
+
+        env.events().publish(
+            (symbol_short!("emp_asgnd"), department_id),
+            EmployeeAssignedEvent {
+                org_id,
+                department_id,
+                employee,
+                previous_department,
+                caller: caller.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Removes an employee from a department's employee list and membership
+    /// flag, clearing the department head if it was that employee.
+    /// Does NOT update `EmployeeDepartment` – caller must handle that.
+    fn remove_employee_from_dept_internal(env: &Env, department_id: u128, employee: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::EmployeeInDepartment(
+                department_id,
+                employee.clone(),
+            ));
+
+        let mut employees: Vec<Address> = env
+            .storage()
             .persistent()
             .get(&StorageKey::DepartmentEmployees(department_id))
             .unwrap_or_else(|| Vec::new(env));
@@ -671,18 +3702,407 @@ impl DepartmentManagerContract {
         env.storage()
             .persistent()
             .set(&StorageKey::DepartmentEmployees(department_id), &employees);
+        env.storage().persistent().set(
+            &StorageKey::DepartmentEmployeeCount(department_id),
+            &employees.len(),
+        );
+
+        let head_key = StorageKey::DepartmentHead(department_id);
+        if env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&head_key)
+            .as_ref()
+            == Some(employee)
+        {
+            Self::remove_department_entry(env, department_id, head_key);
+        }
+    }
+
+    /// Asserts `caller` owns or administers the organization `department_id`
+    /// belongs to.
+    fn require_department_owner(
+        env: &Env,
+        caller: &Address,
+        department_id: u128,
+    ) -> Result<(), DepartmentError> {
+        let dept: Department =
+            Self::load_department(env, department_id).ok_or(DepartmentError::DeptNotFound)?;
+        let org: Organization =
+            Self::load_org(env, dept.org_id).ok_or(DepartmentError::OrgNotFound)?;
+        if !Self::is_owner_or_admin(env, &org, caller) {
+            return Err(DepartmentError::NotOrgOwner);
+        }
+        Ok(())
+    }
+
+    /// Asserts `caller` is the org owner, an org admin, or manages
+    /// `department_id`, either directly, through an ancestor whose manager
+    /// includes children, or through a `Manager` role scoped to an ancestor.
+    fn require_can_manage(
+        env: &Env,
+        caller: &Address,
+        org: &Organization,
+        department_id: u128,
+    ) -> Result<(), DepartmentError> {
+        if Self::is_owner_or_admin(env, org, caller) {
+            return Ok(());
+        }
+        let scope = Self::manager_scope(env, org.id, caller);
+        let mut current = Some(department_id);
+        while let Some(id) = current {
+            let manager: Option<Address> = env
+                .storage()
+                .persistent()
+                .get(&StorageKey::DepartmentManager(id));
+            let covers = id == department_id
+                || env
+                    .storage()
+                    .persistent()
+                    .get(&StorageKey::ManagerIncludesChildren(id))
+                    .unwrap_or(false);
+            if (covers && manager.as_ref() == Some(caller)) || scope == Some(id) {
+                return Ok(());
+            }
+            current = Self::load_department(env, id).and_then(|d| d.parent_id);
+        }
+        Err(DepartmentError::NotOrgOwnerOrManager)
+    }
+
+    /// Returns the root department of `address`'s `Manager` role, if any.
+    fn manager_scope(env: &Env, org_id: u128, address: &Address) -> Option<u128> {
+        env.storage()
+            .persistent()
+            .get::<_, RoleGrant>(&StorageKey::OrgRole(org_id, address.clone()))
+            .filter(|grant| grant.role == Role::Manager)
+            .and_then(|grant| grant.scope_department)
+    }
+
+    /// Returns `true` if `department_id` lies in the subtree of `address`'s
+    /// `Manager` role.
+    fn in_manager_scope(env: &Env, org_id: u128, address: &Address, department_id: u128) -> bool {
+        let Some(scope) = Self::manager_scope(env, org_id, address) else {
+            return false;
+        };
+        let mut current = Some(department_id);
+        while let Some(id) = current {
+            if id == scope {
+                return true;
+            }
+            current = Self::load_department(env, id).and_then(|d| d.parent_id);
+        }
+        false
+    }
+
+    fn is_admin(env: &Env, org_id: u128, address: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&StorageKey::OrgAdmins(org_id))
+            .is_some_and(|admins| admins.contains(address))
+    }
+
+    fn is_owner_or_admin(env: &Env, org: &Organization, caller: &Address) -> bool {
+        org.owner == *caller || Self::is_admin(env, org.id, caller)
+    }
+
+    /// Frees the name of a department that is being archived or deleted.
+    fn release_department_name(env: &Env, department_id: u128) {
+        if let Some(key) = Self::name_index_key(env, department_id) {
+            if env.storage().persistent().get::<_, u128>(&key) == Some(department_id) {
+                env.storage().persistent().remove(&key);
+            }
+        }
+    }
+
+    /// Writes an optional entry of a department and lists it in the
+    /// department's `DepartmentKeys`.
+    fn set_department_entry<V: IntoVal<Env, Val>>(
+        env: &Env,
+        department_id: u128,
+        key: StorageKey,
+        value: &V,
+    ) {
+        env.storage().persistent().set(&key, value);
+        let index_key = StorageKey::DepartmentKeys(department_id);
+        // Departments created before the index are probed on deletion instead.
+        let index: Option<Vec<StorageKey>> = env.storage().persistent().get(&index_key);
+        if let Some(mut keys) = index {
+            if !keys.contains(&key) {
+                keys.push_back(key);
+                env.storage().persistent().set(&index_key, &keys);
+            }
+        }
+    }
+
+    /// Removes an optional entry of a department and drops it from the
+    /// department's `DepartmentKeys`.
+    fn remove_department_entry(env: &Env, department_id: u128, key: StorageKey) {
+        env.storage().persistent().remove(&key);
+        let index_key = StorageKey::DepartmentKeys(department_id);
+        let index: Option<Vec<StorageKey>> = env.storage().persistent().get(&index_key);
+        if let Some(mut keys) = index {
+            if let Some(i) = keys.first_index_of(&key) {
+                keys.remove(i);
+                env.storage().persistent().set(&index_key, &keys);
+            }
+        }
+    }
+
+    /// Appends the entries of an empty department that exist to `keys`: its
+    /// record, name index entry while active, `DepartmentKeys` and the
+    /// entries it lists, and its cost-center index entry.
+    fn push_department_entries(env: &Env, department_id: u128, keys: &mut Vec<StorageKey>) {
+        let index_key = StorageKey::DepartmentKeys(department_id);
+        let index: Option<Vec<StorageKey>> = env.storage().persistent().get(&index_key);
+        let optional = match index {
+            Some(index) => {
+                keys.push_back(index_key);
+                index
+            }
+            None => {
+                let mut found = Vec::new(env);
+                for key in [
+                    StorageKey::DepartmentEmployeeCount(department_id),
+                    StorageKey::DepartmentChildren(department_id),
+                    StorageKey::DepartmentManager(department_id),
+                    StorageKey::ManagerIncludesChildren(department_id),
+                    StorageKey::DepartmentHead(department_id),
+                    StorageKey::ArchivedDepartment(department_id),
+                    StorageKey::DepartmentCostCenter(department_id),
+                    StorageKey::DepartmentCapacity(department_id),
+                    StorageKey::DepartmentSnapshots(department_id),
+                    StorageKey::DepartmentAgreement(department_id),
+                ] {
+                    if env.storage().persistent().has(&key) {
+                        found.push_back(key);
+                    }
+                }
+                found
+            }
+        };
+
+        let record_key = StorageKey::DepartmentV2(department_id);
+        let dept: Option<Department> = env.storage().persistent().get(&record_key);
+        let (record_key, org_id, name_key) = match dept {
+            Some(dept) => (
+                record_key,
+                dept.org_id,
+                Some(StorageKey::DeptNameIndexV2(dept.org_id, dept.name)),
+            ),
+            None => {
+                let legacy_key = StorageKey::Department(department_id);
+                let Some(legacy) = env
+                    .storage()
+                    .persistent()
+                    .get::<_, LegacyDepartment>(&legacy_key)
+                else {
+                    return;
+                };
+                // Departments from before names were unique have no entry.
+                let name_key = StorageKey::DeptNameIndex(legacy.org_id, legacy.name);
+                let name_key = env.storage().persistent().has(&name_key).then_some(name_key);
+                (legacy_key, legacy.org_id, name_key)
+            }
+        };
+        let mut archived = false;
+        for key in optional.iter() {
+            match &key {
+                StorageKey::ArchivedDepartment(_) => archived = true,
+                StorageKey::DepartmentCostCenter(_) => {
+                    let code: Option<soroban_sdk::Symbol> = env.storage().persistent().get(&key);
+                    if let Some(code) = code {
+                        keys.push_back(StorageKey::CostCenterIndex(org_id, code));
+                    }
+                }
+                _ => {}
+            }
+            keys.push_back(key);
+        }
+        // Archiving releases the name.
+        if !archived {
+            if let Some(name_key) = name_key {
+                keys.push_back(name_key);
+            }
+        }
+        keys.push_back(record_key);
+    }
+
+    /// Stores a `Manager` or `Viewer` grant and lists its holder in
+    /// `OrgRoleHolders`.
+    fn set_role_grant(
+        env: &Env,
+        org_id: u128,
+        grantee: &Address,
+        grant: &RoleGrant,
+    ) -> Result<(), DepartmentError> {
+        let holders_key = StorageKey::OrgRoleHolders(org_id);
+        let mut holders: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&holders_key)
+            .unwrap_or_else(|| Vec::new(env));
+        if !holders.contains(grantee) {
+            if holders.len() >= MAX_ORG_ROLES {
+                return Err(DepartmentError::RoleLimitReached);
+            }
+            holders.push_back(grantee.clone());
+            env.storage().persistent().set(&holders_key, &holders);
+        }
+        env.storage()
+            .persistent()
+            .set(&StorageKey::OrgRole(org_id, grantee.clone()), grant);
+        Ok(())
+    }
+
+    /// Removes the `Manager` or `Viewer` grant of `grantee`, if any, and its
+    /// entry in `OrgRoleHolders`.
+    fn remove_role_grant(env: &Env, org_id: u128, grantee: &Address) {
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::OrgRole(org_id, grantee.clone()));
+        let holders_key = StorageKey::OrgRoleHolders(org_id);
+        let holders: Option<Vec<Address>> = env.storage().persistent().get(&holders_key);
+        if let Some(mut holders) = holders {
+            if let Some(i) = holders.first_index_of(grantee) {
+                holders.remove(i);
+                env.storage().persistent().set(&holders_key, &holders);
+            }
+        }
+    }
+
+    /// Returns the name index key of a department, in the format of its
+    /// record (legacy or current).
+    fn name_index_key(env: &Env, department_id: u128) -> Option<StorageKey> {
+        let dept: Option<Department> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentV2(department_id));
+        if let Some(dept) = dept {
+            return Some(StorageKey::DeptNameIndexV2(dept.org_id, dept.name));
+        }
+        env.storage()
+            .persistent()
+            .get::<_, LegacyDepartment>(&StorageKey::Department(department_id))
+            .map(|legacy| StorageKey::DeptNameIndex(legacy.org_id, legacy.name))
+    }
+
+    /// Reads an organization record, converting a legacy one.
+    fn load_org(env: &Env, org_id: u128) -> Option<Organization> {
+        let org: Option<Organization> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::OrganizationV2(org_id));
+        org.or_else(|| {
+            env.storage()
+                .persistent()
+                .get::<_, LegacyOrganization>(&StorageKey::Organization(org_id))
+                .map(|legacy| Organization {
+                    id: legacy.id,
+                    name: Self::symbol_to_string(env, &legacy.name),
+                    owner: legacy.owner,
+                    created_at: legacy.created_at,
+                })
+        })
+    }
+
+    /// Writes an organization record in the current format, dropping any
+    /// legacy copy.
+    fn store_org(env: &Env, org: &Organization) {
+        env.storage()
+            .persistent()
+            .set(&StorageKey::OrganizationV2(org.id), org);
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::Organization(org.id));
+    }
+
+    /// Reads a department record, converting a legacy one.
+    fn load_department(env: &Env, department_id: u128) -> Option<Department> {
+        let dept: Option<Department> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::DepartmentV2(department_id));
+        dept.or_else(|| {
+            env.storage()
+                .persistent()
+                .get::<_, LegacyDepartment>(&StorageKey::Department(department_id))
+                .map(|legacy| Department {
+                    id: legacy.id,
+                    org_id: legacy.org_id,
+                    name: Self::symbol_to_string(env, &legacy.name),
+                    parent_id: legacy.parent_id,
+                    created_at: legacy.created_at,
+                })
+        })
+    }
+
+    /// Writes a department record in the current format. A legacy copy is
+    /// dropped, along with any legacy name index entry, and the name of an
+    /// active department is reserved in the current name index. Records
+    /// written before names were unique have no legacy entry to move.
+    fn store_department(env: &Env, dept: &Department) -> Result<(), DepartmentError> {
+        let legacy_key = StorageKey::Department(dept.id);
+        let legacy: Option<LegacyDepartment> = env.storage().persistent().get(&legacy_key);
+        if let Some(legacy) = legacy {
+            if !Self::is_archived(env, dept.id) {
+                let name_key = StorageKey::DeptNameIndexV2(dept.org_id, dept.name.clone());
+                if env.storage().persistent().has(&name_key) {
+                    return Err(DepartmentError::DuplicateDepartmentName);
+                }
+                env.storage().persistent().set(&name_key, &dept.id);
+            }
+            let old_name_key = StorageKey::DeptNameIndex(legacy.org_id, legacy.name);
+            if env.storage().persistent().get::<_, u128>(&old_name_key) == Some(dept.id) {
+                env.storage().persistent().remove(&old_name_key);
+            }
+            env.storage().persistent().remove(&legacy_key);
+        }
+        env.storage()
+            .persistent()
+            .set(&StorageKey::DepartmentV2(dept.id), dept);
+        Ok(())
+    }
+
+    fn symbol_to_string(env: &Env, symbol: &soroban_sdk::Symbol) -> String {
+        let name = SymbolStr::try_from_val(env, &symbol.to_symbol_val()).unwrap();
+        String::from_str(env, name.as_ref())
+    }
+
+    fn validate_name(name: &String) -> Result<(), DepartmentError> {
+        if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+            return Err(DepartmentError::InvalidName);
+        }
+        Ok(())
+    }
+
+    fn org_settings(env: &Env, org_id: u128) -> OrgSettings {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::OrgSettings(org_id))
+            .unwrap_or(OrgSettings {
+                max_depth: MAX_DEPTH,
+            })
+    }
+
+    fn is_archived(env: &Env, department_id: u128) -> bool {
+        env.storage()
+            .persistent()
+            .has(&StorageKey::ArchivedDepartment(department_id))
     }
 
     /// Asserts the contract has been initialized.
     ///
-    /// # Panics
-    /// Panics with `"Contract not initialized"` if `initialize` was never called.
-    fn require_initialized(env: &Env) {
+    /// # Errors
+    /// `NotInitialized` if `initialize` was never called.
+    fn require_initialized(env: &Env) -> Result<(), DepartmentError> {
         let initialized: bool = env
             .storage()
             .persistent()
             .get(&StorageKey::Initialized)
             .unwrap_or(false);
-        assert!(initialized, "Contract not initialized");
+        if !initialized {
+            return Err(DepartmentError::NotInitialized);
+        }
+        Ok(())
     }
 }
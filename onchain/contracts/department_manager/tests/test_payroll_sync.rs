@@ -0,0 +1,260 @@
+//! Payroll agreement sync tests against the payroll contract wasm.
+//!
+//! Covers:
+//! - Linking departments to agreements (rights, unlinking, events)
+//! - `sync_from_payroll` (new agreement employees, employees placed elsewhere,
+//!   capacity, per-call cap, rights, missing link or payroll contract)
+
+#![cfg(test)]
+
+use department_manager::{
+    DepartmentError, DepartmentManagerContract, DepartmentManagerContractClient, MAX_PAYROLL_SYNC,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    vec, Address, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec,
+};
+
+/// Payroll contract build committed for the gas benchmarks. Its spec cannot
+/// be imported with `contractimport!`, so calls go through `invoke_contract`.
+const PAYROLL_WASM: &[u8] =
+    include_bytes!("../../stello_pay_contract/tests/stello_pay_contract.wasm");
+
+fn call_payroll<T: TryFromVal<Env, Val>>(
+    env: &Env,
+    payroll: &Address,
+    func: &str,
+    args: Vec<Val>,
+) -> T {
+    env.invoke_contract(payroll, &Symbol::new(env, func), args)
+}
+
+struct Setup {
+    env: Env,
+    client: DepartmentManagerContractClient<'static>,
+    owner: Address,
+    org_id: u128,
+    department_id: u128,
+    payroll: Address,
+    agreement_id: u128,
+}
+
+/// An org whose payroll contract holds an empty agreement linked to its
+/// single department.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(DepartmentManagerContract, ());
+    let client = DepartmentManagerContractClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+    let owner = Address::generate(&env);
+    let org_id = client.create_organization(&owner, &String::from_str(&env, "Acme"));
+    let department_id =
+        client.create_department(&owner, &org_id, &String::from_str(&env, "Eng"), &None);
+
+    let payroll = env.register(PAYROLL_WASM, ());
+    let employer = Address::generate(&env);
+    call_payroll::<()>(
+        &env,
+        &payroll,
+        "initialize",
+        vec![&env, employer.into_val(&env)],
+    );
+    let agreement_id: u128 = call_payroll(
+        &env,
+        &payroll,
+        "create_payroll_agreement",
+        vec![
+            &env,
+            employer.into_val(&env),
+            Address::generate(&env).into_val(&env),
+            3600u64.into_val(&env),
+        ],
+    );
+
+    client.set_payroll_contract(&owner, &org_id, &Some(payroll.clone()));
+    client.set_department_agreement(&owner, &department_id, &Some(agreement_id));
+
+    Setup {
+        env,
+        client,
+        owner,
+        org_id,
+        department_id,
+        payroll,
+        agreement_id,
+    }
+}
+
+/// Adds `count` new employees to the agreement and returns them.
+fn hire(s: &Setup, count: u32) -> Vec<Address> {
+    let mut employees = Vec::new(&s.env);
+    for _ in 0..count {
+        let employee = Address::generate(&s.env);
+        call_payroll::<()>(
+            &s.env,
+            &s.payroll,
+            "add_employee_to_agreement",
+            vec![
+                &s.env,
+                s.agreement_id.into_val(&s.env),
+                employee.into_val(&s.env),
+                1000i128.into_val(&s.env),
+            ],
+        );
+        employees.push_back(employee);
+    }
+    employees
+}
+
+/// Returns the topics and data of the last event published.
+fn last_event<T: TryFromVal<Env, Val>>(env: &Env) -> (Vec<Val>, T) {
+    let (_contract, topics, data) = env.events().all().last().unwrap();
+    (topics, T::try_from_val(env, &data).unwrap())
+}
+
+#[test]
+fn test_sync_adds_new_agreement_employees() {
+    let s = setup();
+    let employees = hire(&s, 3);
+
+    assert_eq!(s.client.sync_from_payroll(&s.owner, &s.department_id), 3);
+    let (topics, added) = last_event::<u32>(&s.env);
+    assert_eq!(
+        topics,
+        vec![
+            &s.env,
+            symbol_short!("pay_sync").into_val(&s.env),
+            s.department_id.into_val(&s.env)
+        ]
+    );
+    assert_eq!(added, 3);
+    assert_eq!(
+        s.client.get_department_employees(&s.department_id),
+        employees
+    );
+
+    // Only employees added to the agreement since are new.
+    let later = hire(&s, 1).get(0).unwrap();
+    assert_eq!(s.client.sync_from_payroll(&s.owner, &s.department_id), 1);
+    assert_eq!(
+        s.client.get_employee_department(&later, &s.org_id),
+        Some(s.department_id)
+    );
+    assert_eq!(s.client.sync_from_payroll(&s.owner, &s.department_id), 0);
+    assert_eq!(s.client.get_department_employee_count(&s.department_id), 4);
+}
+
+#[test]
+fn test_sync_leaves_employees_of_other_departments() {
+    let s = setup();
+    let ops =
+        s.client
+            .create_department(&s.owner, &s.org_id, &String::from_str(&s.env, "Ops"), &None);
+    let employees = hire(&s, 2);
+    let moved = employees.get(0).unwrap();
+    s.client
+        .assign_employee_to_department(&s.owner, &s.org_id, &ops, &moved);
+
+    assert_eq!(s.client.sync_from_payroll(&s.owner, &s.department_id), 1);
+    assert_eq!(
+        s.client.get_employee_department(&moved, &s.org_id),
+        Some(ops)
+    );
+    assert_eq!(
+        s.client.get_department_employees(&s.department_id),
+        vec![&s.env, employees.get(1).unwrap()]
+    );
+}
+
+#[test]
+fn test_sync_respects_capacity_and_call_cap() {
+    let s = setup();
+    hire(&s, MAX_PAYROLL_SYNC + 2);
+    s.client
+        .set_department_capacity(&s.owner, &s.department_id, &Some(2));
+
+    assert_eq!(s.client.sync_from_payroll(&s.owner, &s.department_id), 2);
+    assert_eq!(s.client.sync_from_payroll(&s.owner, &s.department_id), 0);
+
+    s.client
+        .set_department_capacity(&s.owner, &s.department_id, &None);
+    assert_eq!(
+        s.client.sync_from_payroll(&s.owner, &s.department_id),
+        MAX_PAYROLL_SYNC
+    );
+    assert_eq!(s.client.sync_from_payroll(&s.owner, &s.department_id), 0);
+    assert_eq!(
+        s.client.get_department_employee_count(&s.department_id),
+        MAX_PAYROLL_SYNC + 2
+    );
+}
+
+#[test]
+fn test_sync_requires_rights_and_link() {
+    let s = setup();
+    hire(&s, 2);
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_sync_from_payroll(&stranger, &s.department_id),
+        Err(Ok(DepartmentError::NotOrgOwnerOrManager))
+    );
+    assert_eq!(
+        s.client
+            .try_set_department_agreement(&stranger, &s.department_id, &None),
+        Err(Ok(DepartmentError::NotOrgOwner))
+    );
+    assert_eq!(
+        s.client.try_sync_from_payroll(&s.owner, &999),
+        Err(Ok(DepartmentError::DeptNotFound))
+    );
+
+    // A department manager may sync its own department.
+    let manager = Address::generate(&s.env);
+    s.client
+        .set_department_manager(&s.owner, &s.department_id, &Some(manager.clone()));
+    assert_eq!(s.client.sync_from_payroll(&manager, &s.department_id), 2);
+
+    s.client.set_payroll_contract(&s.owner, &s.org_id, &None);
+    assert_eq!(
+        s.client.try_sync_from_payroll(&s.owner, &s.department_id),
+        Err(Ok(DepartmentError::AgreementNotLinked))
+    );
+    s.client
+        .set_payroll_contract(&s.owner, &s.org_id, &Some(s.payroll.clone()));
+
+    s.client
+        .set_department_agreement(&s.owner, &s.department_id, &None);
+    let (topics, agreement) = last_event::<Option<u128>>(&s.env);
+    assert_eq!(
+        topics,
+        vec![
+            &s.env,
+            symbol_short!("agr_set").into_val(&s.env),
+            s.department_id.into_val(&s.env)
+        ]
+    );
+    assert_eq!(agreement, None);
+    assert_eq!(s.client.get_department_agreement(&s.department_id), None);
+    assert_eq!(
+        s.client.try_sync_from_payroll(&s.owner, &s.department_id),
+        Err(Ok(DepartmentError::AgreementNotLinked))
+    );
+
+    // Archived departments take no one.
+    s.client
+        .set_department_agreement(&s.owner, &s.department_id, &Some(s.agreement_id));
+    hire(&s, 1);
+    for employee in s.client.get_department_employees(&s.department_id).iter() {
+        s.client
+            .remove_employee_from_department(&s.owner, &s.org_id, &employee);
+    }
+    s.client
+        .archive_department(&s.owner, &s.department_id, &false);
+    assert_eq!(
+        s.client.try_sync_from_payroll(&s.owner, &s.department_id),
+        Err(Ok(DepartmentError::DeptArchived))
+    );
+}
@@ -1,10 +1,115 @@
 #![no_std]
+#![allow(deprecated)] // env.events().publish() — codebase-wide pattern
 
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, BytesN, Env, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, token, vec, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, Val, Vec,
+};
+
+/// Maximum number of recipients in a single `BatchPayment` operation.
+const MAX_BATCH_RECIPIENTS: u32 = 50;
+
+/// Maximum number of operations returned by `get_pending_operations`.
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// Number of ids in one `PendingOps` or `StatusOps` page.
+const INDEX_PAGE_LEN: u32 = 100;
+
+/// Maximum length of an operation memo in bytes.
+const MAX_MEMO_LEN: u32 = 128;
+
+/// Maximum number of tokens reported by `get_treasury_balances`.
+const MAX_TREASURY_TOKENS: u32 = 20;
+
+/// `SchedulerControl` action that calls `pause_job`.
+pub const SCHEDULER_PAUSE: u32 = 0;
+/// `SchedulerControl` action that calls `resume_job`.
+pub const SCHEDULER_RESUME: u32 = 1;
+/// `SchedulerControl` action that calls `cancel_job`.
+pub const SCHEDULER_CANCEL: u32 = 2;
+
+/// Length of a direct-payment spending bucket.
+const DAY_SECONDS: u64 = 86_400;
+
+/// Remaining TTL (in ledgers) below which touched state is extended,
+/// roughly 30 days at ~5 s/ledger.
+pub const TTL_MIN_LEDGERS: u32 = 518_400;
+
+/// TTL (in ledgers) that touched state is extended to, roughly one year.
+pub const TTL_MAX_LEDGERS: u32 = 6_307_200;
 
 #[contract]
 pub struct MultisigContract;
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum MultisigError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    NotSigner = 3,
+    NotGuardian = 4,
+    OperationNotFound = 5,
+    OperationNotPending = 6,
+    InvalidThreshold = 7,
+    DuplicateSigner = 8,
+    Unauthorized = 9,
+    /// The initial signer set is empty.
+    NoSigners = 10,
+    /// Removing the signer would leave less non-expired weight than the threshold.
+    SignerCountBelowThreshold = 11,
+    InvalidAmount = 12,
+    /// Batch is empty or has more than 50 recipients.
+    InvalidBatch = 13,
+    InsufficientBalance = 14,
+    InvalidSpender = 15,
+    /// The guardian policy does not allow this operation type.
+    GuardianTypeNotAllowed = 16,
+    /// The payment exceeds the guardian policy's `max_payment`.
+    GuardianLimitExceeded = 17,
+    /// The guardian policy requires an approval beyond the creator's.
+    CosignerRequired = 18,
+    Overflow = 19,
+    /// An identical operation is already pending.
+    DuplicateOperation = 20,
+    /// The operation has not reached its threshold under an execution delay.
+    NotQueued = 21,
+    /// The execution delay for the operation has not elapsed yet.
+    TimelockActive = 22,
+    /// Signer weights are zero or do not match the signer list.
+    InvalidWeight = 23,
+    /// A direct payment would exceed the token's daily limit.
+    DailyLimitExceeded = 24,
+    /// The operation's approvals do not reach the threshold.
+    ThresholdNotMet = 25,
+    /// The operation memo is longer than 128 bytes.
+    MemoTooLong = 26,
+    /// An `Invoke` operation targets the multisig itself without owner consent.
+    SelfInvokeNotAllowed = 27,
+    /// The operation has no emergency execution queued.
+    NotEmergencyQueued = 28,
+    /// The guardian already queued this operation for emergency execution.
+    EmergencyAlreadyQueued = 29,
+    /// The emergency challenge window has already elapsed.
+    ChallengeWindowClosed = 30,
+    /// The treasury token list already holds 20 tokens.
+    TreasuryTokenLimit = 31,
+    /// The token is already registered in the treasury list.
+    TokenAlreadyRegistered = 32,
+    /// The token is not registered in the treasury list.
+    TokenNotRegistered = 33,
+    /// `SchedulerControl` action is not pause, resume or cancel.
+    InvalidSchedulerAction = 34,
+    /// The operation has not reached `expires_at`, or has met its threshold.
+    NotExpired = 35,
+    /// The operation is past `expires_at` and can only be expired or cancelled.
+    OperationExpired = 36,
+    /// The operation type requires the owner's approval, which is missing.
+    OwnerApprovalRequired = 37,
+    /// `execute_after` is at or beyond the proposal's `expires_at`.
+    ExecuteAfterPastExpiry = 38,
+}
+
 /// Operation kinds supported by the multisig.
 ///
 /// These are intentionally generic so that off-chain automation or
@@ -15,15 +120,103 @@ pub enum OperationKind {
     /// Multi-sig approval for a contract upgrade.
     ///
     /// Tuple layout: (target, new_wasm_hash)
+    ///
+    /// If `target` is the multisig itself its own wasm is replaced; otherwise
+    /// `upgrade(new_wasm_hash)` is invoked on `target`.
     ContractUpgrade(Address, BytesN<32>),
     /// Direct token payment executed from the multisig wallet.
     ///
     /// Tuple layout: (token, to, amount)
     LargePayment(Address, Address, i128),
-    /// Dispute resolution intent for an external payroll-style contract.
+    /// Dispute resolution on an external payroll-style contract.
     ///
     /// Tuple layout: (payroll_contract, agreement_id, pay_employee, refund_employer)
+    ///
+    /// Executes `resolve_dispute` on `payroll_contract` with the multisig as
+    /// the arbiter, so the multisig must be configured as that contract's arbiter.
     DisputeResolution(Address, u128, i128, i128),
+    /// Adds a new signer to the signer set.
+    AddSigner(Address),
+    /// Removes an existing signer from the signer set.
+    ///
+    /// The signer's approvals on other pending operations are purged.
+    RemoveSigner(Address),
+    /// Sets a new approval threshold (must be between 1 and the signer count).
+    ///
+    /// Pending operations are not re-evaluated when this executes. They are
+    /// checked against the new threshold on their next approval and again by
+    /// `execute_operation`, so an operation already queued under the old
+    /// threshold fails with `ThresholdNotMet` if it no longer meets it.
+    ChangeThreshold(u32),
+    /// Token payout run executed from the multisig wallet (all-or-nothing).
+    ///
+    /// Tuple layout: (token, [(to, amount)]), at most 50 recipients.
+    BatchPayment(Address, Vec<(Address, i128)>),
+    /// Token allowance granted from the multisig wallet.
+    ///
+    /// Tuple layout: (token, spender, amount, live_until_ledger)
+    ///
+    /// Executes `approve` on `token`, letting `spender` pull up to `amount`
+    /// with `transfer_from` until `live_until_ledger`. An amount of 0 revokes.
+    TokenApproval(Address, Address, i128, u32),
+    /// Rotates (`Some`) or clears (`None`) the emergency guardian.
+    SetGuardian(Option<Address>),
+    /// Arbitrary contract call made with the multisig as the invoker.
+    ///
+    /// Tuple layout: (target, function, args)
+    ///
+    /// `require_auth` checks for the multisig's address inside `target` are
+    /// satisfied by the call itself. Targeting the multisig is rejected unless
+    /// the owner has enabled it with `set_allow_self_invoke`.
+    Invoke(Address, Symbol, Vec<Val>),
+    /// Pause, resume or cancel a payment scheduler job the multisig employs.
+    ///
+    /// Tuple layout: (scheduler, job_id, action)
+    ///
+    /// `action` is one of `SCHEDULER_PAUSE`, `SCHEDULER_RESUME` or
+    /// `SCHEDULER_CANCEL`; the multisig is passed as the job's employer.
+    SchedulerControl(Address, u128, u32),
+    /// Replaces the whole signer set and threshold in one step.
+    ///
+    /// Tuple layout: (new_signers, new_threshold)
+    ///
+    /// Validated like `initialize` without weights: every new signer weighs 1.
+    /// Approvals by signers outside the new set are purged from other pending
+    /// operations.
+    RotateSigners(Vec<Address>, u32),
+}
+
+/// Data-free tag for each `OperationKind`, used by the guardian policy.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperationType {
+    ContractUpgrade,
+    LargePayment,
+    DisputeResolution,
+    AddSigner,
+    RemoveSigner,
+    ChangeThreshold,
+    BatchPayment,
+    TokenApproval,
+    SetGuardian,
+    Invoke,
+    SchedulerControl,
+    RotateSigners,
+}
+
+/// Owner-configured limits on what the emergency guardian may execute.
+///
+/// Without a policy the guardian can execute any pending operation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianPolicy {
+    /// Cap on the amount of a `LargePayment` (or total of a `BatchPayment`).
+    pub max_payment: Option<i128>,
+    /// Operation types the guardian is allowed to execute.
+    pub allowed_types: Vec<OperationType>,
+    /// If true, the operation must carry at least one approval from a signer
+    /// other than its creator.
+    pub require_cosigner: bool,
 }
 
 #[contracttype]
@@ -32,6 +225,16 @@ pub enum OperationStatus {
     Pending,
     Executed,
     Cancelled,
+    /// Reached `expires_at` without meeting the threshold.
+    Expired,
+}
+
+/// Owner-configured deposit a signer pays to propose an operation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalBond {
+    pub token: Address,
+    pub amount: i128,
 }
 
 #[contracttype]
@@ -43,6 +246,34 @@ pub struct Operation {
     pub status: OperationStatus,
     pub created_at: u64,
     pub executed_at: Option<u64>,
+    /// Set when the threshold is met while an execution delay is configured;
+    /// the operation can be executed with `execute_operation` from then on.
+    pub executable_at: Option<u64>,
+    /// Outcome of the last execution attempt: `Some(true)` once executed,
+    /// `Some(false)` if a cross-contract call failed under `execute_operation`.
+    pub execution_result: Option<bool>,
+    /// Set when the guardian queues an emergency execution under a challenge
+    /// period; `emergency_finalize` may execute the operation from then on.
+    pub emergency_executable_at: Option<u64>,
+    /// Earliest timestamp at which the operation may execute, set by the
+    /// proposer (e.g. a payday). Approvals completing earlier queue it.
+    pub execute_after: Option<u64>,
+    /// Deadline for reaching the threshold, set when the owner configured a
+    /// proposal lifetime; afterwards the operation can be expired.
+    pub expires_at: Option<u64>,
+}
+
+/// Outcome of the last persisted execution attempt of an operation that
+/// calls another contract (`ContractUpgrade` of an external target,
+/// `DisputeResolution`, `Invoke`, `SchedulerControl`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionRecord {
+    pub operation_id: u128,
+    pub executed_at: u64,
+    pub success: bool,
+    /// Error code returned by the downstream contract when the call failed.
+    pub error_code: Option<u32>,
 }
 
 #[contracttype]
@@ -52,10 +283,164 @@ enum StorageKey {
     Owner,
     EmergencyGuardian,
     Signers,
+    /// Voting weight of a signer; signers without an entry weigh 1.
+    SignerWeight(Address),
+    /// Last timestamp at which a signer may sign; absent means no expiry.
+    SignerExpiry(Address),
     Threshold,
     OperationCounter,
     Operation(u128),
+    /// Approving signers in approval order, for listing and weight sums.
     Approvals(u128),
+    /// Set when a signer has approved an operation; answers duplicate checks
+    /// without scanning the approval list.
+    Approval(u128, Address),
+    /// Optional free-form context for an operation (e.g. invoice reference).
+    OperationMemo(u128),
+    /// Ids of operations still in `Pending` status whose id falls in page
+    /// `id / INDEX_PAGE_LEN`, in proposal order. Empty pages are removed.
+    PendingOps(u32),
+    /// Numbers of the non-empty `PendingOps` pages, in ascending order.
+    PendingPages,
+    /// Content hash of a pending operation's kind -> its id.
+    PendingByHash(BytesN<32>),
+    /// Page of at most `INDEX_PAGE_LEN` ids of operations that reached a
+    /// final status, in the order they did. Pending operations are tracked
+    /// by `PendingOps` instead.
+    StatusOps(OperationStatus, u32),
+    /// Number of operations that reached a final status.
+    StatusCount(OperationStatus),
+    GuardianPolicy,
+    ExecutionDelay,
+    /// Seconds signers have to challenge a queued emergency execution.
+    ChallengePeriod,
+    /// Whether operations execute as soon as they reach the threshold.
+    AutoExecute,
+    /// Whether `Invoke` operations may target the multisig itself.
+    AllowSelfInvoke,
+    /// Per-token cap on direct payments per day.
+    DailyLimit(Address),
+    /// Direct-payment spend for a token as `(day bucket, spent)`; a single
+    /// entry per token that resets when the day changes.
+    DailySpent(Address),
+    /// Tokens whose balances `get_treasury_balances` reports.
+    TreasuryTokens,
+    /// Bond required to propose; absent disables bonds.
+    ProposalBond,
+    /// Bond held for an operation until it is executed, cancelled or expired.
+    OperationBond(u128),
+    /// Total of the bonds held in a token; payouts cannot spend it.
+    HeldBonds(Address),
+    /// Seconds a proposal has to reach the threshold; 0 means no expiry.
+    ProposalLifetime,
+    /// Operation types that need the owner's approval on top of the threshold.
+    OwnerApprovalTypes,
+    /// Set once the owner has approved an operation.
+    OwnerApproval(u128),
+    /// Outcome of the last cross-contract execution attempt of an operation.
+    ExecutionRecord(u128),
+    /// Set when an operation was executed by the emergency guardian.
+    ExecutedByGuardian(u128),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigInitializedEvent {
+    pub owner: Address,
+    pub signer_count: u32,
+    pub threshold: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianPolicyChangedEvent {
+    /// True when the policy was removed; the remaining fields are then empty.
+    pub cleared: bool,
+    pub max_payment: Option<i128>,
+    pub allowed_types: Vec<OperationType>,
+    pub require_cosigner: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionDelayChangedEvent {
+    pub old_delay: u64,
+    pub new_delay: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengePeriodChangedEvent {
+    pub old_period: u64,
+    pub new_period: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyQueuedEvent {
+    pub operation_id: u128,
+    pub guardian: Address,
+    pub executable_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyChallengedEvent {
+    pub operation_id: u128,
+    pub signer: Address,
+}
+
+/// Emitted ahead of `operation_executed` when the guardian executes an
+/// operation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyExecutedEvent {
+    pub operation_id: u128,
+    pub guardian: Address,
+    /// Approved weight at execution, comparable with `threshold`.
+    pub approvals_at_execution: u32,
+    pub threshold: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoExecuteChangedEvent {
+    pub enabled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SelfInvokeChangedEvent {
+    pub allowed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationExecutionFailedEvent {
+    pub operation_id: u128,
+    pub error_code: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyLimitChangedEvent {
+    pub token: Address,
+    pub limit: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerExpiryChangedEvent {
+    pub signer: Address,
+    pub valid_until: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryTokenChangedEvent {
+    pub token: Address,
+    /// True when the token was registered, false when it was removed.
+    pub registered: bool,
 }
 
 #[contracttype]
@@ -63,6 +448,7 @@ enum StorageKey {
 pub struct OperationProposedEvent {
     pub operation_id: u128,
     pub creator: Address,
+    pub memo: Option<Bytes>,
 }
 
 #[contracttype]
@@ -71,9 +457,26 @@ pub struct OperationApprovedEvent {
     pub operation_id: u128,
     pub signer: Address,
     pub approvals: u32,
+    pub approved_weight: u32,
     pub threshold: u32,
 }
 
+/// Emitted when the owner approves an operation, separately from signer
+/// approvals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnerApprovedEvent {
+    pub operation_id: u128,
+    pub owner: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnerApprovalRequirementChangedEvent {
+    pub op_type: OperationType,
+    pub required: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct OperationExecutedEvent {
@@ -84,338 +487,2257 @@ pub struct OperationExecutedEvent {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct OperationCancelledEvent {
     pub operation_id: u128,
+    /// Bond kept by the multisig, 0 if none was posted or it was refunded.
+    pub bond_forfeited: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationExpiredEvent {
+    pub operation_id: u128,
+    /// Bond kept by the multisig, 0 if none was posted.
+    pub bond_forfeited: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondRefundedEvent {
+    pub operation_id: u128,
+    pub proposer: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalBondChangedEvent {
+    pub token: Address,
+    /// 0 when bonds were disabled.
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalLifetimeChangedEvent {
+    pub old_lifetime: u64,
+    pub new_lifetime: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DirectPaymentEvent {
+    pub signer: Address,
+    pub token: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub spent_today: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationQueuedEvent {
+    pub operation_id: u128,
+    pub executable_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchPaymentItemEvent {
+    pub operation_id: u128,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchPaymentExecutedEvent {
+    pub operation_id: u128,
+    pub token: Address,
+    pub recipients: u32,
+    pub total: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThresholdChangedEvent {
+    pub operation_id: u128,
+    pub old_threshold: u32,
+    pub new_threshold: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianChangedEvent {
+    pub old_guardian: Option<Address>,
+    pub new_guardian: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerAddedEvent {
+    pub operation_id: u128,
+    pub signer: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerRemovedEvent {
+    pub operation_id: u128,
+    pub signer: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignersRotatedEvent {
+    pub operation_id: u128,
+    pub old_count: u32,
+    pub new_count: u32,
+    pub old_threshold: u32,
+    pub new_threshold: u32,
+}
+
+/// Emitted for each pending operation that lost an approval because its
+/// approver was removed from the signer set.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalsRecomputedEvent {
+    pub operation_id: u128,
+    pub removed_signer: Address,
+    pub approvals: u32,
+    pub approved_weight: u32,
+    /// Whether the remaining approvals still meet the threshold. The
+    /// operation is not executed as a side effect either way.
+    pub threshold_met: bool,
+}
+
+/// Keeps a persistent entry from being archived while the wallet is in use.
+fn extend_ttl(env: &Env, key: &StorageKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_MIN_LEDGERS, TTL_MAX_LEDGERS);
+}
+
+fn extend_ttl_if_present(env: &Env, key: &StorageKey) {
+    if env.storage().persistent().has(key) {
+        extend_ttl(env, key);
+    }
+}
+
+/// Extends the contract instance and every configuration entry.
+fn extend_config_ttl(env: &Env) -> Result<(), MultisigError> {
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_MIN_LEDGERS, TTL_MAX_LEDGERS);
+    for key in [
+        StorageKey::Initialized,
+        StorageKey::Owner,
+        StorageKey::Signers,
+        StorageKey::Threshold,
+        StorageKey::EmergencyGuardian,
+        StorageKey::OperationCounter,
+        StorageKey::PendingPages,
+        StorageKey::GuardianPolicy,
+        StorageKey::ExecutionDelay,
+        StorageKey::ChallengePeriod,
+        StorageKey::AutoExecute,
+        StorageKey::AllowSelfInvoke,
+        StorageKey::TreasuryTokens,
+        StorageKey::ProposalBond,
+        StorageKey::ProposalLifetime,
+        StorageKey::OwnerApprovalTypes,
+    ] {
+        extend_ttl_if_present(env, &key);
+    }
+    for signer in read_signers(env)?.iter() {
+        extend_ttl_if_present(env, &StorageKey::SignerWeight(signer.clone()));
+        extend_ttl_if_present(env, &StorageKey::SignerExpiry(signer));
+    }
+    Ok(())
 }
 
-fn require_initialized(env: &Env) {
+fn require_initialized(env: &Env) -> Result<(), MultisigError> {
     let initialized = env
         .storage()
         .persistent()
         .get::<_, bool>(&StorageKey::Initialized)
         .unwrap_or(false);
-    assert!(initialized, "Contract not initialized");
+    if !initialized {
+        return Err(MultisigError::NotInitialized);
+    }
+    // Every entry point goes through here, so the entries the wallet cannot
+    // work without are kept alive on each use.
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_MIN_LEDGERS, TTL_MAX_LEDGERS);
+    for key in [
+        StorageKey::Initialized,
+        StorageKey::Owner,
+        StorageKey::Signers,
+        StorageKey::Threshold,
+    ] {
+        extend_ttl(env, &key);
+    }
+    Ok(())
 }
 
-fn read_signers(env: &Env) -> Vec<Address> {
+fn read_signers(env: &Env) -> Result<Vec<Address>, MultisigError> {
     env.storage()
         .persistent()
         .get::<_, Vec<Address>>(&StorageKey::Signers)
-        .expect("Signers not set")
+        .ok_or(MultisigError::NotInitialized)
 }
 
-fn read_threshold(env: &Env) -> u32 {
+fn read_threshold(env: &Env) -> Result<u32, MultisigError> {
     env.storage()
         .persistent()
         .get::<_, u32>(&StorageKey::Threshold)
-        .expect("Threshold not set")
+        .ok_or(MultisigError::NotInitialized)
+}
+
+fn signer_weight(env: &Env, signer: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<_, u32>(&StorageKey::SignerWeight(signer.clone()))
+        .unwrap_or(1)
+}
+
+fn sum_weights(env: &Env, signers: &Vec<Address>) -> Result<u32, MultisigError> {
+    let mut total: u32 = 0;
+    for signer in signers.iter() {
+        total = total
+            .checked_add(signer_weight(env, &signer))
+            .ok_or(MultisigError::Overflow)?;
+    }
+    Ok(total)
+}
+
+/// The threshold is a weight sum, so it must lie in `1..=active signer weight`.
+/// Expired signers cannot approve, so their weight does not count.
+fn check_threshold(env: &Env, threshold: u32) -> Result<(), MultisigError> {
+    let total_weight = active_weight(env, &read_signers(env)?)?;
+    if threshold == 0 || threshold > total_weight {
+        return Err(MultisigError::InvalidThreshold);
+    }
+    Ok(())
+}
+
+/// Rejects a signer list that names the same address twice.
+fn check_unique_signers(signers: &Vec<Address>) -> Result<(), MultisigError> {
+    let count = signers.len();
+    for i in 0..count {
+        let a = signers.get(i).unwrap();
+        for j in (i + 1)..count {
+            if a == signers.get(j).unwrap() {
+                return Err(MultisigError::DuplicateSigner);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validates an unweighted signer set the way `initialize` does.
+fn check_signer_set(signers: &Vec<Address>, threshold: u32) -> Result<(), MultisigError> {
+    if signers.is_empty() {
+        return Err(MultisigError::NoSigners);
+    }
+    if threshold == 0 || threshold > signers.len() {
+        return Err(MultisigError::InvalidThreshold);
+    }
+    check_unique_signers(signers)
+}
+
+/// Validates a batch payout list and returns its total.
+fn validate_batch(payments: &Vec<(Address, i128)>) -> Result<i128, MultisigError> {
+    let count = payments.len();
+    if count == 0 || count > MAX_BATCH_RECIPIENTS {
+        return Err(MultisigError::InvalidBatch);
+    }
+
+    let mut total: i128 = 0;
+    for (_, amount) in payments.iter() {
+        if amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+        total = total.checked_add(amount).ok_or(MultisigError::Overflow)?;
+    }
+    Ok(total)
+}
+
+fn check_approval(env: &Env, spender: &Address, amount: i128) -> Result<(), MultisigError> {
+    if amount < 0 {
+        return Err(MultisigError::InvalidAmount);
+    }
+    // The multisig cannot meaningfully grant an allowance to itself.
+    if *spender == env.current_contract_address() {
+        return Err(MultisigError::InvalidSpender);
+    }
+    Ok(())
 }
 
-fn is_signer(env: &Env, addr: &Address) -> bool {
-    let signers = read_signers(env);
-    for i in 0..signers.len() {
-        if &signers.get(i).unwrap() == addr {
-            return true;
+/// Whether `signer`'s signing rights have lapsed. Lapsed signers stay in the
+/// signer list but cannot sign, and their approvals no longer count.
+fn signer_expired(env: &Env, signer: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<_, u64>(&StorageKey::SignerExpiry(signer.clone()))
+        .is_some_and(|valid_until| env.ledger().timestamp() > valid_until)
+}
+
+/// Sums the weights of the approvers whose signing rights have not lapsed.
+fn active_weight(env: &Env, approvals: &Vec<Address>) -> Result<u32, MultisigError> {
+    let mut active = Vec::new(env);
+    for signer in approvals.iter() {
+        if !signer_expired(env, &signer) {
+            active.push_back(signer);
         }
     }
-    false
+    sum_weights(env, &active)
+}
+
+fn is_signer(env: &Env, addr: &Address) -> Result<bool, MultisigError> {
+    Ok(read_signers(env)?.contains(addr) && !signer_expired(env, addr))
+}
+
+fn require_signer(env: &Env, addr: &Address) -> Result<(), MultisigError> {
+    if !is_signer(env, addr)? {
+        return Err(MultisigError::NotSigner);
+    }
+    Ok(())
 }
 
-fn next_operation_id(env: &Env) -> u128 {
+fn next_operation_id(env: &Env) -> Result<u128, MultisigError> {
     let current = env
         .storage()
         .persistent()
         .get::<_, u128>(&StorageKey::OperationCounter)
         .unwrap_or(0);
-    let next = current.checked_add(1).expect("Operation id overflow");
+    let next = current.checked_add(1).ok_or(MultisigError::Overflow)?;
     env.storage()
         .persistent()
         .set(&StorageKey::OperationCounter, &next);
-    next
+    Ok(next)
 }
 
-fn read_operation(env: &Env, operation_id: u128) -> Operation {
-    env.storage()
+fn read_operation(env: &Env, operation_id: u128) -> Result<Operation, MultisigError> {
+    let key = StorageKey::Operation(operation_id);
+    let op = env
+        .storage()
         .persistent()
-        .get::<_, Operation>(&StorageKey::Operation(operation_id))
-        .expect("Operation not found")
+        .get::<_, Operation>(&key)
+        .ok_or(MultisigError::OperationNotFound)?;
+    extend_ttl(env, &key);
+    Ok(op)
+}
+
+fn read_pending_operation(env: &Env, operation_id: u128) -> Result<Operation, MultisigError> {
+    let op = read_operation(env, operation_id)?;
+    if op.status != OperationStatus::Pending {
+        return Err(MultisigError::OperationNotPending);
+    }
+    Ok(op)
 }
 
 fn write_operation(env: &Env, op: &Operation) {
-    env.storage()
-        .persistent()
-        .set(&StorageKey::Operation(op.id), op);
+    let key = StorageKey::Operation(op.id);
+    env.storage().persistent().set(&key, op);
+    extend_ttl(env, &key);
 }
 
-fn read_approvals(env: &Env, operation_id: u128) -> Vec<Address> {
+fn pending_page_of(id: u128) -> u32 {
+    (id / u128::from(INDEX_PAGE_LEN)) as u32
+}
+
+fn read_pending_pages(env: &Env) -> Vec<u32> {
     env.storage()
         .persistent()
-        .get::<_, Vec<Address>>(&StorageKey::Approvals(operation_id))
+        .get::<_, Vec<u32>>(&StorageKey::PendingPages)
         .unwrap_or(Vec::new(env))
 }
 
-fn write_approvals(env: &Env, operation_id: u128, approvals: &Vec<Address>) {
+fn read_pending_page(env: &Env, page: u32) -> Vec<u128> {
     env.storage()
         .persistent()
-        .set(&StorageKey::Approvals(operation_id), approvals);
+        .get::<_, Vec<u128>>(&StorageKey::PendingOps(page))
+        .unwrap_or(Vec::new(env))
 }
 
-fn has_approved(env: &Env, operation_id: u128, signer: &Address) -> bool {
-    let approvals = read_approvals(env, operation_id);
-    for i in 0..approvals.len() {
-        if &approvals.get(i).unwrap() == signer {
-            return true;
-        }
+/// Returns the ids of every pending operation, in proposal order.
+fn read_pending_ops(env: &Env) -> Vec<u128> {
+    let mut ids = Vec::new(env);
+    for page in read_pending_pages(env).iter() {
+        ids.append(&read_pending_page(env, page));
     }
-    false
+    ids
 }
 
-fn approval_count(env: &Env, operation_id: u128) -> u32 {
-    let approvals = read_approvals(env, operation_id);
-    approvals.len()
+fn operation_hash(env: &Env, kind: &OperationKind) -> BytesN<32> {
+    env.crypto().sha256(&kind.clone().to_xdr(env)).into()
 }
 
-fn is_emergency_guardian(env: &Env, addr: &Address) -> bool {
-    match env
-        .storage()
+fn add_pending_op(env: &Env, op: &Operation) {
+    // Ids only grow, so appending keeps every page and the page list sorted.
+    let page = pending_page_of(op.id);
+    let mut ids = read_pending_page(env, page);
+    ids.push_back(op.id);
+    env.storage()
         .persistent()
-        .get::<_, Address>(&StorageKey::EmergencyGuardian)
-    {
-        Some(g) => &g == addr,
-        None => false,
-    }
+        .set(&StorageKey::PendingOps(page), &ids);
+    let mut pages = read_pending_pages(env);
+    if pages.last() != Some(page) {
+        pages.push_back(page);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::PendingPages, &pages);
+    }
+    env.storage().persistent().set(
+        &StorageKey::PendingByHash(operation_hash(env, &op.kind)),
+        &op.id,
+    );
+}
+
+fn remove_pending_op(env: &Env, op: &Operation) {
+    let page = pending_page_of(op.id);
+    let mut ids = read_pending_page(env, page);
+    if let Some(index) = ids.first_index_of(op.id) {
+        ids.remove(index);
+        if ids.is_empty() {
+            env.storage()
+                .persistent()
+                .remove(&StorageKey::PendingOps(page));
+            let mut pages = read_pending_pages(env);
+            if let Some(index) = pages.first_index_of(page) {
+                pages.remove(index);
+                env.storage()
+                    .persistent()
+                    .set(&StorageKey::PendingPages, &pages);
+            }
+        } else {
+            env.storage()
+                .persistent()
+                .set(&StorageKey::PendingOps(page), &ids);
+        }
+    }
+    env.storage()
+        .persistent()
+        .remove(&StorageKey::PendingByHash(operation_hash(env, &op.kind)));
+}
+
+fn read_status_count(env: &Env, status: OperationStatus) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<_, u32>(&StorageKey::StatusCount(status))
+        .unwrap_or(0)
+}
+
+fn read_status_page(env: &Env, status: OperationStatus, page: u32) -> Vec<u128> {
+    env.storage()
+        .persistent()
+        .get::<_, Vec<u128>>(&StorageKey::StatusOps(status, page))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Moves an operation out of the pending index into its final status index.
+fn finalize_op(env: &Env, op: &Operation) {
+    remove_pending_op(env, op);
+    if op.status == OperationStatus::Executed {
+        refund_bond(env, op);
+    }
+    let count = read_status_count(env, op.status.clone());
+    let page = count / INDEX_PAGE_LEN;
+    let mut ids = read_status_page(env, op.status.clone(), page);
+    ids.push_back(op.id);
+    env.storage()
+        .persistent()
+        .set(&StorageKey::StatusOps(op.status.clone(), page), &ids);
+    env.storage()
+        .persistent()
+        .set(&StorageKey::StatusCount(op.status.clone()), &(count + 1));
+}
+
+/// Returns up to `limit` operations that reached the final `status`,
+/// starting at position `start` in the order they did.
+fn page_status_operations(
+    env: &Env,
+    status: OperationStatus,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<Operation>, MultisigError> {
+    let end = start
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(read_status_count(env, status.clone()));
+
+    let mut page = Vec::new(env);
+    let mut i = start;
+    while i < end {
+        let ids = read_status_page(env, status.clone(), i / INDEX_PAGE_LEN);
+        let offset = i % INDEX_PAGE_LEN;
+        let last = ids.len().min(offset + (end - i));
+        if last <= offset {
+            break;
+        }
+        for j in offset..last {
+            page.push_back(read_operation(env, ids.get(j).unwrap())?);
+        }
+        i += last - offset;
+    }
+    Ok(page)
+}
+
+/// Returns up to `limit` pending operations, starting at position `start` in
+/// proposal order. Only the pages overlapping the requested range are read.
+fn page_pending_operations(
+    env: &Env,
+    start: u32,
+    limit: u32,
+) -> Result<Vec<Operation>, MultisigError> {
+    let mut remaining = limit.min(MAX_PAGE_SIZE);
+    let mut skip = start;
+    let mut page = Vec::new(env);
+    for number in read_pending_pages(env).iter() {
+        if remaining == 0 {
+            break;
+        }
+        let ids = read_pending_page(env, number);
+        if skip >= ids.len() {
+            skip -= ids.len();
+            continue;
+        }
+        let last = ids.len().min(skip + remaining);
+        for i in skip..last {
+            page.push_back(read_operation(env, ids.get(i).unwrap())?);
+        }
+        remaining -= last - skip;
+        skip = 0;
+    }
+    Ok(page)
+}
+
+fn read_held_bonds(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<_, i128>(&StorageKey::HeldBonds(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Adds `delta` to the bonds held in `token`.
+fn adjust_held_bonds(env: &Env, token: &Address, delta: i128) {
+    let key = StorageKey::HeldBonds(token.clone());
+    let held = read_held_bonds(env, token) + delta;
+    if held == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &held);
+    }
+}
+
+/// Fails unless the multisig can pay `amount` of `token` without touching
+/// the proposal bonds it holds.
+fn check_spendable(env: &Env, token: &Address, amount: i128) -> Result<(), MultisigError> {
+    let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+    if balance - read_held_bonds(env, token) < amount {
+        return Err(MultisigError::InsufficientBalance);
+    }
+    Ok(())
+}
+
+/// Returns an operation's bond to its proposer, if one is held.
+fn refund_bond(env: &Env, op: &Operation) {
+    let key = StorageKey::OperationBond(op.id);
+    if let Some(bond) = env.storage().persistent().get::<_, ProposalBond>(&key) {
+        env.storage().persistent().remove(&key);
+        adjust_held_bonds(env, &bond.token, -bond.amount);
+        token::Client::new(env, &bond.token).transfer(
+            &env.current_contract_address(),
+            &op.creator,
+            &bond.amount,
+        );
+        env.events().publish(
+            ("bond_refunded", op.id),
+            BondRefundedEvent {
+                operation_id: op.id,
+                proposer: op.creator.clone(),
+                token: bond.token,
+                amount: bond.amount,
+            },
+        );
+    }
+}
+
+/// Releases an operation's bond to the multisig, returning the amount kept.
+fn forfeit_bond(env: &Env, operation_id: u128) -> i128 {
+    let key = StorageKey::OperationBond(operation_id);
+    let Some(bond) = env.storage().persistent().get::<_, ProposalBond>(&key) else {
+        return 0;
+    };
+    env.storage().persistent().remove(&key);
+    adjust_held_bonds(env, &bond.token, -bond.amount);
+    bond.amount
+}
+
+fn check_not_expired(env: &Env, op: &Operation) -> Result<(), MultisigError> {
+    if op
+        .expires_at
+        .is_some_and(|at| env.ledger().timestamp() >= at)
+    {
+        return Err(MultisigError::OperationExpired);
+    }
+    Ok(())
+}
+
+fn read_proposal_lifetime(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<_, u64>(&StorageKey::ProposalLifetime)
+        .unwrap_or(0)
+}
+
+fn read_approvals(env: &Env, operation_id: u128) -> Vec<Address> {
+    let key = StorageKey::Approvals(operation_id);
+    match env.storage().persistent().get::<_, Vec<Address>>(&key) {
+        Some(approvals) => {
+            extend_ttl(env, &key);
+            approvals
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Records `signer`'s approval and returns the updated approval list.
+fn add_approval(env: &Env, operation_id: u128, signer: &Address) -> Vec<Address> {
+    let mut approvals = read_approvals(env, operation_id);
+    approvals.push_back(signer.clone());
+    let key = StorageKey::Approvals(operation_id);
+    env.storage().persistent().set(&key, &approvals);
+    extend_ttl(env, &key);
+    let key = StorageKey::Approval(operation_id, signer.clone());
+    env.storage().persistent().set(&key, &true);
+    extend_ttl(env, &key);
+    approvals
+}
+
+fn remove_approval(env: &Env, operation_id: u128, signer: &Address) {
+    let mut approvals = read_approvals(env, operation_id);
+    if let Some(index) = approvals.first_index_of(signer) {
+        approvals.remove(index);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::Approvals(operation_id), &approvals);
+    }
+    env.storage()
+        .persistent()
+        .remove(&StorageKey::Approval(operation_id, signer.clone()));
+}
+
+fn has_approved(env: &Env, operation_id: u128, signer: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&StorageKey::Approval(operation_id, signer.clone()))
+}
+
+fn approval_weight(env: &Env, operation_id: u128) -> Result<u32, MultisigError> {
+    active_weight(env, &read_approvals(env, operation_id))
+}
+
+/// Removes `signer` from the approvals of every pending operation other than
+/// `except`, so approval counts only ever reflect current signers. Operations
+/// queued under a delay that fall below the threshold are unqueued and must
+/// be re-approved, which starts a fresh delay.
+fn purge_pending_approvals(env: &Env, signer: &Address, except: u128) -> Result<(), MultisigError> {
+    let threshold = read_threshold(env)?;
+    for id in read_pending_ops(env).iter() {
+        if id == except || !has_approved(env, id, signer) {
+            continue;
+        }
+        remove_approval(env, id, signer);
+
+        let approvals = read_approvals(env, id);
+        let approved_weight = active_weight(env, &approvals)?;
+        if approved_weight < threshold {
+            let mut op = read_operation(env, id)?;
+            if op.executable_at.take().is_some() {
+                write_operation(env, &op);
+            }
+        }
+        env.events().publish(
+            ("approvals_recomputed", id),
+            ApprovalsRecomputedEvent {
+                operation_id: id,
+                removed_signer: signer.clone(),
+                approvals: approvals.len(),
+                approved_weight,
+                threshold_met: approved_weight >= threshold,
+            },
+        );
+    }
+    Ok(())
+}
+
+fn read_owner(env: &Env) -> Result<Address, MultisigError> {
+    env.storage()
+        .persistent()
+        .get::<_, Address>(&StorageKey::Owner)
+        .ok_or(MultisigError::NotInitialized)
+}
+
+fn operation_type(kind: &OperationKind) -> OperationType {
+    match kind {
+        OperationKind::ContractUpgrade(..) => OperationType::ContractUpgrade,
+        OperationKind::LargePayment(..) => OperationType::LargePayment,
+        OperationKind::DisputeResolution(..) => OperationType::DisputeResolution,
+        OperationKind::AddSigner(..) => OperationType::AddSigner,
+        OperationKind::RemoveSigner(..) => OperationType::RemoveSigner,
+        OperationKind::ChangeThreshold(..) => OperationType::ChangeThreshold,
+        OperationKind::BatchPayment(..) => OperationType::BatchPayment,
+        OperationKind::TokenApproval(..) => OperationType::TokenApproval,
+        OperationKind::SetGuardian(..) => OperationType::SetGuardian,
+        OperationKind::Invoke(..) => OperationType::Invoke,
+        OperationKind::SchedulerControl(..) => OperationType::SchedulerControl,
+        OperationKind::RotateSigners(..) => OperationType::RotateSigners,
+    }
+}
+
+/// Enforces the guardian policy, if one is configured, on `op`.
+fn check_guardian_policy(env: &Env, op: &Operation) -> Result<(), MultisigError> {
+    let policy = match env
+        .storage()
+        .persistent()
+        .get::<_, GuardianPolicy>(&StorageKey::GuardianPolicy)
+    {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+
+    if !policy.allowed_types.contains(operation_type(&op.kind)) {
+        return Err(MultisigError::GuardianTypeNotAllowed);
+    }
+
+    if let Some(max) = policy.max_payment {
+        let amount = match &op.kind {
+            OperationKind::LargePayment(_, _, amount) => *amount,
+            OperationKind::BatchPayment(_, payments) => validate_batch(payments)?,
+            _ => 0,
+        };
+        if amount > max {
+            return Err(MultisigError::GuardianLimitExceeded);
+        }
+    }
+
+    if policy.require_cosigner {
+        let cosigned = read_approvals(env, op.id)
+            .iter()
+            .any(|signer| signer != op.creator && !signer_expired(env, &signer));
+        if !cosigned {
+            return Err(MultisigError::CosignerRequired);
+        }
+    }
+    Ok(())
+}
+
+fn read_guardian(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<_, Address>(&StorageKey::EmergencyGuardian)
+}
+
+fn is_emergency_guardian(env: &Env, addr: &Address) -> bool {
+    match read_guardian(env) {
+        Some(g) => &g == addr,
+        None => false,
+    }
+}
+
+fn write_guardian(env: &Env, guardian: Option<Address>) {
+    let old_guardian = read_guardian(env);
+    match &guardian {
+        Some(g) => env
+            .storage()
+            .persistent()
+            .set(&StorageKey::EmergencyGuardian, g),
+        None => env
+            .storage()
+            .persistent()
+            .remove(&StorageKey::EmergencyGuardian),
+    }
+
+    env.events().publish(
+        ("guardian_changed",),
+        GuardianChangedEvent {
+            old_guardian,
+            new_guardian: guardian,
+        },
+    );
+}
+
+fn read_owner_approval_types(env: &Env) -> Vec<OperationType> {
+    env.storage()
+        .persistent()
+        .get::<_, Vec<OperationType>>(&StorageKey::OwnerApprovalTypes)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Whether the operation still waits on an owner approval its type requires.
+fn owner_approval_missing(env: &Env, op: &Operation) -> bool {
+    read_owner_approval_types(env).contains(operation_type(&op.kind))
+        && !env
+            .storage()
+            .persistent()
+            .has(&StorageKey::OwnerApproval(op.id))
+}
+
+fn read_treasury_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get::<_, Vec<Address>>(&StorageKey::TreasuryTokens)
+        .unwrap_or(Vec::new(env))
+}
+
+fn read_daily_limit(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<_, i128>(&StorageKey::DailyLimit(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Returns today's direct-payment spend; a bucket from an earlier day reads as 0.
+fn read_daily_spent(env: &Env, token: &Address) -> i128 {
+    let today = env.ledger().timestamp() / DAY_SECONDS;
+    match env
+        .storage()
+        .persistent()
+        .get::<_, (u64, i128)>(&StorageKey::DailySpent(token.clone()))
+    {
+        Some((day, spent)) if day == today => spent,
+        _ => 0,
+    }
+}
+
+fn write_daily_spent(env: &Env, token: &Address, spent: i128) {
+    let today = env.ledger().timestamp() / DAY_SECONDS;
+    env.storage()
+        .persistent()
+        .set(&StorageKey::DailySpent(token.clone()), &(today, spent));
+}
+
+fn read_execution_delay(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<_, u64>(&StorageKey::ExecutionDelay)
+        .unwrap_or(0)
+}
+
+fn read_challenge_period(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<_, u64>(&StorageKey::ChallengePeriod)
+        .unwrap_or(0)
+}
+
+fn require_guardian(env: &Env, guardian: &Address) -> Result<(), MultisigError> {
+    guardian.require_auth();
+    if !is_emergency_guardian(env, guardian) {
+        return Err(MultisigError::NotGuardian);
+    }
+    Ok(())
+}
+
+fn read_auto_execute(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get::<_, bool>(&StorageKey::AutoExecute)
+        .unwrap_or(true)
 }
 
-fn execute_if_threshold_met(env: &Env, operation_id: u128) {
-    let threshold = read_threshold(env);
-    let approvals = approval_count(env, operation_id);
-    if approvals >= threshold {
+fn read_allow_self_invoke(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get::<_, bool>(&StorageKey::AllowSelfInvoke)
+        .unwrap_or(false)
+}
+
+/// Rejects an `Invoke` aimed at the multisig unless the owner allowed it, so
+/// approvals for a generic call cannot reach the admin entry points.
+fn check_invoke_target(env: &Env, target: &Address) -> Result<(), MultisigError> {
+    if *target == env.current_contract_address() && !read_allow_self_invoke(env) {
+        return Err(MultisigError::SelfInvokeNotAllowed);
+    }
+    Ok(())
+}
+
+/// Scheduler entry point for a `SchedulerControl` action.
+fn scheduler_function(action: u32) -> Result<&'static str, MultisigError> {
+    match action {
+        SCHEDULER_PAUSE => Ok("pause_job"),
+        SCHEDULER_RESUME => Ok("resume_job"),
+        SCHEDULER_CANCEL => Ok("cancel_job"),
+        _ => Err(MultisigError::InvalidSchedulerAction),
+    }
+}
+
+fn execute_if_threshold_met(env: &Env, operation_id: u128) -> Result<(), MultisigError> {
+    let threshold = read_threshold(env)?;
+    if approval_weight(env, operation_id)? < threshold {
+        return Ok(());
+    }
+
+    let mut op = read_operation(env, operation_id)?;
+    if owner_approval_missing(env, &op) {
+        // Executed or queued once `owner_approve` is called.
+        return Ok(());
+    }
+    let now = env.ledger().timestamp();
+    let delay = read_execution_delay(env);
+    let scheduled = op.execute_after.filter(|at| *at > now);
+    if delay == 0 && scheduled.is_none() {
+        if !read_auto_execute(env) {
+            // Left for an explicit `execute_operation` call.
+            return Ok(());
+        }
         // Execute without additional signer auth (they already authenticated
-        // when approving). Execution itself is a pure state transition.
-        perform_execute(env, operation_id);
+        // when approving). A failed downstream call is recorded rather than
+        // reverting the approval, so the threshold stays met for a retry.
+        return perform_execute(env, operation_id, true);
     }
+
+    // Queue the operation; it becomes executable once the delay has elapsed
+    // and the scheduled time, if any, has been reached.
+    if op.executable_at.is_none() {
+        let executable_at = now
+            .checked_add(delay)
+            .ok_or(MultisigError::Overflow)?
+            .max(scheduled.unwrap_or(0));
+        op.executable_at = Some(executable_at);
+        write_operation(env, &op);
+
+        env.events().publish(
+            ("operation_queued", operation_id),
+            OperationQueuedEvent {
+                operation_id,
+                executable_at,
+            },
+        );
+    }
+    Ok(())
 }
 
-fn perform_execute(env: &Env, operation_id: u128) {
-    let mut op = read_operation(env, operation_id);
+/// Invokes `func` on `contract`. On failure returns the callee's error code,
+/// if it reported one.
+///
+/// With `record_failure` unset a failed call reverts with the callee's error,
+/// so the error reaches the guardian unchanged.
+fn call_external(
+    env: &Env,
+    contract: &Address,
+    func: &Symbol,
+    args: Vec<Val>,
+    record_failure: bool,
+) -> Result<(), Option<u32>> {
+    match env.try_invoke_contract::<Val, soroban_sdk::Error>(contract, func, args) {
+        Ok(_) => Ok(()),
+        Err(Ok(err)) if record_failure => Err(Some(err.get_code())),
+        Err(Err(_)) if record_failure => Err(None),
+        Err(Ok(err)) => panic_with_error!(env, err),
+        Err(Err(_)) => panic!("External call aborted"),
+    }
+}
+
+/// Whether executing `kind` calls another contract.
+fn is_external_call(env: &Env, kind: &OperationKind) -> bool {
+    match kind {
+        OperationKind::ContractUpgrade(target, _) => *target != env.current_contract_address(),
+        OperationKind::DisputeResolution(..)
+        | OperationKind::Invoke(..)
+        | OperationKind::SchedulerControl(..) => true,
+        _ => false,
+    }
+}
+
+fn write_execution_record(env: &Env, operation_id: u128, error_code: Result<(), Option<u32>>) {
+    let record = ExecutionRecord {
+        operation_id,
+        executed_at: env.ledger().timestamp(),
+        success: error_code.is_ok(),
+        error_code: error_code.err().flatten(),
+    };
+    let key = StorageKey::ExecutionRecord(operation_id);
+    env.storage().persistent().set(&key, &record);
+    extend_ttl(env, &key);
+}
+
+/// Executes a pending operation. With `record_failure` set, a failed
+/// cross-contract call is recorded as `execution_result = Some(false)` and the
+/// operation stays Pending instead of the whole call reverting.
+fn perform_execute(
+    env: &Env,
+    operation_id: u128,
+    record_failure: bool,
+) -> Result<(), MultisigError> {
+    let mut op = read_operation(env, operation_id)?;
     if op.status != OperationStatus::Pending {
-        return;
+        return Ok(());
     }
 
     match &op.kind {
         OperationKind::LargePayment(token, to, amount) => {
-            assert!(*amount > 0, "Amount must be positive");
+            if *amount <= 0 {
+                return Err(MultisigError::InvalidAmount);
+            }
+            check_spendable(env, token, *amount)?;
             let client = token::Client::new(env, token);
             // Transfer from multisig contract balance.
             client.transfer(&env.current_contract_address(), to, amount);
         }
-        // For ContractUpgrade and DisputeResolution we intentionally only
-        // record the approval and execution. Off-chain or higher-level
-        // orchestrators consume these events and perform the concrete action.
-        OperationKind::ContractUpgrade(_, _) => {}
-        OperationKind::DisputeResolution(_, _, _, _) => {}
+        OperationKind::BatchPayment(token, payments) => {
+            // Validate everything before the first transfer so the batch is
+            // all-or-nothing.
+            let total = validate_batch(payments)?;
+            check_spendable(env, token, total)?;
+            let client = token::Client::new(env, token);
+            let contract = env.current_contract_address();
+
+            for (to, amount) in payments.iter() {
+                client.transfer(&contract, &to, &amount);
+                env.events().publish(
+                    ("batch_payment_item", operation_id),
+                    BatchPaymentItemEvent {
+                        operation_id,
+                        to,
+                        amount,
+                    },
+                );
+            }
+
+            env.events().publish(
+                ("batch_payment_executed", operation_id),
+                BatchPaymentExecutedEvent {
+                    operation_id,
+                    token: token.clone(),
+                    recipients: payments.len(),
+                    total,
+                },
+            );
+        }
+        OperationKind::TokenApproval(token, spender, amount, live_until_ledger) => {
+            check_approval(env, spender, *amount)?;
+            let client = token::Client::new(env, token);
+            client.approve(
+                &env.current_contract_address(),
+                spender,
+                amount,
+                live_until_ledger,
+            );
+        }
+        OperationKind::ContractUpgrade(target, new_wasm_hash) => {
+            // A failing upgrade never leaves the operation marked Executed.
+            if *target == env.current_contract_address() {
+                env.deployer()
+                    .update_current_contract_wasm(new_wasm_hash.clone());
+            } else if let Err(code) = call_external(
+                env,
+                target,
+                &Symbol::new(env, "upgrade"),
+                vec![env, new_wasm_hash.into_val(env)],
+                record_failure,
+            ) {
+                return record_execution_failure(env, op, code);
+            }
+        }
+        OperationKind::DisputeResolution(
+            payroll_contract,
+            agreement_id,
+            pay_employee,
+            refund_employer,
+        ) => {
+            // Errors from the payroll contract (e.g. NoDispute) leave the
+            // operation Pending.
+            if let Err(code) = call_external(
+                env,
+                payroll_contract,
+                &Symbol::new(env, "resolve_dispute"),
+                vec![
+                    env,
+                    env.current_contract_address().into_val(env),
+                    agreement_id.into_val(env),
+                    pay_employee.into_val(env),
+                    refund_employer.into_val(env),
+                ],
+                record_failure,
+            ) {
+                return record_execution_failure(env, op, code);
+            }
+        }
+        OperationKind::AddSigner(signer) => {
+            let mut signers = read_signers(env)?;
+            if signers.contains(signer) {
+                return Err(MultisigError::DuplicateSigner);
+            }
+            signers.push_back(signer.clone());
+            env.storage()
+                .persistent()
+                .set(&StorageKey::Signers, &signers);
+
+            env.events().publish(
+                ("signer_added", operation_id),
+                SignerAddedEvent {
+                    operation_id,
+                    signer: signer.clone(),
+                },
+            );
+        }
+        OperationKind::ChangeThreshold(new_threshold) => {
+            // Re-validate: the signer set may have changed since proposal.
+            check_threshold(env, *new_threshold)?;
+            let old_threshold = read_threshold(env)?;
+            env.storage()
+                .persistent()
+                .set(&StorageKey::Threshold, new_threshold);
+
+            env.events().publish(
+                ("threshold_changed", operation_id),
+                ThresholdChangedEvent {
+                    operation_id,
+                    old_threshold,
+                    new_threshold: *new_threshold,
+                },
+            );
+        }
+        OperationKind::SetGuardian(guardian) => write_guardian(env, guardian.clone()),
+        OperationKind::Invoke(target, function, args) => {
+            // Re-checked here in case the owner revoked consent after proposal.
+            check_invoke_target(env, target)?;
+            if let Err(code) = call_external(env, target, function, args.clone(), record_failure) {
+                return record_execution_failure(env, op, code);
+            }
+        }
+        OperationKind::SchedulerControl(scheduler, job_id, action) => {
+            // Scheduler errors (e.g. JobNotActive) leave the operation Pending.
+            if let Err(code) = call_external(
+                env,
+                scheduler,
+                &Symbol::new(env, scheduler_function(*action)?),
+                vec![
+                    env,
+                    env.current_contract_address().into_val(env),
+                    job_id.into_val(env),
+                ],
+                record_failure,
+            ) {
+                return record_execution_failure(env, op, code);
+            }
+        }
+        OperationKind::RotateSigners(new_signers, new_threshold) => {
+            check_signer_set(new_signers, *new_threshold)?;
+            let old_signers = read_signers(env)?;
+            let old_threshold = read_threshold(env)?;
+
+            // Every signer of the new set weighs 1; signers that stay keep
+            // their expiry.
+            for signer in old_signers.iter() {
+                env.storage()
+                    .persistent()
+                    .remove(&StorageKey::SignerWeight(signer.clone()));
+                if !new_signers.contains(&signer) {
+                    env.storage()
+                        .persistent()
+                        .remove(&StorageKey::SignerExpiry(signer));
+                }
+            }
+            env.storage()
+                .persistent()
+                .set(&StorageKey::Signers, new_signers);
+            env.storage()
+                .persistent()
+                .set(&StorageKey::Threshold, new_threshold);
+
+            // Purged after the threshold update, so `approvals_recomputed`
+            // reports against the new threshold.
+            for signer in old_signers.iter() {
+                if !new_signers.contains(&signer) {
+                    purge_pending_approvals(env, &signer, operation_id)?;
+                }
+            }
+
+            env.events().publish(
+                ("signers_rotated", operation_id),
+                SignersRotatedEvent {
+                    operation_id,
+                    old_count: old_signers.len(),
+                    new_count: new_signers.len(),
+                    old_threshold,
+                    new_threshold: *new_threshold,
+                },
+            );
+        }
+        OperationKind::RemoveSigner(signer) => {
+            let mut signers = read_signers(env)?;
+            let index = signers
+                .first_index_of(signer)
+                .ok_or(MultisigError::NotSigner)?;
+            signers.remove(index);
+            if active_weight(env, &signers)? < read_threshold(env)? {
+                return Err(MultisigError::SignerCountBelowThreshold);
+            }
+            env.storage()
+                .persistent()
+                .set(&StorageKey::Signers, &signers);
+            env.storage()
+                .persistent()
+                .remove(&StorageKey::SignerWeight(signer.clone()));
+            env.storage()
+                .persistent()
+                .remove(&StorageKey::SignerExpiry(signer.clone()));
+            purge_pending_approvals(env, signer, operation_id)?;
+
+            env.events().publish(
+                ("signer_removed", operation_id),
+                SignerRemovedEvent {
+                    operation_id,
+                    signer: signer.clone(),
+                },
+            );
+        }
+    }
+
+    if is_external_call(env, &op.kind) {
+        write_execution_record(env, operation_id, Ok(()));
     }
 
     op.status = OperationStatus::Executed;
     op.executed_at = Some(env.ledger().timestamp());
+    op.execution_result = Some(true);
     write_operation(env, &op);
+    finalize_op(env, &op);
 
     env.events().publish(
         ("operation_executed", operation_id),
         OperationExecutedEvent { operation_id },
     );
+    Ok(())
+}
+
+/// Guardian execution path: flags and announces the execution, then runs it
+/// like a threshold execution.
+fn emergency_execute(
+    env: &Env,
+    guardian: Address,
+    operation_id: u128,
+) -> Result<(), MultisigError> {
+    let key = StorageKey::ExecutedByGuardian(operation_id);
+    env.storage().persistent().set(&key, &true);
+    extend_ttl(env, &key);
+
+    env.events().publish(
+        ("emergency_executed", operation_id),
+        EmergencyExecutedEvent {
+            operation_id,
+            guardian,
+            approvals_at_execution: approval_weight(env, operation_id)?,
+            threshold: read_threshold(env)?,
+        },
+    );
+    perform_execute(env, operation_id, false)
+}
+
+fn record_execution_failure(
+    env: &Env,
+    mut op: Operation,
+    error_code: Option<u32>,
+) -> Result<(), MultisigError> {
+    op.execution_result = Some(false);
+    write_operation(env, &op);
+    write_execution_record(env, op.id, Err(error_code));
+
+    env.events().publish(
+        ("operation_execution_failed", op.id),
+        OperationExecutionFailedEvent {
+            operation_id: op.id,
+            error_code,
+        },
+    );
+    Ok(())
 }
 
-#[contractimpl]
-impl MultisigContract {
-    /// @notice Initializes the multisig wallet with signers and a threshold.
-    /// @dev Can only be called once by the designated owner.
-    /// @param owner Address that controls configuration updates.
-    /// @param signers Initial signer set allowed to approve operations.
-    /// @param threshold Number of signatures required to execute.
-    /// @param emergency_guardian Optional address that can unilaterally execute
-    ///        any pending operation for break-glass scenarios.
-    pub fn initialize(
+#[contractimpl]
+impl MultisigContract {
+    /// @notice Initializes the multisig wallet with signers and a threshold.
+    /// @dev Can only be called once by the designated owner.
+    /// @param owner Address that controls configuration updates.
+    /// @param signers Initial signer set allowed to approve operations.
+    /// @param threshold Number of signatures required to execute.
+    /// @param emergency_guardian Optional address that can unilaterally execute
+    ///        any pending operation for break-glass scenarios.
+    /// @param weights Optional voting weight per signer (parallel to `signers`);
+    ///        `None` gives every signer weight 1. `threshold` is a weight sum.
+    pub fn initialize(
+        env: Env,
+        owner: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+        emergency_guardian: Option<Address>,
+        weights: Option<Vec<u32>>,
+    ) -> Result<(), MultisigError> {
+        owner.require_auth();
+
+        if require_initialized(&env).is_ok() {
+            return Err(MultisigError::AlreadyInitialized);
+        }
+
+        let signer_count = signers.len();
+        if signer_count == 0 {
+            return Err(MultisigError::NoSigners);
+        }
+
+        let total_weight = match &weights {
+            Some(weights) => {
+                if weights.len() != signer_count || weights.contains(0) {
+                    return Err(MultisigError::InvalidWeight);
+                }
+                let mut total: u32 = 0;
+                for weight in weights.iter() {
+                    total = total.checked_add(weight).ok_or(MultisigError::Overflow)?;
+                }
+                total
+            }
+            None => signer_count,
+        };
+        if threshold == 0 || threshold > total_weight {
+            return Err(MultisigError::InvalidThreshold);
+        }
+
+        check_unique_signers(&signers)?;
+
+        env.storage().persistent().set(&StorageKey::Owner, &owner);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::Signers, &signers);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::Threshold, &threshold);
+
+        if let Some(weights) = weights {
+            for (signer, weight) in signers.iter().zip(weights.iter()) {
+                env.storage()
+                    .persistent()
+                    .set(&StorageKey::SignerWeight(signer), &weight);
+            }
+        }
+
+        if let Some(g) = emergency_guardian {
+            env.storage()
+                .persistent()
+                .set(&StorageKey::EmergencyGuardian, &g);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::Initialized, &true);
+        extend_config_ttl(&env)?;
+
+        env.events().publish(
+            ("multisig_initialized",),
+            MultisigInitializedEvent {
+                owner,
+                signer_count,
+                threshold,
+            },
+        );
+        Ok(())
+    }
+
+    /// @notice Proposes a new multisig-protected operation.
+    /// @dev The proposer must be one of the configured signers.
+    /// @param proposer Signer creating the operation.
+    /// @param kind Encoded operation details.
+    /// @param memo Optional context of at most 128 bytes.
+    /// @param execute_after Optional earliest execution timestamp.
+    /// @return operation_id Newly created operation identifier.
+    pub fn propose_operation(
+        env: Env,
+        proposer: Address,
+        kind: OperationKind,
+        memo: Option<Bytes>,
+        execute_after: Option<u64>,
+    ) -> Result<u128, MultisigError> {
+        require_initialized(&env)?;
+        proposer.require_auth();
+        require_signer(&env, &proposer)?;
+        if let Some(m) = &memo {
+            if m.len() > MAX_MEMO_LEN {
+                return Err(MultisigError::MemoTooLong);
+            }
+        }
+        // Fail fast on parameters that can be checked before approval.
+        match &kind {
+            OperationKind::ChangeThreshold(threshold) => check_threshold(&env, *threshold)?,
+            OperationKind::BatchPayment(_, payments) => {
+                validate_batch(payments)?;
+            }
+            OperationKind::TokenApproval(_, spender, amount, _) => {
+                check_approval(&env, spender, *amount)?
+            }
+            OperationKind::Invoke(target, _, _) => check_invoke_target(&env, target)?,
+            OperationKind::SchedulerControl(_, _, action) => {
+                scheduler_function(*action)?;
+            }
+            OperationKind::RotateSigners(signers, threshold) => {
+                check_signer_set(signers, *threshold)?
+            }
+            _ => {}
+        }
+
+        // Reject an identical proposal while the original is still pending,
+        // so the same payment cannot be approved and executed twice.
+        let hash = operation_hash(&env, &kind);
+        if env
+            .storage()
+            .persistent()
+            .has(&StorageKey::PendingByHash(hash))
+        {
+            return Err(MultisigError::DuplicateOperation);
+        }
+
+        let id = next_operation_id(&env)?;
+        let now = env.ledger().timestamp();
+        let lifetime = read_proposal_lifetime(&env);
+        let expires_at = if lifetime > 0 {
+            Some(now.checked_add(lifetime).ok_or(MultisigError::Overflow)?)
+        } else {
+            None
+        };
+        // A proposal scheduled past its own deadline could never execute.
+        if let (Some(after), Some(at)) = (execute_after, expires_at) {
+            if after >= at {
+                return Err(MultisigError::ExecuteAfterPastExpiry);
+            }
+        }
+        let op = Operation {
+            id,
+            kind,
+            creator: proposer.clone(),
+            status: OperationStatus::Pending,
+            created_at: now,
+            executed_at: None,
+            executable_at: None,
+            execution_result: None,
+            emergency_executable_at: None,
+            execute_after,
+            expires_at,
+        };
+        write_operation(&env, &op);
+        add_pending_op(&env, &op);
+
+        // Hold the proposal bond until the operation is settled.
+        if let Some(bond) = env
+            .storage()
+            .persistent()
+            .get::<_, ProposalBond>(&StorageKey::ProposalBond)
+        {
+            token::Client::new(&env, &bond.token).transfer(
+                &proposer,
+                env.current_contract_address(),
+                &bond.amount,
+            );
+            adjust_held_bonds(&env, &bond.token, bond.amount);
+            env.storage()
+                .persistent()
+                .set(&StorageKey::OperationBond(id), &bond);
+        }
+        if let Some(m) = &memo {
+            env.storage()
+                .persistent()
+                .set(&StorageKey::OperationMemo(id), m);
+        }
+
+        // Auto-approve by proposer.
+        add_approval(&env, id, &proposer);
+
+        env.events().publish(
+            ("operation_proposed", id),
+            OperationProposedEvent {
+                operation_id: id,
+                creator: proposer,
+                memo,
+            },
+        );
+
+        execute_if_threshold_met(&env, id)?;
+
+        Ok(id)
+    }
+
+    /// @notice Approves a pending operation as a signer.
+    /// @dev Once the approval count reaches the configured threshold, the
+    ///      operation is executed automatically; a failed cross-contract call
+    ///      is recorded and leaves it Pending. Repeat approvals are not
+    ///      counted twice but do re-check the current threshold, so an
+    ///      operation can be executed after the threshold has been lowered.
+    /// @param signer Signer approving the operation.
+    /// @param operation_id Operation identifier.
+    pub fn approve_operation(
+        env: Env,
+        signer: Address,
+        operation_id: u128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        signer.require_auth();
+        require_signer(&env, &signer)?;
+        let op = read_pending_operation(&env, operation_id)?;
+        check_not_expired(&env, &op)?;
+
+        if has_approved(&env, operation_id, &signer) {
+            return execute_if_threshold_met(&env, operation_id);
+        }
+
+        let approvals = add_approval(&env, operation_id, &signer);
+        let count = approvals.len();
+        let approved_weight = active_weight(&env, &approvals)?;
+        let threshold = read_threshold(&env)?;
+
+        env.events().publish(
+            ("operation_approved", operation_id),
+            OperationApprovedEvent {
+                operation_id,
+                signer,
+                approvals: count,
+                approved_weight,
+                threshold,
+            },
+        );
+
+        execute_if_threshold_met(&env, operation_id)
+    }
+
+    /// @notice Records the owner's approval of a pending operation.
+    /// @dev Only needed for operation types flagged with
+    ///      `set_require_owner_approval`; those do not execute until both the
+    ///      signer threshold and this approval are in place. The owner's
+    ///      approval carries no signer weight.
+    /// @param owner Contract owner.
+    /// @param operation_id Operation identifier.
+    pub fn owner_approve(
+        env: Env,
+        owner: Address,
+        operation_id: u128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+        let op = read_pending_operation(&env, operation_id)?;
+        check_not_expired(&env, &op)?;
+
+        let key = StorageKey::OwnerApproval(operation_id);
+        if !env.storage().persistent().has(&key) {
+            env.storage().persistent().set(&key, &true);
+            extend_ttl(&env, &key);
+
+            env.events().publish(
+                ("owner_approved", operation_id),
+                OwnerApprovedEvent {
+                    operation_id,
+                    owner,
+                },
+            );
+        }
+
+        execute_if_threshold_met(&env, operation_id)
+    }
+
+    /// @notice Returns whether the owner has approved an operation.
+    pub fn has_owner_approval(env: Env, operation_id: u128) -> bool {
+        env.storage()
+            .persistent()
+            .has(&StorageKey::OwnerApproval(operation_id))
+    }
+
+    /// @notice Cancels a pending operation.
+    /// @dev Only the creator or the owner can cancel. The bond is refunded
+    ///      when the owner cancels, including the owner's own proposal; any
+    ///      other creator withdrawing their proposal forfeits it.
+    /// @param caller Address requesting cancellation.
+    /// @param operation_id Operation identifier.
+    pub fn cancel_operation(
+        env: Env,
+        caller: Address,
+        operation_id: u128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        caller.require_auth();
+
+        let mut op = read_pending_operation(&env, operation_id)?;
+        let by_owner = caller == read_owner(&env)?;
+        if caller != op.creator && !by_owner {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        op.status = OperationStatus::Cancelled;
+        write_operation(&env, &op);
+        finalize_op(&env, &op);
+
+        let bond_forfeited = if by_owner {
+            refund_bond(&env, &op);
+            0
+        } else {
+            forfeit_bond(&env, operation_id)
+        };
+
+        env.events().publish(
+            ("operation_cancelled", operation_id),
+            OperationCancelledEvent {
+                operation_id,
+                bond_forfeited,
+            },
+        );
+        Ok(())
+    }
+
+    /// @notice Marks an operation that missed its deadline as expired.
+    /// @dev Callable by anyone once `expires_at` has passed, as long as the
+    ///      operation has not met its threshold. Any proposal bond is kept by
+    ///      the multisig.
+    /// @param operation_id Operation identifier.
+    pub fn expire_operation(env: Env, operation_id: u128) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+
+        let mut op = read_pending_operation(&env, operation_id)?;
+        let expired = op
+            .expires_at
+            .is_some_and(|at| env.ledger().timestamp() >= at);
+        if !expired
+            || op.executable_at.is_some()
+            || approval_weight(&env, operation_id)? >= read_threshold(&env)?
+        {
+            return Err(MultisigError::NotExpired);
+        }
+
+        op.status = OperationStatus::Expired;
+        write_operation(&env, &op);
+        finalize_op(&env, &op);
+        let bond_forfeited = forfeit_bond(&env, operation_id);
+
+        env.events().publish(
+            ("operation_expired", operation_id),
+            OperationExpiredEvent {
+                operation_id,
+                bond_forfeited,
+            },
+        );
+        Ok(())
+    }
+
+    /// @notice Pays a small amount from the multisig with a single signature.
+    /// @dev Spend is tracked per token per day bucket (UTC day of the ledger
+    ///      timestamp) and must stay within the owner-set daily limit; larger
+    ///      payments go through `propose_operation`. Held proposal bonds
+    ///      cannot be spent.
+    /// @param signer Signer making the payment.
+    /// @param token Token to pay in.
+    /// @param to Recipient.
+    /// @param amount Amount to transfer.
+    pub fn direct_payment(
+        env: Env,
+        signer: Address,
+        token: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        signer.require_auth();
+        require_signer(&env, &signer)?;
+        if amount <= 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+
+        let spent_today = read_daily_spent(&env, &token)
+            .checked_add(amount)
+            .ok_or(MultisigError::Overflow)?;
+        if spent_today > read_daily_limit(&env, &token) {
+            return Err(MultisigError::DailyLimitExceeded);
+        }
+        check_spendable(&env, &token, amount)?;
+        write_daily_spent(&env, &token, spent_today);
+
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+
+        env.events().publish(
+            ("direct_payment",),
+            DirectPaymentEvent {
+                signer,
+                token,
+                to,
+                amount,
+                spent_today,
+            },
+        );
+        Ok(())
+    }
+
+    /// @notice Limits how long a signer may sign, e.g. for a contractor.
+    /// @dev Only the owner can set expiries. After `valid_until` the signer
+    ///      can neither propose nor approve, and their approvals on pending
+    ///      operations stop counting. `None` removes the expiry.
+    /// @param owner Contract owner.
+    /// @param signer Signer whose rights are limited.
+    /// @param valid_until Last timestamp at which the signer may sign.
+    pub fn set_signer_expiry(
+        env: Env,
+        owner: Address,
+        signer: Address,
+        valid_until: Option<u64>,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+        if !read_signers(&env)?.contains(&signer) {
+            return Err(MultisigError::NotSigner);
+        }
+
+        let key = StorageKey::SignerExpiry(signer.clone());
+        match valid_until {
+            Some(valid_until) => env.storage().persistent().set(&key, &valid_until),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events().publish(
+            ("signer_expiry_changed",),
+            SignerExpiryChangedEvent {
+                signer,
+                valid_until,
+            },
+        );
+        Ok(())
+    }
+
+    /// @notice Returns a signer's expiry timestamp, if one is set.
+    pub fn get_signer_expiry(env: Env, signer: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::SignerExpiry(signer))
+    }
+
+    /// @notice Sets the daily direct-payment limit for a token.
+    /// @dev Only the owner can configure limits. Zero disables direct payments.
+    /// @param owner Contract owner.
+    /// @param token Token the limit applies to.
+    /// @param limit Maximum direct-payment total per day.
+    pub fn set_daily_limit(
+        env: Env,
+        owner: Address,
+        token: Address,
+        limit: i128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+        if limit < 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::DailyLimit(token.clone()), &limit);
+
+        env.events().publish(
+            ("daily_limit_changed",),
+            DailyLimitChangedEvent { token, limit },
+        );
+        Ok(())
+    }
+
+    /// @notice Returns the daily direct-payment limit for a token.
+    pub fn get_daily_limit(env: Env, token: Address) -> i128 {
+        read_daily_limit(&env, &token)
+    }
+
+    /// @notice Returns today's direct-payment spend for a token.
+    pub fn get_daily_spent(env: Env, token: Address) -> i128 {
+        read_daily_spent(&env, &token)
+    }
+
+    /// @notice Adds a token to the list reported by `get_treasury_balances`.
+    /// @dev Only the owner can register tokens; at most 20 are kept.
+    /// @param owner Contract owner.
+    /// @param token Token contract address.
+    pub fn register_treasury_token(
         env: Env,
         owner: Address,
-        signers: Vec<Address>,
-        threshold: u32,
-        emergency_guardian: Option<Address>,
-    ) {
+        token: Address,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
         owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
 
-        let initialized = env
-            .storage()
+        let mut tokens = read_treasury_tokens(&env);
+        if tokens.contains(&token) {
+            return Err(MultisigError::TokenAlreadyRegistered);
+        }
+        if tokens.len() >= MAX_TREASURY_TOKENS {
+            return Err(MultisigError::TreasuryTokenLimit);
+        }
+        tokens.push_back(token.clone());
+        env.storage()
             .persistent()
-            .get::<_, bool>(&StorageKey::Initialized)
-            .unwrap_or(false);
-        assert!(!initialized, "Contract already initialized");
+            .set(&StorageKey::TreasuryTokens, &tokens);
 
-        let signer_count = signers.len();
-        assert!(signer_count > 0, "At least one signer required");
-        assert!(
-            threshold > 0 && threshold <= signer_count,
-            "Invalid threshold"
+        env.events().publish(
+            ("treasury_token_changed",),
+            TreasuryTokenChangedEvent {
+                token,
+                registered: true,
+            },
         );
+        Ok(())
+    }
 
-        // Ensure signer list has no duplicates.
-        for i in 0..signer_count {
-            let a = signers.get(i).unwrap();
-            for j in (i + 1)..signer_count {
-                let b = signers.get(j).unwrap();
-                assert!(a != b, "Duplicate signer");
-            }
+    /// @notice Removes a token from the treasury list.
+    /// @param owner Contract owner.
+    /// @param token Token contract address.
+    pub fn deregister_treasury_token(
+        env: Env,
+        owner: Address,
+        token: Address,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
         }
 
-        env.storage().persistent().set(&StorageKey::Owner, &owner);
-        env.storage()
-            .persistent()
-            .set(&StorageKey::Signers, &signers);
+        let mut tokens = read_treasury_tokens(&env);
+        let index = tokens
+            .first_index_of(&token)
+            .ok_or(MultisigError::TokenNotRegistered)?;
+        tokens.remove(index);
         env.storage()
             .persistent()
-            .set(&StorageKey::Threshold, &threshold);
+            .set(&StorageKey::TreasuryTokens, &tokens);
 
-        if let Some(g) = emergency_guardian {
-            env.storage()
-                .persistent()
-                .set(&StorageKey::EmergencyGuardian, &g);
+        env.events().publish(
+            ("treasury_token_changed",),
+            TreasuryTokenChangedEvent {
+                token,
+                registered: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// @notice Returns the registered treasury tokens.
+    pub fn get_treasury_tokens(env: Env) -> Vec<Address> {
+        read_treasury_tokens(&env)
+    }
+
+    /// @notice Returns the multisig's balance of every registered token.
+    /// @dev Balances are listed in registration order.
+    pub fn get_treasury_balances(env: Env) -> Vec<(Address, i128)> {
+        let this = env.current_contract_address();
+        let mut balances = Vec::new(&env);
+        for token in read_treasury_tokens(&env).iter() {
+            let balance = token::Client::new(&env, &token).balance(&this);
+            balances.push_back((token, balance));
         }
+        balances
+    }
 
-        env.storage()
-            .persistent()
-            .set(&StorageKey::Initialized, &true);
+    /// @notice Executes an approved operation that was not auto-executed.
+    /// @dev Callable by anyone. Queued operations must have passed their
+    ///      `executable_at`. In every case the approvals of unexpired signers
+    ///      must still meet the threshold, so a queued operation whose
+    ///      approver has since lapsed cannot execute.
+    ///      A failed cross-contract call is recorded in `execution_result`
+    ///      and the operation stays Pending so it can be retried.
+    /// @param caller Address triggering execution.
+    /// @param operation_id Operation identifier.
+    pub fn execute_operation(
+        env: Env,
+        caller: Address,
+        operation_id: u128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        caller.require_auth();
+
+        let op = read_pending_operation(&env, operation_id)?;
+        if owner_approval_missing(&env, &op) {
+            return Err(MultisigError::OwnerApprovalRequired);
+        }
+        match op.executable_at {
+            Some(executable_at) => {
+                if env.ledger().timestamp() < executable_at {
+                    return Err(MultisigError::TimelockActive);
+                }
+            }
+            None => {
+                if read_execution_delay(&env) > 0 {
+                    return Err(MultisigError::NotQueued);
+                }
+                if op
+                    .execute_after
+                    .is_some_and(|at| env.ledger().timestamp() < at)
+                {
+                    return Err(MultisigError::TimelockActive);
+                }
+            }
+        }
+        if approval_weight(&env, operation_id)? < read_threshold(&env)? {
+            return Err(MultisigError::ThresholdNotMet);
+        }
+
+        perform_execute(&env, operation_id, true)
     }
 
-    /// @notice Proposes a new multisig-protected operation.
-    /// @dev The proposer must be one of the configured signers.
-    /// @param proposer Signer creating the operation.
-    /// @param kind Encoded operation details.
-    /// @return operation_id Newly created operation identifier.
-    pub fn propose_operation(env: Env, proposer: Address, kind: OperationKind) -> u128 {
-        require_initialized(&env);
-        proposer.require_auth();
-        assert!(is_signer(&env, &proposer), "Only signers can propose");
+    /// @notice Queues a pending operation for execution by the emergency guardian.
+    /// @dev Guardian can bypass threshold checks in break-glass scenarios,
+    ///      subject to the guardian policy if the owner has set one. With a
+    ///      challenge period configured the operation becomes executable via
+    ///      `emergency_finalize` once the period has passed, unless a signer
+    ///      challenges it first; with no period it executes immediately.
+    /// @param guardian Configured guardian address.
+    /// @param operation_id Operation identifier.
+    pub fn emergency_queue(
+        env: Env,
+        guardian: Address,
+        operation_id: u128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        require_guardian(&env, &guardian)?;
 
-        let id = next_operation_id(&env);
-        let op = Operation {
-            id,
-            kind,
-            creator: proposer.clone(),
-            status: OperationStatus::Pending,
-            created_at: env.ledger().timestamp(),
-            executed_at: None,
-        };
-        write_operation(&env, &op);
+        let mut op = read_pending_operation(&env, operation_id)?;
+        check_guardian_policy(&env, &op)?;
 
-        // Auto-approve by proposer.
-        let mut approvals = Vec::new(&env);
-        approvals.push_back(proposer.clone());
-        write_approvals(&env, id, &approvals);
+        let period = read_challenge_period(&env);
+        if period == 0 {
+            return emergency_execute(&env, guardian, operation_id);
+        }
+        if op.emergency_executable_at.is_some() {
+            return Err(MultisigError::EmergencyAlreadyQueued);
+        }
+
+        let executable_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(period)
+            .ok_or(MultisigError::Overflow)?;
+        op.emergency_executable_at = Some(executable_at);
+        write_operation(&env, &op);
 
         env.events().publish(
-            ("operation_proposed", id),
-            OperationProposedEvent {
-                operation_id: id,
-                creator: proposer,
+            ("emergency_queued", operation_id),
+            EmergencyQueuedEvent {
+                operation_id,
+                guardian,
+                executable_at,
             },
         );
-
-        execute_if_threshold_met(&env, id);
-
-        id
+        Ok(())
     }
 
-    /// @notice Approves a pending operation as a signer.
-    /// @dev Once the approval count reaches the configured threshold, the
-    ///      operation is executed automatically.
-    /// @param signer Signer approving the operation.
+    /// @notice Cancels a queued emergency execution.
+    /// @dev Any signer may challenge while the window is open. The operation
+    ///      itself stays pending and can still be approved normally.
+    /// @param signer Signer challenging the emergency execution.
     /// @param operation_id Operation identifier.
-    pub fn approve_operation(env: Env, signer: Address, operation_id: u128) {
-        require_initialized(&env);
+    pub fn challenge_emergency(
+        env: Env,
+        signer: Address,
+        operation_id: u128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
         signer.require_auth();
-        assert!(is_signer(&env, &signer), "Only signers can approve");
-
-        let op = read_operation(&env, operation_id);
-        assert!(
-            op.status == OperationStatus::Pending,
-            "Operation not pending"
-        );
+        require_signer(&env, &signer)?;
 
-        if has_approved(&env, operation_id, &signer) {
-            return;
+        let mut op = read_pending_operation(&env, operation_id)?;
+        let executable_at = op
+            .emergency_executable_at
+            .ok_or(MultisigError::NotEmergencyQueued)?;
+        if env.ledger().timestamp() >= executable_at {
+            return Err(MultisigError::ChallengeWindowClosed);
         }
 
-        let mut approvals = read_approvals(&env, operation_id);
-        approvals.push_back(signer.clone());
-        let count = approvals.len();
-        let threshold = read_threshold(&env);
-
-        write_approvals(&env, operation_id, &approvals);
+        op.emergency_executable_at = None;
+        write_operation(&env, &op);
 
         env.events().publish(
-            ("operation_approved", operation_id),
-            OperationApprovedEvent {
+            ("emergency_challenged", operation_id),
+            EmergencyChallengedEvent {
                 operation_id,
                 signer,
-                approvals: count,
-                threshold,
             },
         );
-
-        execute_if_threshold_met(&env, operation_id);
+        Ok(())
     }
 
-    /// @notice Cancels a pending operation.
-    /// @dev Only the creator or the owner can cancel.
-    /// @param caller Address requesting cancellation.
+    /// @notice Executes an emergency-queued operation once its window has passed.
+    /// @dev The guardian policy is re-checked, since the owner may have
+    ///      tightened it during the window.
+    /// @param guardian Configured guardian address.
     /// @param operation_id Operation identifier.
-    pub fn cancel_operation(env: Env, caller: Address, operation_id: u128) {
-        require_initialized(&env);
-        caller.require_auth();
+    pub fn emergency_finalize(
+        env: Env,
+        guardian: Address,
+        operation_id: u128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        require_guardian(&env, &guardian)?;
+
+        let op = read_pending_operation(&env, operation_id)?;
+        let executable_at = op
+            .emergency_executable_at
+            .ok_or(MultisigError::NotEmergencyQueued)?;
+        if env.ledger().timestamp() < executable_at {
+            return Err(MultisigError::TimelockActive);
+        }
+        check_guardian_policy(&env, &op)?;
+
+        emergency_execute(&env, guardian, operation_id)
+    }
+
+    /// @notice Lets the current emergency guardian give up the role.
+    /// @param guardian Configured guardian address.
+    pub fn renounce_guardian(env: Env, guardian: Address) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        require_guardian(&env, &guardian)?;
+
+        write_guardian(&env, None);
+        Ok(())
+    }
+
+    /// @notice Returns the current emergency guardian, if any.
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        read_guardian(&env)
+    }
+
+    /// @notice Sets or clears the policy limiting the emergency guardian.
+    /// @dev Only the owner can configure the policy. `None` removes all limits.
+    /// @param owner Contract owner.
+    /// @param policy New guardian policy.
+    pub fn set_guardian_policy(
+        env: Env,
+        owner: Address,
+        policy: Option<GuardianPolicy>,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        match &policy {
+            Some(policy) => {
+                if policy.max_payment.is_some_and(|max| max < 0) {
+                    return Err(MultisigError::InvalidAmount);
+                }
+                env.storage()
+                    .persistent()
+                    .set(&StorageKey::GuardianPolicy, policy);
+            }
+            None => env
+                .storage()
+                .persistent()
+                .remove(&StorageKey::GuardianPolicy),
+        }
 
-        let mut op = read_operation(&env, operation_id);
-        assert!(
-            op.status == OperationStatus::Pending,
-            "Operation not pending"
+        env.events().publish(
+            ("guardian_policy_changed",),
+            match policy {
+                Some(policy) => GuardianPolicyChangedEvent {
+                    cleared: false,
+                    max_payment: policy.max_payment,
+                    allowed_types: policy.allowed_types,
+                    require_cosigner: policy.require_cosigner,
+                },
+                None => GuardianPolicyChangedEvent {
+                    cleared: true,
+                    max_payment: None,
+                    allowed_types: Vec::new(&env),
+                    require_cosigner: false,
+                },
+            },
         );
+        Ok(())
+    }
 
-        let owner = env
-            .storage()
+    /// @notice Sets the delay between reaching the threshold and execution.
+    /// @dev Only the owner can configure the delay. Zero executes operations
+    ///      as soon as the threshold is met. Already-queued operations keep
+    ///      their `executable_at`.
+    /// @param owner Contract owner.
+    /// @param delay_seconds New execution delay in seconds.
+    pub fn set_execution_delay(
+        env: Env,
+        owner: Address,
+        delay_seconds: u64,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let old_delay = read_execution_delay(&env);
+        env.storage()
             .persistent()
-            .get::<_, Address>(&StorageKey::Owner)
-            .expect("Owner not set");
+            .set(&StorageKey::ExecutionDelay, &delay_seconds);
 
-        assert!(
-            caller == op.creator || caller == owner,
-            "Only creator or owner can cancel"
+        env.events().publish(
+            ("execution_delay_changed",),
+            ExecutionDelayChangedEvent {
+                old_delay,
+                new_delay: delay_seconds,
+            },
         );
+        Ok(())
+    }
 
-        op.status = OperationStatus::Cancelled;
-        write_operation(&env, &op);
+    /// @notice Sets how long signers have to challenge an emergency execution.
+    /// @dev Only the owner can configure this. Zero lets the guardian execute
+    ///      immediately. Already-queued operations keep their deadline.
+    /// @param owner Contract owner.
+    /// @param period_seconds New challenge period in seconds.
+    pub fn set_challenge_period(
+        env: Env,
+        owner: Address,
+        period_seconds: u64,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let old_period = read_challenge_period(&env);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::ChallengePeriod, &period_seconds);
 
         env.events().publish(
-            ("operation_cancelled", operation_id),
-            OperationCancelledEvent { operation_id },
+            ("challenge_period_changed",),
+            ChallengePeriodChangedEvent {
+                old_period,
+                new_period: period_seconds,
+            },
         );
+        Ok(())
     }
 
-    /// @notice Executes a pending operation via the emergency guardian.
-    /// @dev Guardian can bypass threshold checks in break-glass scenarios.
-    /// @param guardian Configured guardian address.
-    /// @param operation_id Operation identifier.
-    pub fn emergency_execute(env: Env, guardian: Address, operation_id: u128) {
-        require_initialized(&env);
-        guardian.require_auth();
-        assert!(
-            is_emergency_guardian(&env, &guardian),
-            "Only guardian can execute"
+    /// @notice Returns the emergency challenge period in seconds.
+    pub fn get_challenge_period(env: Env) -> u64 {
+        read_challenge_period(&env)
+    }
+
+    /// @notice Enables or disables execution as soon as the threshold is met.
+    /// @dev Only the owner can configure this. When disabled, approved
+    ///      operations wait for an explicit `execute_operation` call.
+    /// @param owner Contract owner.
+    /// @param enabled Whether to auto-execute.
+    pub fn set_auto_execute(env: Env, owner: Address, enabled: bool) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::AutoExecute, &enabled);
+
+        env.events().publish(
+            ("auto_execute_changed",),
+            AutoExecuteChangedEvent { enabled },
         );
+        Ok(())
+    }
+
+    /// @notice Allows or forbids `Invoke` operations that target the multisig.
+    /// @dev Only the owner can configure this. Disabled by default.
+    /// @param owner Contract owner.
+    /// @param allowed Whether self-targeted `Invoke` operations are accepted.
+    pub fn set_allow_self_invoke(
+        env: Env,
+        owner: Address,
+        allowed: bool,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::AllowSelfInvoke, &allowed);
+
+        env.events()
+            .publish(("self_invoke_changed",), SelfInvokeChangedEvent { allowed });
+        Ok(())
+    }
+
+    /// @notice Requires or stops requiring the owner's approval for an operation type.
+    /// @dev Only the owner can configure this. Applies to pending operations
+    ///      too, since the flag is checked at execution time. Emergency
+    ///      guardian execution is not affected.
+    /// @param owner Contract owner.
+    /// @param op_type Operation type to configure.
+    /// @param required Whether `owner_approve` is needed before execution.
+    pub fn set_require_owner_approval(
+        env: Env,
+        owner: Address,
+        op_type: OperationType,
+        required: bool,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let mut types = read_owner_approval_types(&env);
+        match (types.first_index_of(op_type), required) {
+            (None, true) => types.push_back(op_type),
+            (Some(index), false) => {
+                types.remove(index);
+            }
+            _ => {}
+        }
+        env.storage()
+            .persistent()
+            .set(&StorageKey::OwnerApprovalTypes, &types);
 
-        let op = read_operation(&env, operation_id);
-        assert!(
-            op.status == OperationStatus::Pending,
-            "Operation not pending"
+        env.events().publish(
+            ("owner_approval_requirement_changed",),
+            OwnerApprovalRequirementChangedEvent { op_type, required },
         );
+        Ok(())
+    }
+
+    /// @notice Returns whether an operation type needs the owner's approval.
+    pub fn get_require_owner_approval(env: Env, op_type: OperationType) -> bool {
+        read_owner_approval_types(&env).contains(op_type)
+    }
+
+    /// @notice Returns whether `Invoke` operations may target the multisig.
+    pub fn get_allow_self_invoke(env: Env) -> bool {
+        read_allow_self_invoke(&env)
+    }
+
+    /// @notice Returns whether operations auto-execute on reaching the threshold.
+    pub fn get_auto_execute(env: Env) -> bool {
+        read_auto_execute(&env)
+    }
+
+    /// @notice Returns the execution delay in seconds.
+    pub fn get_execution_delay(env: Env) -> u64 {
+        read_execution_delay(&env)
+    }
 
-        perform_execute(&env, operation_id);
+    /// @notice Returns the guardian policy, if any.
+    pub fn get_guardian_policy(env: Env) -> Option<GuardianPolicy> {
+        env.storage().persistent().get(&StorageKey::GuardianPolicy)
     }
 
     /// @notice Returns the stored operation by id, if any.
@@ -427,15 +2749,46 @@ impl MultisigContract {
             .get(&StorageKey::Operation(operation_id))
     }
 
-    /// @notice Returns the current signer set.
+    /// @notice Returns the outcome of an operation's last cross-contract execution attempt.
+    /// @dev Failed calls are recorded too, whether triggered by
+    ///      `execute_operation` or by auto-execution on approval; the operation
+    ///      then stays pending so it can be executed again.
+    pub fn get_execution_record(env: Env, operation_id: u128) -> Option<ExecutionRecord> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::ExecutionRecord(operation_id))
+    }
+
+    /// @notice Returns whether an operation was executed by the emergency guardian.
+    pub fn was_emergency_executed(env: Env, operation_id: u128) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, bool>(&StorageKey::ExecutedByGuardian(operation_id))
+            .unwrap_or(false)
+    }
+
+    /// @notice Returns the memo attached to an operation, if any.
+    /// @param operation_id operation_id parameter
+    pub fn get_operation_memo(env: Env, operation_id: u128) -> Option<Bytes> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::OperationMemo(operation_id))
+    }
+
+    /// @notice Returns the current signer set with each signer's weight.
     /// @dev Requires caller authentication
-    pub fn get_signers(env: Env) -> Vec<Address> {
-        read_signers(&env)
+    pub fn get_signers(env: Env) -> Result<Vec<(Address, u32)>, MultisigError> {
+        let mut weighted = Vec::new(&env);
+        for signer in read_signers(&env)?.iter() {
+            let weight = signer_weight(&env, &signer);
+            weighted.push_back((signer, weight));
+        }
+        Ok(weighted)
     }
 
-    /// @notice Returns the current threshold.
+    /// @notice Returns the current threshold (a sum of signer weights).
     /// @dev Requires caller authentication
-    pub fn get_threshold(env: Env) -> u32 {
+    pub fn get_threshold(env: Env) -> Result<u32, MultisigError> {
         read_threshold(&env)
     }
 
@@ -445,4 +2798,165 @@ impl MultisigContract {
     pub fn get_approvals(env: Env, operation_id: u128) -> Vec<Address> {
         read_approvals(&env, operation_id)
     }
+
+    /// @notice Returns a page of operations still awaiting execution.
+    /// @dev Operations are returned in proposal order. `limit` is capped at 50.
+    /// @param start Index into the pending list to start from.
+    /// @param limit Maximum number of operations to return.
+    pub fn get_pending_operations(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Operation>, MultisigError> {
+        page_pending_operations(&env, start, limit)
+    }
+
+    /// @notice Returns a page of operations with the given status.
+    /// @dev Pending operations are returned in proposal order; executed,
+    ///      cancelled and expired ones in the order they reached that status.
+    ///      `limit` is capped at 50.
+    /// @param status Status to filter by.
+    /// @param start Index into the filtered list to start from.
+    /// @param limit Maximum number of operations to return.
+    pub fn get_operations_by_status(
+        env: Env,
+        status: OperationStatus,
+        start: u32,
+        limit: u32,
+    ) -> Result<Vec<Operation>, MultisigError> {
+        if status == OperationStatus::Pending {
+            return page_pending_operations(&env, start, limit);
+        }
+        page_status_operations(&env, status, start, limit)
+    }
+
+    /// @notice Extends the TTL of the wallet's configuration and pending operations.
+    /// @dev Anyone may call this; it only postpones archival. Entries touched
+    ///      by regular calls are extended as they are used, so this is for
+    ///      wallets that sit idle for long periods.
+    pub fn bump_state(env: Env) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        extend_config_ttl(&env)?;
+
+        for page in read_pending_pages(&env).iter() {
+            extend_ttl_if_present(&env, &StorageKey::PendingOps(page));
+        }
+        for id in read_pending_ops(&env).iter() {
+            let op = read_operation(&env, id)?;
+            for signer in read_approvals(&env, id).iter() {
+                extend_ttl_if_present(&env, &StorageKey::Approval(id, signer));
+            }
+            extend_ttl_if_present(&env, &StorageKey::OperationMemo(id));
+            if let Some(bond) = env
+                .storage()
+                .persistent()
+                .get::<_, ProposalBond>(&StorageKey::OperationBond(id))
+            {
+                extend_ttl_if_present(&env, &StorageKey::OperationBond(id));
+                extend_ttl_if_present(&env, &StorageKey::HeldBonds(bond.token));
+            }
+            extend_ttl_if_present(&env, &StorageKey::OwnerApproval(id));
+            extend_ttl_if_present(&env, &StorageKey::ExecutionRecord(id));
+            extend_ttl_if_present(
+                &env,
+                &StorageKey::PendingByHash(operation_hash(&env, &op.kind)),
+            );
+        }
+        Ok(())
+    }
+
+    /// @notice Sets the bond signers pay to propose an operation.
+    /// @dev Only the owner can configure this. An amount of 0 disables bonds.
+    ///      Bonds already held keep their original token and amount.
+    /// @param owner Contract owner.
+    /// @param token Token the bond is paid in.
+    /// @param amount Bond per proposal.
+    pub fn set_proposal_bond(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+        if amount < 0 {
+            return Err(MultisigError::InvalidAmount);
+        }
+
+        if amount == 0 {
+            env.storage().persistent().remove(&StorageKey::ProposalBond);
+        } else {
+            env.storage().persistent().set(
+                &StorageKey::ProposalBond,
+                &ProposalBond {
+                    token: token.clone(),
+                    amount,
+                },
+            );
+        }
+
+        env.events().publish(
+            ("proposal_bond_changed",),
+            ProposalBondChangedEvent { token, amount },
+        );
+        Ok(())
+    }
+
+    /// @notice Returns the proposal bond, if bonds are enabled.
+    pub fn get_proposal_bond(env: Env) -> Option<ProposalBond> {
+        env.storage().persistent().get(&StorageKey::ProposalBond)
+    }
+
+    /// @notice Returns the total of the proposal bonds held in `token`.
+    /// @dev Payments cannot spend this part of the multisig's balance.
+    pub fn get_held_bonds(env: Env, token: Address) -> i128 {
+        read_held_bonds(&env, &token)
+    }
+
+    /// @notice Sets how long new proposals have to reach the threshold.
+    /// @dev Only the owner can configure this. Zero disables expiry. Existing
+    ///      operations keep their `expires_at`.
+    /// @param owner Contract owner.
+    /// @param lifetime_seconds New proposal lifetime in seconds.
+    pub fn set_proposal_lifetime(
+        env: Env,
+        owner: Address,
+        lifetime_seconds: u64,
+    ) -> Result<(), MultisigError> {
+        require_initialized(&env)?;
+        owner.require_auth();
+        if owner != read_owner(&env)? {
+            return Err(MultisigError::Unauthorized);
+        }
+
+        let old_lifetime = read_proposal_lifetime(&env);
+        env.storage()
+            .persistent()
+            .set(&StorageKey::ProposalLifetime, &lifetime_seconds);
+
+        env.events().publish(
+            ("proposal_lifetime_changed",),
+            ProposalLifetimeChangedEvent {
+                old_lifetime,
+                new_lifetime: lifetime_seconds,
+            },
+        );
+        Ok(())
+    }
+
+    /// @notice Returns the proposal lifetime in seconds (0 means no expiry).
+    pub fn get_proposal_lifetime(env: Env) -> u64 {
+        read_proposal_lifetime(&env)
+    }
+
+    /// @notice Returns the total number of operations ever proposed.
+    pub fn get_operation_count(env: Env) -> u128 {
+        env.storage()
+            .persistent()
+            .get::<_, u128>(&StorageKey::OperationCounter)
+            .unwrap_or(0)
+    }
 }
@@ -0,0 +1,275 @@
+//! Multisig-as-arbiter integration tests against the payroll contract wasm.
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    vec, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+use multisig::{
+    ExecutionRecord, MultisigContract, MultisigContractClient, OperationExecutionFailedEvent,
+    OperationKind, OperationStatus,
+};
+
+/// Payroll contract build committed for the gas benchmarks. Its spec cannot
+/// be imported with `contractimport!`, so calls go through `invoke_contract`.
+const PAYROLL_WASM: &[u8] =
+    include_bytes!("../../stello_pay_contract/tests/stello_pay_contract.wasm");
+
+fn call_payroll<T: soroban_sdk::TryFromVal<Env, Val>>(
+    env: &Env,
+    payroll: &Address,
+    func: &str,
+    args: Vec<Val>,
+) -> T {
+    env.invoke_contract(payroll, &Symbol::new(env, func), args)
+}
+
+struct Setup {
+    env: Env,
+    multisig: MultisigContractClient<'static>,
+    owner: Address,
+    signers: Vec<Address>,
+    payroll: Address,
+    token: TokenClient<'static>,
+    employer: Address,
+    contributor: Address,
+    agreement_id: u128,
+}
+
+/// 2-of-3 multisig configured as arbiter of a funded 1,000-token escrow agreement.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let multisig_id = env.register(MultisigContract, ());
+    let multisig = MultisigContractClient::new(&env, &multisig_id);
+    let mut signers = Vec::new(&env);
+    for _ in 0..3 {
+        signers.push_back(Address::generate(&env));
+    }
+    let owner = Address::generate(&env);
+    multisig.initialize(&owner, &signers, &2u32, &None, &None);
+
+    let payroll = env.register(PAYROLL_WASM, ());
+    let employer = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token = TokenClient::new(&env, &token_addr);
+
+    call_payroll::<()>(
+        &env,
+        &payroll,
+        "initialize",
+        vec![&env, employer.into_val(&env)],
+    );
+    call_payroll::<bool>(
+        &env,
+        &payroll,
+        "set_arbiter",
+        vec![&env, employer.into_val(&env), multisig_id.into_val(&env)],
+    );
+    let agreement_id: u128 = call_payroll(
+        &env,
+        &payroll,
+        "create_escrow_agreement",
+        vec![
+            &env,
+            employer.into_val(&env),
+            contributor.into_val(&env),
+            token_addr.into_val(&env),
+            1000i128.into_val(&env),
+            3600u64.into_val(&env),
+            1u32.into_val(&env),
+        ],
+    );
+    StellarAssetClient::new(&env, &token_addr).mint(&payroll, &1000);
+
+    Setup {
+        env,
+        multisig,
+        owner,
+        signers,
+        payroll,
+        token,
+        employer,
+        contributor,
+        agreement_id,
+    }
+}
+
+fn raise_dispute(s: &Setup) {
+    call_payroll::<()>(
+        &s.env,
+        &s.payroll,
+        "raise_dispute",
+        vec![
+            &s.env,
+            s.employer.into_val(&s.env),
+            s.agreement_id.into_val(&s.env),
+        ],
+    );
+}
+
+#[test]
+fn raised_dispute_resolved_through_multisig() {
+    let s = setup();
+    raise_dispute(&s);
+
+    let op_id = s.multisig.propose_operation(
+        &s.signers.get(0).unwrap(),
+        &OperationKind::DisputeResolution(s.payroll.clone(), s.agreement_id, 600, 400),
+        &None,
+        &None,
+    );
+
+    // One approval is not enough; nothing is paid out yet.
+    assert_eq!(s.token.balance(&s.contributor), 0);
+
+    s.multisig
+        .approve_operation(&s.signers.get(1).unwrap(), &op_id);
+
+    let op = s.multisig.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Executed);
+    assert_eq!(s.token.balance(&s.contributor), 600);
+    assert_eq!(s.token.balance(&s.employer), 400);
+}
+
+#[test]
+fn failed_auto_execution_is_recorded_and_stays_pending() {
+    let s = setup();
+    assert!(s.multisig.get_auto_execute());
+    s.env.ledger().set_timestamp(1_000);
+
+    let op_id = s.multisig.propose_operation(
+        &s.signers.get(0).unwrap(),
+        &OperationKind::DisputeResolution(s.payroll.clone(), s.agreement_id, 600, 400),
+        &None,
+        &None,
+    );
+
+    // The approval meeting the threshold auto-executes; PayrollError::NoDispute
+    // is recorded instead of reverting the approval.
+    s.multisig
+        .approve_operation(&s.signers.get(1).unwrap(), &op_id);
+
+    let op = s.multisig.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.execution_result, Some(false));
+    assert_eq!(s.multisig.get_approvals(&op_id).len(), 2);
+    assert_eq!(
+        s.multisig.get_execution_record(&op_id),
+        Some(ExecutionRecord {
+            operation_id: op_id,
+            executed_at: 1_000,
+            success: false,
+            error_code: Some(8),
+        })
+    );
+    assert_eq!(s.token.balance(&s.contributor), 0);
+
+    // Once a dispute is raised, the same operation can still be executed.
+    raise_dispute(&s);
+    s.multisig
+        .execute_operation(&Address::generate(&s.env), &op_id);
+    assert_eq!(
+        s.multisig.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(s.token.balance(&s.contributor), 600);
+}
+
+#[test]
+fn failed_manual_execution_is_recorded_and_retryable() {
+    let s = setup();
+    s.multisig.set_auto_execute(&s.owner, &false);
+
+    let op_id = s.multisig.propose_operation(
+        &s.signers.get(0).unwrap(),
+        &OperationKind::DisputeResolution(s.payroll.clone(), s.agreement_id, 600, 400),
+        &None,
+        &None,
+    );
+    s.multisig
+        .approve_operation(&s.signers.get(1).unwrap(), &op_id);
+
+    // No dispute raised yet: the payroll call fails, the failure is recorded.
+    let caller = Address::generate(&s.env);
+    s.multisig.execute_operation(&caller, &op_id);
+    let op = s.multisig.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.execution_result, Some(false));
+    assert_eq!(s.token.balance(&s.contributor), 0);
+
+    raise_dispute(&s);
+    s.multisig.execute_operation(&caller, &op_id);
+    let op = s.multisig.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Executed);
+    assert_eq!(op.execution_result, Some(true));
+    assert_eq!(s.token.balance(&s.contributor), 600);
+}
+
+#[test]
+fn execution_record_captures_downstream_error() {
+    let s = setup();
+    s.multisig.set_auto_execute(&s.owner, &false);
+    s.env.ledger().set_timestamp(1_000);
+
+    let op_id = s.multisig.propose_operation(
+        &s.signers.get(0).unwrap(),
+        &OperationKind::DisputeResolution(s.payroll.clone(), s.agreement_id, 600, 400),
+        &None,
+        &None,
+    );
+    s.multisig
+        .approve_operation(&s.signers.get(1).unwrap(), &op_id);
+    assert_eq!(s.multisig.get_execution_record(&op_id), None);
+
+    // Resolving a dispute that was never raised fails with NoDispute (8).
+    let caller = Address::generate(&s.env);
+    s.multisig.execute_operation(&caller, &op_id);
+    let failed = s
+        .env
+        .events()
+        .all()
+        .iter()
+        .find(|e| e.1 == ("operation_execution_failed", op_id).into_val(&s.env))
+        .map(|e| {
+            let event: OperationExecutionFailedEvent = e.2.into_val(&s.env);
+            event
+        });
+    assert_eq!(
+        failed,
+        Some(OperationExecutionFailedEvent {
+            operation_id: op_id,
+            error_code: Some(8),
+        })
+    );
+    assert_eq!(
+        s.multisig.get_execution_record(&op_id),
+        Some(ExecutionRecord {
+            operation_id: op_id,
+            executed_at: 1_000,
+            success: false,
+            error_code: Some(8),
+        })
+    );
+
+    // A later successful attempt replaces the record.
+    raise_dispute(&s);
+    s.env.ledger().set_timestamp(2_000);
+    s.multisig.execute_operation(&caller, &op_id);
+    assert_eq!(
+        s.multisig.get_execution_record(&op_id),
+        Some(ExecutionRecord {
+            operation_id: op_id,
+            executed_at: 2_000,
+            success: true,
+            error_code: None,
+        })
+    );
+}
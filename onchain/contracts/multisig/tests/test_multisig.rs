@@ -1,12 +1,22 @@
 #![cfg(test)]
+#![allow(deprecated)]
 
 use soroban_sdk::{
-    testutils::Address as _,
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, Events, Ledger,
+    },
     token::{Client as TokenClient, StellarAssetClient},
-    Address, BytesN, Env, Vec,
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec,
 };
 
-use multisig::{MultisigContract, MultisigContractClient, OperationKind, OperationStatus};
+use multisig::{
+    ApprovalsRecomputedEvent, BatchPaymentExecutedEvent, DailyLimitChangedEvent,
+    EmergencyExecutedEvent, ExecutionDelayChangedEvent, GuardianPolicy, MultisigContract,
+    MultisigContractClient, MultisigError, MultisigInitializedEvent, OperationKind,
+    OperationProposedEvent, OperationStatus, OperationType, SignersRotatedEvent,
+    ThresholdChangedEvent, TTL_MAX_LEDGERS, TTL_MIN_LEDGERS,
+};
 
 fn create_env() -> Env {
     let env = Env::default();
@@ -48,7 +58,7 @@ fn setup_initialized(
 
     let guardian = Address::generate(env);
 
-    client.initialize(&owner, &signers, &2u32, &Some(guardian.clone()));
+    client.initialize(&owner, &signers, &2u32, &Some(guardian.clone()), &None);
 
     (id, client, owner, signers, guardian)
 }
@@ -64,19 +74,19 @@ fn initialize_rejects_invalid_threshold() {
     signers.push_back(s1);
 
     // threshold 0 is invalid
-    let res = client.try_initialize(&owner, &signers, &0u32, &None);
-    assert!(res.is_err());
+    let res = client.try_initialize(&owner, &signers, &0u32, &None, &None);
+    assert_eq!(res, Err(Ok(MultisigError::InvalidThreshold)));
 
     // threshold > len(signers) is invalid
-    let res = client.try_initialize(&owner, &signers, &2u32, &None);
-    assert!(res.is_err());
+    let res = client.try_initialize(&owner, &signers, &2u32, &None, &None);
+    assert_eq!(res, Err(Ok(MultisigError::InvalidThreshold)));
 
     // Sanity: valid config succeeds
-    client.initialize(&owner, &signers, &1u32, &None);
+    client.initialize(&owner, &signers, &1u32, &None, &None);
 
     // second initialize should fail
-    let res = client.try_initialize(&owner, &signers, &1u32, &None);
-    assert!(res.is_err());
+    let res = client.try_initialize(&owner, &signers, &1u32, &None, &None);
+    assert_eq!(res, Err(Ok(MultisigError::AlreadyInitialized)));
 
     // avoid unused warning
     let _ = id;
@@ -94,6 +104,8 @@ fn propose_and_auto_approve_by_creator() {
     let op_id = client.propose_operation(
         &proposer,
         &OperationKind::ContractUpgrade(target.clone(), hash),
+        &None,
+        &None,
     );
 
     let op = client.get_operation(&op_id).unwrap();
@@ -123,6 +135,8 @@ fn threshold_execution_for_large_payment() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 500i128),
+        &None,
+        &None,
     );
 
     // One approval (from proposer) is not enough yet (threshold = 2)
@@ -154,10 +168,12 @@ fn emergency_guardian_can_execute_without_threshold() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 200i128),
+        &None,
+        &None,
     );
 
     // Guardian executes directly
-    client.emergency_execute(&guardian, &op_id);
+    client.emergency_queue(&guardian, &op_id);
 
     let op = client.get_operation(&op_id).unwrap();
     assert_eq!(op.status, OperationStatus::Executed);
@@ -175,11 +191,13 @@ fn cancel_operation_by_creator_or_owner() {
     let op_id = client.propose_operation(
         &proposer,
         &OperationKind::DisputeResolution(Address::generate(&env), 1u128, 10, 0),
+        &None,
+        &None,
     );
 
     // non-creator, non-owner cannot cancel
     let res = client.try_cancel_operation(&other, &op_id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(MultisigError::Unauthorized)));
 
     // creator can cancel
     client.cancel_operation(&proposer, &op_id);
@@ -188,5 +206,2473 @@ fn cancel_operation_by_creator_or_owner() {
 
     // owner can no longer cancel an already-cancelled op
     let res = client.try_cancel_operation(&owner, &op_id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(MultisigError::OperationNotPending)));
+}
+
+#[test]
+fn guardian_execution_is_flagged_and_announced() {
+    let env = create_env();
+    let (_id, client, _owner, signers, guardian) = setup_initialized(&env);
+
+    let emergency = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::AddSigner(Address::generate(&env)),
+        &None,
+        &None,
+    );
+    client.emergency_queue(&guardian, &emergency);
+
+    let events = env.events().all();
+    let position = |topic: &str| {
+        events
+            .iter()
+            .position(|e| e.1 == (topic, emergency).into_val(&env))
+            .unwrap()
+    };
+    let emergency_at = position("emergency_executed");
+    assert!(emergency_at < position("operation_executed"));
+    let event: EmergencyExecutedEvent = events.get(emergency_at as u32).unwrap().2.into_val(&env);
+    assert_eq!(
+        event,
+        EmergencyExecutedEvent {
+            operation_id: emergency,
+            guardian: guardian.clone(),
+            approvals_at_execution: 1,
+            threshold: 2,
+        }
+    );
+    assert!(client.was_emergency_executed(&emergency));
+
+    // A regular threshold execution is not flagged.
+    let normal = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &normal);
+    let guardian_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|e| e.1 == ("emergency_executed", normal).into_val(&env))
+        .count();
+    assert_eq!(guardian_events, 0);
+    assert_eq!(
+        client.get_operation(&normal).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert!(!client.was_emergency_executed(&normal));
+}
+
+// ==================== Signer Management ====================
+
+#[test]
+fn added_signer_can_approve_operations() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let new_signer = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::AddSigner(new_signer.clone()),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(client.get_signers().len(), 4);
+    assert!(client.get_signers().contains(&(new_signer.clone(), 1)));
+
+    // The 4th signer can now contribute the second approval.
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+    let pay_id = client.propose_operation(
+        &signers.get(2).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 300i128),
+        &None,
+        &None,
+    );
+    client.approve_operation(&new_signer, &pay_id);
+    assert_eq!(token.balance(&recipient), 300);
+}
+
+#[test]
+fn add_existing_signer_rejected() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::AddSigner(signers.get(2).unwrap()),
+        &None,
+        &None,
+    );
+    let res = client.try_approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::DuplicateSigner)));
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+    assert_eq!(client.get_signers().len(), 3);
+}
+
+#[test]
+fn removed_signer_pending_approval_no_longer_counts() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let s3 = signers.get(2).unwrap();
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+
+    // s3 proposes a payment (1 of 2 approvals).
+    let pay_id = client.propose_operation(
+        &s3,
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
+    );
+
+    // s1 and s2 remove s3.
+    let remove_id =
+        client.propose_operation(&s1, &OperationKind::RemoveSigner(s3.clone()), &None, &None);
+    client.approve_operation(&s2, &remove_id);
+    assert_eq!(client.get_signers().len(), 2);
+    assert!(!client.get_signers().contains(&(s3.clone(), 1)));
+
+    // s3's approval has been purged, so one more approval is still not enough.
+    assert_eq!(client.get_approvals(&pay_id).len(), 0);
+    client.approve_operation(&s1, &pay_id);
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().status,
+        OperationStatus::Pending
+    );
+    assert_eq!(token.balance(&recipient), 0);
+
+    client.approve_operation(&s2, &pay_id);
+    assert_eq!(token.balance(&recipient), 100);
+
+    // s3 can no longer approve.
+    let res = client.try_approve_operation(&s3, &remove_id);
+    assert_eq!(res, Err(Ok(MultisigError::NotSigner)));
+}
+
+#[test]
+fn removal_drops_nearly_approved_operation_back_two_approvals() {
+    let env = create_env();
+    let (multisig_id, client) = register_contract(&env);
+    let owner = Address::generate(&env);
+    let mut signers = Vec::new(&env);
+    for _ in 0..4 {
+        signers.push_back(Address::generate(&env));
+    }
+    client.initialize(&owner, &signers, &3u32, &None, &None);
+    let (s1, s2, s3, s4) = (
+        signers.get(0).unwrap(),
+        signers.get(1).unwrap(),
+        signers.get(2).unwrap(),
+        signers.get(3).unwrap(),
+    );
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+
+    // s4 proposes and s1 approves: one approval short of 3.
+    let pay_id = client.propose_operation(
+        &s4,
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
+    );
+    client.approve_operation(&s1, &pay_id);
+
+    let remove_id =
+        client.propose_operation(&s1, &OperationKind::RemoveSigner(s4.clone()), &None, &None);
+    client.approve_operation(&s2, &remove_id);
+    client.approve_operation(&s3, &remove_id);
+
+    let topics: Vec<soroban_sdk::Val> = ("approvals_recomputed", pay_id).into_val(&env);
+    let recomputed: std::vec::Vec<ApprovalsRecomputedEvent> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|e| e.1 == topics)
+        .map(|e| e.2.into_val(&env))
+        .collect();
+    assert_eq!(
+        recomputed,
+        [ApprovalsRecomputedEvent {
+            operation_id: pay_id,
+            removed_signer: s4.clone(),
+            approvals: 1,
+            approved_weight: 1,
+            threshold_met: false,
+        }]
+    );
+
+    // Only s1's approval remains, so two more are needed.
+    assert_eq!(client.get_approvals(&pay_id), Vec::from_array(&env, [s1]));
+    client.approve_operation(&s2, &pay_id);
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().status,
+        OperationStatus::Pending
+    );
+    client.approve_operation(&s3, &pay_id);
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn removing_signer_unqueues_delayed_operation() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let s3 = signers.get(2).unwrap();
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    client.set_execution_delay(&owner, &3_600);
+    env.ledger().set_timestamp(1_000);
+
+    // s3 and s1 approve a payment; it is queued.
+    let recipient = Address::generate(&env);
+    let pay_id = client.propose_operation(
+        &s3,
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
+    );
+    client.approve_operation(&s1, &pay_id);
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().executable_at,
+        Some(4_600)
+    );
+
+    let remove_id =
+        client.propose_operation(&s1, &OperationKind::RemoveSigner(s3.clone()), &None, &None);
+    client.approve_operation(&s2, &remove_id);
+
+    env.ledger().set_timestamp(4_600);
+    let anyone = Address::generate(&env);
+    client.execute_operation(&anyone, &remove_id);
+
+    // Without s3 the payment is below the threshold and no longer queued.
+    assert_eq!(client.get_operation(&pay_id).unwrap().executable_at, None);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &pay_id),
+        Err(Ok(MultisigError::NotQueued))
+    );
+    assert_eq!(token.balance(&recipient), 0);
+
+    // Re-approval queues it again behind a fresh delay.
+    client.approve_operation(&s2, &pay_id);
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().executable_at,
+        Some(8_200)
+    );
+    env.ledger().set_timestamp(8_200);
+    client.execute_operation(&anyone, &pay_id);
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn remove_signer_below_threshold_rejected() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let s3 = signers.get(2).unwrap();
+
+    let first =
+        client.propose_operation(&s1, &OperationKind::RemoveSigner(s3.clone()), &None, &None);
+    client.approve_operation(&s2, &first);
+    assert_eq!(client.get_signers().len(), 2);
+
+    // 2-of-2: removing another signer would leave 1 signer for threshold 2.
+    let second =
+        client.propose_operation(&s1, &OperationKind::RemoveSigner(s2.clone()), &None, &None);
+    let res = client.try_approve_operation(&s2, &second);
+    assert_eq!(res, Err(Ok(MultisigError::SignerCountBelowThreshold)));
+    assert_eq!(client.get_signers().len(), 2);
+}
+
+#[test]
+fn rotate_signers_replaces_set_and_purges_stale_approvals() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let s3 = signers.get(2).unwrap();
+    let n1 = Address::generate(&env);
+    let n2 = Address::generate(&env);
+
+    // Pending operation carrying only the approval of a signer being rotated out.
+    let new_member = Address::generate(&env);
+    let pending = client.propose_operation(
+        &s3,
+        &OperationKind::AddSigner(new_member.clone()),
+        &None,
+        &None,
+    );
+
+    let new_set = Vec::from_array(&env, [s1.clone(), n1.clone(), n2.clone()]);
+    let rotate_id = client.propose_operation(
+        &s1,
+        &OperationKind::RotateSigners(new_set.clone(), 2),
+        &None,
+        &None,
+    );
+    client.approve_operation(&s2, &rotate_id);
+
+    let rotated = env
+        .events()
+        .all()
+        .iter()
+        .find(|e| e.1 == ("signers_rotated", rotate_id).into_val(&env))
+        .map(|e| {
+            let event: SignersRotatedEvent = e.2.into_val(&env);
+            event
+        });
+    assert_eq!(
+        rotated,
+        Some(SignersRotatedEvent {
+            operation_id: rotate_id,
+            old_count: 3,
+            new_count: 3,
+            old_threshold: 2,
+            new_threshold: 2,
+        })
+    );
+
+    let mut expected = Vec::new(&env);
+    for signer in new_set.iter() {
+        expected.push_back((signer, 1u32));
+    }
+    assert_eq!(client.get_signers(), expected);
+    assert_eq!(client.get_approvals(&pending).len(), 0);
+    let res = client.try_propose_operation(&s2, &OperationKind::ChangeThreshold(1), &None, &None);
+    assert_eq!(res, Err(Ok(MultisigError::NotSigner)));
+
+    // The new set can act immediately, including on the older pending operation.
+    let op_id = client.propose_operation(&n1, &OperationKind::ChangeThreshold(3), &None, &None);
+    client.approve_operation(&n2, &op_id);
+    assert_eq!(client.get_threshold(), 3);
+
+    client.approve_operation(&n1, &pending);
+    client.approve_operation(&n2, &pending);
+    client.approve_operation(&s1, &pending);
+    assert_eq!(
+        client.get_operation(&pending).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(client.get_signers().len(), 4);
+}
+
+#[test]
+fn rotate_signers_validated_like_initialize() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let n1 = Address::generate(&env);
+    let n2 = Address::generate(&env);
+
+    for (new_set, threshold, err) in [
+        (Vec::new(&env), 1, MultisigError::NoSigners),
+        (
+            Vec::from_array(&env, [n1.clone(), n2.clone()]),
+            3,
+            MultisigError::InvalidThreshold,
+        ),
+        (
+            Vec::from_array(&env, [n1.clone(), n2.clone()]),
+            0,
+            MultisigError::InvalidThreshold,
+        ),
+        (
+            Vec::from_array(&env, [n1.clone(), n2.clone(), n1.clone()]),
+            2,
+            MultisigError::DuplicateSigner,
+        ),
+    ] {
+        let res = client.try_propose_operation(
+            &s1,
+            &OperationKind::RotateSigners(new_set, threshold),
+            &None,
+            &None,
+        );
+        assert_eq!(res, Err(Ok(err)));
+    }
+}
+
+// ==================== Threshold Changes ====================
+
+#[test]
+fn raise_threshold_requires_more_approvals() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let s3 = signers.get(2).unwrap();
+
+    let op_id = client.propose_operation(&s1, &OperationKind::ChangeThreshold(3), &None, &None);
+    client.approve_operation(&s2, &op_id);
+    assert_eq!(client.get_threshold(), 3);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+    let pay_id = client.propose_operation(
+        &s1,
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
+    );
+    client.approve_operation(&s2, &pay_id);
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().status,
+        OperationStatus::Pending
+    );
+    client.approve_operation(&s3, &pay_id);
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn lowered_threshold_rechecks_pending_on_next_approval() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+
+    // Pending payment with a single approval under 2-of-3.
+    let pay_id = client.propose_operation(
+        &s1,
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
+    );
+
+    let op_id = client.propose_operation(&s1, &OperationKind::ChangeThreshold(1), &None, &None);
+    client.approve_operation(&s2, &op_id);
+    assert_eq!(client.get_threshold(), 1);
+
+    // Lowering the threshold does not execute pending operations by itself.
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().status,
+        OperationStatus::Pending
+    );
+
+    // A repeat approval re-checks against the new threshold.
+    client.approve_operation(&s1, &pay_id);
+    assert_eq!(
+        client.get_operation(&pay_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(client.get_approvals(&pay_id).len(), 1);
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn invalid_threshold_rejected() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let s3 = signers.get(2).unwrap();
+
+    // Fails fast at proposal time.
+    assert_eq!(
+        client.try_propose_operation(&s1, &OperationKind::ChangeThreshold(0), &None, &None),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+    assert_eq!(
+        client.try_propose_operation(&s1, &OperationKind::ChangeThreshold(4), &None, &None),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+
+    // Valid when proposed, invalid by execution time after a signer is removed.
+    let change_id = client.propose_operation(&s1, &OperationKind::ChangeThreshold(3), &None, &None);
+    let remove_id =
+        client.propose_operation(&s1, &OperationKind::RemoveSigner(s3.clone()), &None, &None);
+    client.approve_operation(&s2, &remove_id);
+    assert_eq!(client.get_signers().len(), 2);
+
+    assert_eq!(
+        client.try_approve_operation(&s2, &change_id),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+    assert_eq!(client.get_threshold(), 2);
+    assert_eq!(
+        client.get_operation(&change_id).unwrap().status,
+        OperationStatus::Pending
+    );
+}
+
+// ==================== Contract Upgrade ====================
+
+/// Any valid contract wasm works as the upgrade target; reuse the payroll
+/// fixture already committed for the gas benchmarks.
+const UPGRADE_WASM: &[u8] =
+    include_bytes!("../../stello_pay_contract/tests/stello_pay_contract.wasm");
+
+#[test]
+fn self_upgrade_applies_only_after_threshold() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let new_hash = env.deployer().upload_contract_wasm(UPGRADE_WASM);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ContractUpgrade(multisig_id.clone(), new_hash),
+        &None,
+        &None,
+    );
+
+    // Below threshold: still running the multisig code.
+    assert_eq!(client.get_threshold(), 2);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    // The contract now runs the uploaded wasm, which has no multisig entrypoints.
+    assert!(client.try_get_threshold().is_err());
+}
+
+#[test]
+fn flagged_upgrade_waits_for_owner_approval() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    client.set_require_owner_approval(&owner, &OperationType::ContractUpgrade, &true);
+    assert!(client.get_require_owner_approval(&OperationType::ContractUpgrade));
+    assert!(!client.get_require_owner_approval(&OperationType::LargePayment));
+
+    let new_hash = env.deployer().upload_contract_wasm(UPGRADE_WASM);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ContractUpgrade(multisig_id.clone(), new_hash),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    // Signer threshold met, but the owner has not approved yet.
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+    assert!(!client.has_owner_approval(&op_id));
+
+    client.owner_approve(&owner, &op_id);
+    let approved = env
+        .events()
+        .all()
+        .iter()
+        .any(|e| e.1 == ("owner_approved", op_id).into_val(&env));
+    assert!(approved);
+    assert!(client.try_get_threshold().is_err());
+}
+
+#[test]
+fn owner_approval_is_owner_only_and_blocks_manual_execution() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    client.set_auto_execute(&owner, &false);
+    client.set_require_owner_approval(&owner, &OperationType::ChangeThreshold, &true);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    let res = client.try_execute_operation(&owner, &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::OwnerApprovalRequired)));
+    let res = client.try_owner_approve(&signers.get(0).unwrap(), &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::Unauthorized)));
+
+    // Owner approval alone carries no signer weight.
+    let other = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(1),
+        &None,
+        &None,
+    );
+    client.owner_approve(&owner, &other);
+    let res = client.try_execute_operation(&owner, &other);
+    assert_eq!(res, Err(Ok(MultisigError::ThresholdNotMet)));
+
+    client.owner_approve(&owner, &op_id);
+    client.execute_operation(&owner, &op_id);
+    assert_eq!(client.get_threshold(), 3);
+
+    // Clearing the flag lets the type execute on the threshold alone.
+    client.set_require_owner_approval(&owner, &OperationType::ChangeThreshold, &false);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(2),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    client.approve_operation(&signers.get(2).unwrap(), &op_id);
+    client.execute_operation(&owner, &op_id);
+    assert_eq!(client.get_threshold(), 2);
+}
+
+// ==================== Batch Payments ====================
+
+#[test]
+fn batch_payment_pays_all_recipients() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &2_000i128);
+
+    let mut payments = Vec::new(&env);
+    let mut recipients = Vec::new(&env);
+    for i in 1..=5 {
+        let to = Address::generate(&env);
+        payments.push_back((to.clone(), i * 100));
+        recipients.push_back(to);
+    }
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::BatchPayment(token.address.clone(), payments),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    // The summary event precedes the generic operation_executed event.
+    let events = env.events().all();
+    let summary: BatchPaymentExecutedEvent = events.get(events.len() - 2).unwrap().2.into_val(&env);
+    assert_eq!(summary.recipients, 5);
+    assert_eq!(summary.total, 1_500);
+
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    for i in 0..5u32 {
+        let expected = (i as i128 + 1) * 100;
+        assert_eq!(token.balance(&recipients.get(i).unwrap()), expected);
+    }
+    assert_eq!(token.balance(&multisig_id), 500);
+}
+
+#[test]
+fn batch_payment_exceeding_balance_moves_nothing() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let mut payments = Vec::new(&env);
+    payments.push_back((first.clone(), 600i128));
+    payments.push_back((second.clone(), 600i128));
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::BatchPayment(token.address.clone(), payments),
+        &None,
+        &None,
+    );
+    let res = client.try_approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::InsufficientBalance)));
+
+    assert_eq!(token.balance(&first), 0);
+    assert_eq!(token.balance(&second), 0);
+    assert_eq!(token.balance(&multisig_id), 1_000);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+}
+
+#[test]
+fn batch_payment_rejects_invalid_lists_at_proposal() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let token = Address::generate(&env);
+    let mut payments = Vec::new(&env);
+    payments.push_back((Address::generate(&env), 100i128));
+    payments.push_back((Address::generate(&env), 0i128));
+
+    let res = client.try_propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::BatchPayment(token.clone(), payments),
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(MultisigError::InvalidAmount)));
+
+    let res = client.try_propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::BatchPayment(token, Vec::new(&env)),
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(MultisigError::InvalidBatch)));
+}
+
+// ==================== Token Approvals ====================
+
+#[test]
+fn token_approval_lets_spender_transfer_from_multisig() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    let hot_wallet = Address::generate(&env);
+    let payee = Address::generate(&env);
+    let live_until = env.ledger().sequence() + 1_000;
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::TokenApproval(token.address.clone(), hot_wallet.clone(), 300, live_until),
+        &None,
+        &None,
+    );
+    // Not approved until the threshold is met.
+    assert_eq!(token.allowance(&multisig_id, &hot_wallet), 0);
+
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(token.allowance(&multisig_id, &hot_wallet), 300);
+
+    token.transfer_from(&hot_wallet, &multisig_id, &payee, &200);
+    assert_eq!(token.balance(&payee), 200);
+    assert_eq!(token.balance(&multisig_id), 800);
+    assert_eq!(token.allowance(&multisig_id, &hot_wallet), 100);
+
+    // The allowance caps what the spender can pull.
+    let res = token.try_transfer_from(&hot_wallet, &multisig_id, &payee, &200);
+    assert!(res.is_err());
+    assert_eq!(token.balance(&multisig_id), 800);
+}
+
+#[test]
+fn token_approval_rejects_invalid_parameters() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let token = Address::generate(&env);
+    let res = client.try_propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::TokenApproval(token.clone(), Address::generate(&env), -1, 100),
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(MultisigError::InvalidAmount)));
+
+    let res = client.try_propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::TokenApproval(token, multisig_id, 100, 100),
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(MultisigError::InvalidSpender)));
+}
+
+// ==================== Pending Operations ====================
+
+fn pending_ids(client: &MultisigContractClient, start: u32, limit: u32) -> std::vec::Vec<u128> {
+    client
+        .get_pending_operations(&start, &limit)
+        .iter()
+        .map(|op| op.id)
+        .collect()
+}
+
+#[test]
+fn pending_list_tracks_execution_and_cancellation() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    let proposer = signers.get(0).unwrap();
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..5 {
+        ids.push(client.propose_operation(
+            &proposer,
+            &OperationKind::LargePayment(token.address.clone(), Address::generate(&env), 10),
+            &None,
+            &None,
+        ));
+    }
+    assert_eq!(client.get_operation_count(), 5);
+    assert_eq!(pending_ids(&client, 0, 10), ids);
+
+    // Execute the second, cancel the fourth.
+    client.approve_operation(&signers.get(1).unwrap(), &ids[1]);
+    client.cancel_operation(&proposer, &ids[3]);
+
+    assert_eq!(pending_ids(&client, 0, 10), [ids[0], ids[2], ids[4]]);
+    for op in client.get_pending_operations(&0, &10).iter() {
+        assert_eq!(op.status, OperationStatus::Pending);
+    }
+    // The counter covers every proposal, not only pending ones.
+    assert_eq!(client.get_operation_count(), 5);
+}
+
+#[test]
+fn pending_operations_paginate() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let proposer = signers.get(0).unwrap();
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..5 {
+        ids.push(client.propose_operation(
+            &proposer,
+            &OperationKind::AddSigner(Address::generate(&env)),
+            &None,
+            &None,
+        ));
+    }
+
+    assert_eq!(pending_ids(&client, 0, 2), &ids[0..2]);
+    assert_eq!(pending_ids(&client, 2, 2), &ids[2..4]);
+    assert_eq!(pending_ids(&client, 4, 2), &ids[4..5]);
+    assert!(pending_ids(&client, 5, 2).is_empty());
+    assert!(pending_ids(&client, 0, 0).is_empty());
+}
+
+fn status_ids(
+    client: &MultisigContractClient,
+    status: OperationStatus,
+    start: u32,
+    limit: u32,
+) -> std::vec::Vec<u128> {
+    client
+        .get_operations_by_status(&status, &start, &limit)
+        .iter()
+        .map(|op| op.id)
+        .collect()
+}
+
+#[test]
+fn operations_listed_by_status() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    let proposer = signers.get(0).unwrap();
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..6 {
+        ids.push(client.propose_operation(
+            &proposer,
+            &OperationKind::LargePayment(token.address.clone(), Address::generate(&env), 10),
+            &None,
+            &None,
+        ));
+    }
+
+    // Finalize out of proposal order: execute 4 then 1, cancel 2 and 5.
+    client.approve_operation(&signers.get(1).unwrap(), &ids[4]);
+    client.cancel_operation(&owner, &ids[2]);
+    client.approve_operation(&signers.get(2).unwrap(), &ids[1]);
+    client.cancel_operation(&proposer, &ids[5]);
+
+    assert_eq!(
+        status_ids(&client, OperationStatus::Pending, 0, 10),
+        [ids[0], ids[3]]
+    );
+    assert_eq!(
+        status_ids(&client, OperationStatus::Executed, 0, 10),
+        [ids[4], ids[1]]
+    );
+    assert_eq!(
+        status_ids(&client, OperationStatus::Cancelled, 0, 10),
+        [ids[2], ids[5]]
+    );
+    for op in client
+        .get_operations_by_status(&OperationStatus::Executed, &0, &10)
+        .iter()
+    {
+        assert_eq!(op.status, OperationStatus::Executed);
+    }
+
+    // Pagination and the page-size cap apply to every status.
+    assert_eq!(
+        status_ids(&client, OperationStatus::Cancelled, 1, 1),
+        [ids[5]]
+    );
+    assert!(status_ids(&client, OperationStatus::Executed, 2, 10).is_empty());
+    assert_eq!(
+        status_ids(&client, OperationStatus::Pending, 0, 1000).len(),
+        2
+    );
+}
+
+#[test]
+fn status_listing_reads_across_index_pages() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    let proposer = signers.get(0).unwrap();
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..105 {
+        let id = client.propose_operation(
+            &proposer,
+            &OperationKind::AddSigner(Address::generate(&env)),
+            &None,
+            &None,
+        );
+        client.cancel_operation(&owner, &id);
+        ids.push(id);
+    }
+
+    // Ids are indexed 100 per page; a read spanning the boundary joins both.
+    assert_eq!(
+        status_ids(&client, OperationStatus::Cancelled, 95, 10),
+        ids[95..105]
+    );
+    assert_eq!(
+        status_ids(&client, OperationStatus::Cancelled, 100, 50),
+        ids[100..105]
+    );
+    assert_eq!(
+        status_ids(&client, OperationStatus::Cancelled, 0, 1000),
+        ids[0..50]
+    );
+    assert!(status_ids(&client, OperationStatus::Cancelled, 105, 10).is_empty());
+}
+
+#[test]
+fn pending_listing_reads_across_index_pages() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    let proposer = signers.get(0).unwrap();
+    let mut ids = std::vec::Vec::new();
+    for _ in 0..230 {
+        ids.push(client.propose_operation(
+            &proposer,
+            &OperationKind::AddSigner(Address::generate(&env)),
+            &None,
+            &None,
+        ));
+    }
+
+    // Pending ids are indexed 100 per page by id; emptying a whole page
+    // drops it from the listing.
+    for id in ids.iter().filter(|id| (100..200).contains(*id)) {
+        client.cancel_operation(&owner, id);
+    }
+    let pending: std::vec::Vec<u128> = ids
+        .into_iter()
+        .filter(|id| !(100..200).contains(id))
+        .collect();
+    let total = pending.len() as u32;
+    assert!(total > 100);
+
+    assert_eq!(pending_ids(&client, 0, 1000), pending[0..50]);
+    assert_eq!(pending_ids(&client, 90, 20), pending[90..110]);
+    assert_eq!(
+        pending_ids(&client, total - 5, 50),
+        pending[pending.len() - 5..]
+    );
+    assert!(pending_ids(&client, total, 10).is_empty());
+    assert_eq!(
+        status_ids(&client, OperationStatus::Pending, 90, 20),
+        pending[90..110]
+    );
+}
+
+// ==================== Guardian Policy ====================
+
+fn payment_only_policy(env: &Env, max_payment: Option<i128>) -> GuardianPolicy {
+    let mut allowed_types = Vec::new(env);
+    allowed_types.push_back(OperationType::LargePayment);
+    GuardianPolicy {
+        max_payment,
+        allowed_types,
+        require_cosigner: false,
+    }
+}
+
+#[test]
+fn guardian_blocked_on_over_limit_payment() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    client.set_guardian_policy(&owner, &Some(payment_only_policy(&env, Some(100))));
+
+    let recipient = Address::generate(&env);
+    let big = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 200),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.try_emergency_queue(&guardian, &big),
+        Err(Ok(MultisigError::GuardianLimitExceeded))
+    );
+    assert_eq!(
+        client.get_operation(&big).unwrap().status,
+        OperationStatus::Pending
+    );
+
+    // Within the limit the guardian can still act.
+    let small = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+    client.emergency_queue(&guardian, &small);
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn guardian_blocked_on_excluded_operation_type() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, guardian) = setup_initialized(&env);
+
+    client.set_guardian_policy(&owner, &Some(payment_only_policy(&env, None)));
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ContractUpgrade(multisig_id, BytesN::from_array(&env, &[0u8; 32])),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.try_emergency_queue(&guardian, &op_id),
+        Err(Ok(MultisigError::GuardianTypeNotAllowed))
+    );
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+}
+
+#[test]
+fn guardian_policy_can_require_cosigner() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, guardian) = setup_initialized(&env);
+
+    // 3-of-3 so a second approval does not execute the operation by itself.
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(client.get_threshold(), 3);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    let mut policy = payment_only_policy(&env, None);
+    policy.require_cosigner = true;
+    client.set_guardian_policy(&owner, &Some(policy.clone()));
+    assert_eq!(client.get_guardian_policy(), Some(policy));
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), Address::generate(&env), 50),
+        &None,
+        &None,
+    );
+    // Only the creator's approval: rejected.
+    assert_eq!(
+        client.try_emergency_queue(&guardian, &op_id),
+        Err(Ok(MultisigError::CosignerRequired))
+    );
+
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    client.emergency_queue(&guardian, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+
+    // Only the owner configures the policy.
+    let res = client.try_set_guardian_policy(&signers.get(0).unwrap(), &None);
+    assert_eq!(res, Err(Ok(MultisigError::Unauthorized)));
+}
+
+// ==================== Guardian Rotation ====================
+
+#[test]
+fn rotated_guardian_replaces_old_one() {
+    let env = create_env();
+    let (_id, client, _owner, signers, old_guardian) = setup_initialized(&env);
+
+    let new_guardian = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::SetGuardian(Some(new_guardian.clone())),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(client.get_guardian(), Some(new_guardian.clone()));
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::AddSigner(Address::generate(&env)),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.try_emergency_queue(&old_guardian, &op_id),
+        Err(Ok(MultisigError::NotGuardian))
+    );
+
+    client.emergency_queue(&new_guardian, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+}
+
+#[test]
+fn cleared_guardian_disables_emergency_queue() {
+    let env = create_env();
+    let (_id, client, _owner, signers, guardian) = setup_initialized(&env);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::SetGuardian(None),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(client.get_guardian(), None);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::AddSigner(Address::generate(&env)),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.try_emergency_queue(&guardian, &op_id),
+        Err(Ok(MultisigError::NotGuardian))
+    );
+    for signer in signers.iter() {
+        assert_eq!(
+            client.try_emergency_queue(&signer, &op_id),
+            Err(Ok(MultisigError::NotGuardian))
+        );
+    }
+}
+
+#[test]
+fn guardian_can_renounce() {
+    let env = create_env();
+    let (_id, client, _owner, signers, guardian) = setup_initialized(&env);
+
+    // Only the guardian can renounce.
+    let res = client.try_renounce_guardian(&signers.get(0).unwrap());
+    assert_eq!(res, Err(Ok(MultisigError::NotGuardian)));
+
+    client.renounce_guardian(&guardian);
+    assert_eq!(client.get_guardian(), None);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::AddSigner(Address::generate(&env)),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.try_emergency_queue(&guardian, &op_id),
+        Err(Ok(MultisigError::NotGuardian))
+    );
+}
+
+// ==================== Emergency Challenge Window ====================
+
+#[test]
+fn challenged_emergency_queue_cannot_be_finalized() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, guardian) = setup_initialized(&env);
+    client.set_challenge_period(&owner, &3_600);
+    assert_eq!(client.get_challenge_period(), 3_600);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+
+    env.ledger().set_timestamp(1_000);
+    client.emergency_queue(&guardian, &op_id);
+    assert_eq!(
+        client
+            .get_operation(&op_id)
+            .unwrap()
+            .emergency_executable_at,
+        Some(4_600)
+    );
+    assert_eq!(
+        client.try_emergency_queue(&guardian, &op_id),
+        Err(Ok(MultisigError::EmergencyAlreadyQueued))
+    );
+    assert_eq!(
+        client.try_emergency_finalize(&guardian, &op_id),
+        Err(Ok(MultisigError::TimelockActive))
+    );
+
+    env.ledger().set_timestamp(4_599);
+    client.challenge_emergency(&signers.get(2).unwrap(), &op_id);
+    let op = client.get_operation(&op_id).unwrap();
+    assert_eq!(op.emergency_executable_at, None);
+    assert_eq!(op.status, OperationStatus::Pending);
+
+    env.ledger().set_timestamp(5_000);
+    assert_eq!(
+        client.try_emergency_finalize(&guardian, &op_id),
+        Err(Ok(MultisigError::NotEmergencyQueued))
+    );
+    assert_eq!(token.balance(&recipient), 0);
+}
+
+#[test]
+fn unchallenged_emergency_queue_finalizes_after_window() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, guardian) = setup_initialized(&env);
+    client.set_challenge_period(&owner, &3_600);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+
+    env.ledger().set_timestamp(1_000);
+    client.emergency_queue(&guardian, &op_id);
+
+    env.ledger().set_timestamp(4_600);
+    assert_eq!(
+        client.try_challenge_emergency(&signers.get(1).unwrap(), &op_id),
+        Err(Ok(MultisigError::ChallengeWindowClosed))
+    );
+    assert_eq!(
+        client.try_emergency_finalize(&signers.get(1).unwrap(), &op_id),
+        Err(Ok(MultisigError::NotGuardian))
+    );
+    client.emergency_finalize(&guardian, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert!(client.was_emergency_executed(&op_id));
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+// ==================== Duplicate Detection ====================
+
+#[test]
+fn duplicate_pending_operation_rejected() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let token = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let kind = OperationKind::LargePayment(token.clone(), recipient.clone(), 500);
+
+    let first = client.propose_operation(&signers.get(0).unwrap(), &kind, &None, &None);
+
+    // Same content from another signer is rejected while the first is pending.
+    let res = client.try_propose_operation(&signers.get(1).unwrap(), &kind, &None, &None);
+    assert_eq!(res, Err(Ok(MultisigError::DuplicateOperation)));
+
+    // A different amount is a different operation.
+    let other = client.propose_operation(
+        &signers.get(1).unwrap(),
+        &OperationKind::LargePayment(token, recipient, 501),
+        &None,
+        &None,
+    );
+    assert_ne!(first, other);
+}
+
+#[test]
+fn duplicate_allowed_after_cancellation_or_execution() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    let recipient = Address::generate(&env);
+    let kind = OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100);
+    let proposer = signers.get(0).unwrap();
+
+    let first = client.propose_operation(&proposer, &kind, &None, &None);
+    client.cancel_operation(&proposer, &first);
+
+    let second = client.propose_operation(&proposer, &kind, &None, &None);
+    assert_ne!(first, second);
+    client.approve_operation(&signers.get(1).unwrap(), &second);
+    assert_eq!(token.balance(&recipient), 100);
+
+    // Once executed, an intentional repeat payment can be proposed again.
+    let third = client.propose_operation(&proposer, &kind, &None, &None);
+    assert_eq!(
+        client.get_operation(&third).unwrap().status,
+        OperationStatus::Pending
+    );
+}
+
+// ==================== Execution Delay ====================
+
+#[test]
+fn zero_delay_executes_on_threshold() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    assert_eq!(client.get_execution_delay(), 0);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    let recipient = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    let op = client.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Executed);
+    assert_eq!(op.executable_at, None);
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn delayed_operation_executes_after_window() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    client.set_execution_delay(&owner, &3_600);
+    env.ledger().set_timestamp(1_000);
+
+    let recipient = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+    let anyone = Address::generate(&env);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &op_id),
+        Err(Ok(MultisigError::NotQueued))
+    );
+
+    // Threshold met: queued, not executed.
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    let op = client.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.executable_at, Some(4_600));
+    assert_eq!(token.balance(&recipient), 0);
+
+    env.ledger().set_timestamp(4_599);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &op_id),
+        Err(Ok(MultisigError::TimelockActive))
+    );
+
+    env.ledger().set_timestamp(4_600);
+    client.execute_operation(&anyone, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn queued_operation_rechecks_threshold_after_signer_expiry() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let contractor = signers.get(2).unwrap();
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    client.set_execution_delay(&owner, &3_600);
+    client.set_signer_expiry(&owner, &contractor, &Some(2_000));
+    env.ledger().set_timestamp(1_000);
+
+    let recipient = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &s1,
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+    client.approve_operation(&contractor, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().executable_at,
+        Some(4_600)
+    );
+
+    // The contractor's approval lapsed while the operation was queued.
+    env.ledger().set_timestamp(10_000);
+    let anyone = Address::generate(&env);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &op_id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+    assert_eq!(token.balance(&recipient), 0);
+
+    client.approve_operation(&s2, &op_id);
+    client.execute_operation(&anyone, &op_id);
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn owner_can_cancel_during_delay_window() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    client.set_execution_delay(&owner, &3_600);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::AddSigner(Address::generate(&env)),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert!(client
+        .get_operation(&op_id)
+        .unwrap()
+        .executable_at
+        .is_some());
+
+    client.cancel_operation(&owner, &op_id);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 3_600);
+    assert_eq!(
+        client.try_execute_operation(&owner, &op_id),
+        Err(Ok(MultisigError::OperationNotPending))
+    );
+    assert_eq!(client.get_signers().len(), 3);
+
+    // Only the owner configures the delay.
+    let res = client.try_set_execution_delay(&signers.get(0).unwrap(), &0);
+    assert_eq!(res, Err(Ok(MultisigError::Unauthorized)));
+}
+
+// ==================== Scheduled Execution ====================
+
+#[test]
+fn approved_payment_waits_for_scheduled_time() {
+    let env = create_env();
+    let (multisig_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+
+    env.ledger().set_timestamp(1_000);
+    let payday = 50_000;
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &Some(payday),
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    // Threshold met early: queued for payday rather than executed.
+    let op = client.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.execute_after, Some(payday));
+    assert_eq!(op.executable_at, Some(payday));
+    assert_eq!(token.balance(&recipient), 0);
+
+    let anyone = Address::generate(&env);
+    env.ledger().set_timestamp(payday - 1);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &op_id),
+        Err(Ok(MultisigError::TimelockActive))
+    );
+
+    env.ledger().set_timestamp(payday);
+    client.execute_operation(&anyone, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn past_scheduled_time_executes_on_threshold() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    env.ledger().set_timestamp(1_000);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &Some(2_000),
+    );
+    env.ledger().set_timestamp(2_000);
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(client.get_threshold(), 3);
+}
+
+#[test]
+fn scheduled_time_past_expiry_rejected() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    client.set_proposal_lifetime(&owner, &86_400);
+
+    env.ledger().set_timestamp(1_000);
+    let proposer = signers.get(0).unwrap();
+    assert_eq!(
+        client.try_propose_operation(
+            &proposer,
+            &OperationKind::ChangeThreshold(3),
+            &None,
+            &Some(87_400),
+        ),
+        Err(Ok(MultisigError::ExecuteAfterPastExpiry))
+    );
+
+    let op_id = client.propose_operation(
+        &proposer,
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &Some(87_399),
+    );
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().execute_after,
+        Some(87_399)
+    );
+}
+
+#[test]
+fn scheduled_time_combines_with_execution_delay() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    client.set_execution_delay(&owner, &3_600);
+
+    env.ledger().set_timestamp(1_000);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &Some(2_000),
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    // The later of the delay and the scheduled time wins.
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().executable_at,
+        Some(4_600)
+    );
+}
+
+// ==================== Weighted Signers ====================
+
+fn setup_weighted(env: &Env) -> (MultisigContractClient<'static>, Address, Address, Address) {
+    let (_id, client) = register_contract(env);
+    let owner = Address::generate(env);
+    let ceo = Address::generate(env);
+    let director = Address::generate(env);
+    let manager = Address::generate(env);
+
+    let mut signers = Vec::new(env);
+    signers.push_back(ceo.clone());
+    signers.push_back(director.clone());
+    signers.push_back(manager.clone());
+    let mut weights = Vec::new(env);
+    weights.push_back(2u32);
+    weights.push_back(1u32);
+    weights.push_back(1u32);
+
+    client.initialize(&owner, &signers, &2u32, &None, &Some(weights));
+    (client, ceo, director, manager)
+}
+
+#[test]
+fn weight_two_signer_alone_meets_threshold() {
+    let env = create_env();
+    let (client, ceo, _director, _manager) = setup_weighted(&env);
+
+    let op_id = client.propose_operation(
+        &ceo,
+        &OperationKind::AddSigner(Address::generate(&env)),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+}
+
+#[test]
+fn weight_one_signer_alone_does_not_meet_threshold() {
+    let env = create_env();
+    let (client, ceo, director, manager) = setup_weighted(&env);
+
+    let signers = client.get_signers();
+    assert_eq!(signers.get(0).unwrap(), (ceo, 2));
+    assert_eq!(signers.get(1).unwrap(), (director.clone(), 1));
+
+    let op_id = client.propose_operation(
+        &director,
+        &OperationKind::AddSigner(Address::generate(&env)),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+
+    // Two weight-1 signers together reach the threshold.
+    client.approve_operation(&manager, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+}
+
+#[test]
+fn weighted_initialize_validates_weights() {
+    let env = create_env();
+    let (_id, client) = register_contract(&env);
+    let owner = Address::generate(&env);
+    let mut signers = Vec::new(&env);
+    signers.push_back(Address::generate(&env));
+    signers.push_back(Address::generate(&env));
+
+    let mut short = Vec::new(&env);
+    short.push_back(2u32);
+    let res = client.try_initialize(&owner, &signers, &1u32, &None, &Some(short));
+    assert_eq!(res, Err(Ok(MultisigError::InvalidWeight)));
+
+    let mut zero = Vec::new(&env);
+    zero.push_back(2u32);
+    zero.push_back(0u32);
+    let res = client.try_initialize(&owner, &signers, &1u32, &None, &Some(zero));
+    assert_eq!(res, Err(Ok(MultisigError::InvalidWeight)));
+
+    // Threshold is bounded by total weight (3), not the signer count (2).
+    let mut weights = Vec::new(&env);
+    weights.push_back(2u32);
+    weights.push_back(1u32);
+    let res = client.try_initialize(&owner, &signers, &4u32, &None, &Some(weights.clone()));
+    assert_eq!(res, Err(Ok(MultisigError::InvalidThreshold)));
+    client.initialize(&owner, &signers, &3u32, &None, &Some(weights));
+    assert_eq!(client.get_threshold(), 3);
+}
+
+// ==================== Signer Expiry ====================
+
+#[test]
+fn expired_signer_approval_stops_counting() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let contractor = signers.get(2).unwrap();
+
+    env.ledger().set_timestamp(1_000);
+    client.set_signer_expiry(&owner, &contractor, &Some(5_000));
+    assert_eq!(client.get_signer_expiry(&contractor), Some(5_000));
+    client.set_auto_execute(&owner, &false);
+
+    let op_id = client.propose_operation(&s1, &OperationKind::ChangeThreshold(1), &None, &None);
+    client.approve_operation(&contractor, &op_id);
+
+    // Past the expiry the contractor's approval no longer meets the threshold.
+    env.ledger().set_timestamp(5_001);
+    let anyone = Address::generate(&env);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &op_id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+    assert_eq!(
+        client.try_approve_operation(&contractor, &op_id),
+        Err(Ok(MultisigError::NotSigner))
+    );
+    assert_eq!(
+        client.try_propose_operation(
+            &contractor,
+            &OperationKind::ChangeThreshold(1),
+            &None,
+            &None
+        ),
+        Err(Ok(MultisigError::NotSigner))
+    );
+
+    client.approve_operation(&s2, &op_id);
+    client.execute_operation(&anyone, &op_id);
+    assert_eq!(client.get_threshold(), 1);
+}
+
+#[test]
+fn expired_signer_weight_does_not_count_toward_threshold_checks() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+    let s2 = signers.get(1).unwrap();
+    let contractor = signers.get(2).unwrap();
+    client.set_auto_execute(&owner, &false);
+
+    // Queued while all three signers are active.
+    let raise_id = client.propose_operation(&s1, &OperationKind::ChangeThreshold(3), &None, &None);
+    client.approve_operation(&s2, &raise_id);
+    let remove_id =
+        client.propose_operation(&s1, &OperationKind::RemoveSigner(s2.clone()), &None, &None);
+    client.approve_operation(&s2, &remove_id);
+
+    env.ledger().set_timestamp(1_000);
+    client.set_signer_expiry(&owner, &contractor, &Some(500));
+
+    // Only two signers can still approve, so a threshold of 3 is unreachable.
+    assert_eq!(
+        client.try_propose_operation(&s1, &OperationKind::ChangeThreshold(3), &None, &None),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+    let anyone = Address::generate(&env);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &raise_id),
+        Err(Ok(MultisigError::InvalidThreshold))
+    );
+
+    // Removing s2 would leave only s1's weight against a threshold of 2.
+    assert_eq!(
+        client.try_execute_operation(&anyone, &remove_id),
+        Err(Ok(MultisigError::SignerCountBelowThreshold))
+    );
+    assert_eq!(client.get_threshold(), 2);
+    assert_eq!(client.get_signers().len(), 3);
+}
+
+#[test]
+fn signer_expiry_is_owner_only_and_clearable() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let s1 = signers.get(0).unwrap();
+
+    assert_eq!(
+        client.try_set_signer_expiry(&s1, &s1, &Some(10)),
+        Err(Ok(MultisigError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_signer_expiry(&owner, &Address::generate(&env), &Some(10)),
+        Err(Ok(MultisigError::NotSigner))
+    );
+
+    env.ledger().set_timestamp(100);
+    client.set_signer_expiry(&owner, &s1, &Some(10));
+    assert_eq!(
+        client.try_propose_operation(&s1, &OperationKind::ChangeThreshold(3), &None, &None),
+        Err(Ok(MultisigError::NotSigner))
+    );
+
+    client.set_signer_expiry(&owner, &s1, &None);
+    assert_eq!(client.get_signer_expiry(&s1), None);
+    client.propose_operation(&s1, &OperationKind::ChangeThreshold(3), &None, &None);
+}
+
+// ==================== Direct Payments ====================
+
+#[test]
+fn direct_payments_respect_daily_limit() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    client.set_daily_limit(&owner, &token.address, &100);
+    env.ledger().set_timestamp(86_400 * 10 + 100);
+
+    let signer = signers.get(0).unwrap();
+    let recipient = Address::generate(&env);
+    client.direct_payment(&signer, &token.address, &recipient, &40);
+    client.direct_payment(&signers.get(1).unwrap(), &token.address, &recipient, &40);
+    assert_eq!(token.balance(&recipient), 80);
+    assert_eq!(client.get_daily_spent(&token.address), 80);
+
+    let res = client.try_direct_payment(&signer, &token.address, &recipient, &40);
+    assert_eq!(res, Err(Ok(MultisigError::DailyLimitExceeded)));
+    assert_eq!(token.balance(&recipient), 80);
+
+    // New day bucket: the counter starts from zero.
+    env.ledger().set_timestamp(86_400 * 11);
+    assert_eq!(client.get_daily_spent(&token.address), 0);
+    client.direct_payment(&signer, &token.address, &recipient, &40);
+    assert_eq!(token.balance(&recipient), 120);
+
+    // The token keeps one `(day, spent)` entry that the new day overwrote.
+    let bucket: (u64, i128) = env.as_contract(&multisig_id, || {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(&env, "DailySpent"), token.address.clone()))
+            .unwrap()
+    });
+    assert_eq!(bucket, (11, 40));
+}
+
+#[test]
+fn direct_payment_requires_signer_and_limit() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+    let recipient = Address::generate(&env);
+
+    // No limit configured: direct payments are disabled.
+    let res = client.try_direct_payment(&signers.get(0).unwrap(), &token.address, &recipient, &1);
+    assert_eq!(res, Err(Ok(MultisigError::DailyLimitExceeded)));
+
+    client.set_daily_limit(&owner, &token.address, &100);
+    let outsider = Address::generate(&env);
+    let res = client.try_direct_payment(&outsider, &token.address, &recipient, &1);
+    assert_eq!(res, Err(Ok(MultisigError::NotSigner)));
+
+    let res = client.try_set_daily_limit(&signers.get(0).unwrap(), &token.address, &1_000);
+    assert_eq!(res, Err(Ok(MultisigError::Unauthorized)));
+    assert_eq!(client.get_daily_limit(&token.address), 100);
+}
+
+// ==================== Proposal Bonds ====================
+
+#[test]
+fn proposal_bond_refunded_on_execution() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let proposer = signers.get(0).unwrap();
+
+    let admin = Address::generate(&env);
+    let bond_token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &bond_token.address).mint(&proposer, &100i128);
+    client.set_proposal_bond(&owner, &bond_token.address, &40);
+
+    let op_id =
+        client.propose_operation(&proposer, &OperationKind::ChangeThreshold(3), &None, &None);
+    assert_eq!(bond_token.balance(&proposer), 60);
+    assert_eq!(bond_token.balance(&multisig_id), 40);
+
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(bond_token.balance(&proposer), 100);
+    assert_eq!(bond_token.balance(&multisig_id), 0);
+}
+
+#[test]
+fn proposal_bond_refunded_on_owner_cancel() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let proposer = signers.get(0).unwrap();
+
+    let admin = Address::generate(&env);
+    let bond_token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &bond_token.address).mint(&proposer, &100i128);
+    client.set_proposal_bond(&owner, &bond_token.address, &40);
+
+    let op_id =
+        client.propose_operation(&proposer, &OperationKind::ChangeThreshold(3), &None, &None);
+    client.cancel_operation(&owner, &op_id);
+    assert_eq!(bond_token.balance(&proposer), 100);
+    assert_eq!(bond_token.balance(&multisig_id), 0);
+}
+
+#[test]
+fn proposal_bond_forfeited_on_creator_cancel() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let proposer = signers.get(0).unwrap();
+
+    let admin = Address::generate(&env);
+    let bond_token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &bond_token.address).mint(&proposer, &100i128);
+    client.set_proposal_bond(&owner, &bond_token.address, &40);
+
+    let op_id =
+        client.propose_operation(&proposer, &OperationKind::ChangeThreshold(3), &None, &None);
+    client.cancel_operation(&proposer, &op_id);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Cancelled
+    );
+    assert_eq!(bond_token.balance(&proposer), 60);
+    assert_eq!(bond_token.balance(&multisig_id), 40);
+}
+
+#[test]
+fn proposal_bond_refunded_when_owner_cancels_own_proposal() {
+    let env = create_env();
+    let (multisig_id, client) = register_contract(&env);
+    let owner = Address::generate(&env);
+    let mut signers = Vec::new(&env);
+    signers.push_back(owner.clone());
+    signers.push_back(Address::generate(&env));
+    client.initialize(&owner, &signers, &2u32, &None, &None);
+
+    let admin = Address::generate(&env);
+    let bond_token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &bond_token.address).mint(&owner, &100i128);
+    client.set_proposal_bond(&owner, &bond_token.address, &40);
+
+    let op_id = client.propose_operation(&owner, &OperationKind::ChangeThreshold(1), &None, &None);
+    assert_eq!(bond_token.balance(&owner), 60);
+    client.cancel_operation(&owner, &op_id);
+    assert_eq!(bond_token.balance(&owner), 100);
+    assert_eq!(bond_token.balance(&multisig_id), 0);
+}
+
+#[test]
+fn proposal_bond_forfeited_on_expiry() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let proposer = signers.get(0).unwrap();
+
+    let admin = Address::generate(&env);
+    let bond_token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &bond_token.address).mint(&proposer, &100i128);
+    client.set_proposal_bond(&owner, &bond_token.address, &40);
+    client.set_proposal_lifetime(&owner, &86_400);
+
+    env.ledger().set_timestamp(1_000);
+    let op_id =
+        client.propose_operation(&proposer, &OperationKind::ChangeThreshold(3), &None, &None);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().expires_at,
+        Some(87_400)
+    );
+    assert_eq!(
+        client.try_expire_operation(&op_id),
+        Err(Ok(MultisigError::NotExpired))
+    );
+
+    env.ledger().set_timestamp(87_400);
+    assert_eq!(
+        client.try_approve_operation(&signers.get(1).unwrap(), &op_id),
+        Err(Ok(MultisigError::OperationExpired))
+    );
+    client.expire_operation(&op_id);
+
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Expired
+    );
+    assert_eq!(bond_token.balance(&proposer), 60);
+    assert_eq!(bond_token.balance(&multisig_id), 40);
+    assert_eq!(
+        client
+            .get_operations_by_status(&OperationStatus::Expired, &0, &10)
+            .len(),
+        1
+    );
+    assert!(client.get_pending_operations(&0, &10).is_empty());
+}
+
+#[test]
+fn held_proposal_bonds_cannot_be_paid_out() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    let proposer = signers.get(0).unwrap();
+    let recipient = Address::generate(&env);
+
+    let admin = Address::generate(&env);
+    let bond_token = create_token_contract(&env, &admin);
+    let minter = StellarAssetClient::new(&env, &bond_token.address);
+    minter.mint(&proposer, &100i128);
+    minter.mint(&multisig_id, &100i128);
+    client.set_proposal_bond(&owner, &bond_token.address, &40);
+    client.set_daily_limit(&owner, &bond_token.address, &1_000);
+
+    let other_op =
+        client.propose_operation(&proposer, &OperationKind::ChangeThreshold(3), &None, &None);
+    let payment_op = client.propose_operation(
+        &proposer,
+        &OperationKind::LargePayment(bond_token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_held_bonds(&bond_token.address), 80);
+    assert_eq!(bond_token.balance(&multisig_id), 180);
+
+    // Only the 100 outside the held bonds can be spent.
+    assert_eq!(
+        client.try_direct_payment(&proposer, &bond_token.address, &recipient, &101),
+        Err(Ok(MultisigError::InsufficientBalance))
+    );
+    client.direct_payment(&proposer, &bond_token.address, &recipient, &30);
+    assert_eq!(
+        client.try_approve_operation(&signers.get(1).unwrap(), &payment_op),
+        Err(Ok(MultisigError::InsufficientBalance))
+    );
+
+    // A forfeited bond becomes part of the treasury and can be spent.
+    client.cancel_operation(&proposer, &other_op);
+    assert_eq!(client.get_held_bonds(&bond_token.address), 40);
+    client.approve_operation(&signers.get(1).unwrap(), &payment_op);
+    assert_eq!(
+        client.get_operation(&payment_op).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(client.get_held_bonds(&bond_token.address), 0);
+    assert_eq!(bond_token.balance(&recipient), 130);
+    assert_eq!(bond_token.balance(&proposer), 60);
+    assert_eq!(bond_token.balance(&multisig_id), 10);
+}
+
+#[test]
+fn zero_bond_disables_bonds() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let bond_token = create_token_contract(&env, &admin);
+    client.set_proposal_bond(&owner, &bond_token.address, &40);
+    assert!(client.get_proposal_bond().is_some());
+    client.set_proposal_bond(&owner, &bond_token.address, &0);
+    assert_eq!(client.get_proposal_bond(), None);
+
+    // The proposer holds no bond tokens, so this only succeeds without a bond.
+    client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &None,
+    );
+}
+
+// ==================== Treasury Balances ====================
+
+#[test]
+fn treasury_balances_report_registered_tokens() {
+    let env = create_env();
+    let (multisig_id, client, owner, _signers, _guardian) = setup_initialized(&env);
+
+    let admin = Address::generate(&env);
+    let usdc = create_token_contract(&env, &admin);
+    let eurc = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &usdc.address).mint(&multisig_id, &1_500i128);
+    StellarAssetClient::new(&env, &eurc.address).mint(&multisig_id, &250i128);
+
+    assert!(client.get_treasury_balances().is_empty());
+    client.register_treasury_token(&owner, &usdc.address);
+    client.register_treasury_token(&owner, &eurc.address);
+    assert_eq!(
+        client.try_register_treasury_token(&owner, &usdc.address),
+        Err(Ok(MultisigError::TokenAlreadyRegistered))
+    );
+
+    assert_eq!(
+        client.get_treasury_balances(),
+        Vec::from_array(
+            &env,
+            [(usdc.address.clone(), 1_500), (eurc.address.clone(), 250)]
+        )
+    );
+
+    client.deregister_treasury_token(&owner, &usdc.address);
+    assert_eq!(
+        client.get_treasury_balances(),
+        Vec::from_array(&env, [(eurc.address.clone(), 250)])
+    );
+    assert_eq!(
+        client.try_deregister_treasury_token(&owner, &usdc.address),
+        Err(Ok(MultisigError::TokenNotRegistered))
+    );
+}
+
+#[test]
+fn treasury_token_list_is_capped_and_owner_only() {
+    let env = create_env();
+    let (_id, client, owner, signers, _guardian) = setup_initialized(&env);
+
+    assert_eq!(
+        client.try_register_treasury_token(&signers.get(0).unwrap(), &Address::generate(&env)),
+        Err(Ok(MultisigError::Unauthorized))
+    );
+    for _ in 0..20 {
+        client.register_treasury_token(&owner, &Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_register_treasury_token(&owner, &Address::generate(&env)),
+        Err(Ok(MultisigError::TreasuryTokenLimit))
+    );
+    assert_eq!(client.get_treasury_tokens().len(), 20);
+}
+
+// ==================== Configuration Events ====================
+
+#[test]
+fn initialize_emits_config_event() {
+    let env = create_env();
+    let (id, client, owner, _signers, _guardian) = setup_initialized(&env);
+
+    let events = env.events().all();
+    let (contract, _topics, data) = events.last().unwrap();
+    assert_eq!(contract, id);
+    let event: MultisigInitializedEvent = data.into_val(&env);
+    assert_eq!(
+        event,
+        MultisigInitializedEvent {
+            owner,
+            signer_count: 3,
+            threshold: 2,
+        }
+    );
+    assert_eq!(client.get_threshold(), 2);
+}
+
+#[test]
+fn threshold_change_emits_config_event() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &None,
+    );
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    // threshold_changed precedes the generic operation_executed event.
+    let events = env.events().all();
+    let event: ThresholdChangedEvent = events.get(events.len() - 2).unwrap().2.into_val(&env);
+    assert_eq!(
+        event,
+        ThresholdChangedEvent {
+            operation_id: op_id,
+            old_threshold: 2,
+            new_threshold: 3,
+        }
+    );
+}
+
+#[test]
+fn owner_settings_emit_config_events() {
+    let env = create_env();
+    let (_id, client, owner, _signers, _guardian) = setup_initialized(&env);
+
+    client.set_execution_delay(&owner, &600);
+    let event: ExecutionDelayChangedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(
+        event,
+        ExecutionDelayChangedEvent {
+            old_delay: 0,
+            new_delay: 600,
+        }
+    );
+
+    let token = Address::generate(&env);
+    client.set_daily_limit(&owner, &token, &250);
+    let event: DailyLimitChangedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(event, DailyLimitChangedEvent { token, limit: 250 });
+}
+
+// ==================== Manual Execution ====================
+
+#[test]
+fn manual_execution_when_auto_execute_disabled() {
+    let env = create_env();
+    let (multisig_id, client, owner, signers, _guardian) = setup_initialized(&env);
+    assert!(client.get_auto_execute());
+
+    let admin = Address::generate(&env);
+    let token = create_token_contract(&env, &admin);
+    StellarAssetClient::new(&env, &token.address).mint(&multisig_id, &1_000i128);
+
+    client.set_auto_execute(&owner, &false);
+
+    let recipient = Address::generate(&env);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100),
+        &None,
+        &None,
+    );
+    let anyone = Address::generate(&env);
+    assert_eq!(
+        client.try_execute_operation(&anyone, &op_id),
+        Err(Ok(MultisigError::ThresholdNotMet))
+    );
+
+    // Threshold met, but nothing happens until someone executes.
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+    let op = client.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.execution_result, None);
+    assert_eq!(token.balance(&recipient), 0);
+
+    client.execute_operation(&anyone, &op_id);
+    let op = client.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Executed);
+    assert_eq!(op.execution_result, Some(true));
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+// ==================== Memos ====================
+
+#[test]
+fn memo_round_trips_through_storage_and_event() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let memo = Bytes::from_slice(&env, b"invoice #2024-117");
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &Some(memo.clone()),
+        &None,
+    );
+    let event: OperationProposedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(event.memo, Some(memo.clone()));
+    assert_eq!(client.get_operation_memo(&op_id), Some(memo));
+
+    let plain = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(1),
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_operation_memo(&plain), None);
+}
+
+#[test]
+fn oversized_memo_rejected() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_initialized(&env);
+
+    let memo = Bytes::from_slice(&env, &[7u8; 129]);
+    let res = client.try_propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &Some(memo),
+        &None,
+    );
+    assert_eq!(res, Err(Ok(MultisigError::MemoTooLong)));
+
+    let memo = Bytes::from_slice(&env, &[7u8; 128]);
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &Some(memo.clone()),
+        &None,
+    );
+    assert_eq!(client.get_operation_memo(&op_id), Some(memo));
+}
+
+// ==================== Large Signer Sets ====================
+
+#[test]
+fn fifteen_signers_approve_same_operation() {
+    let env = create_env();
+    let (_id, client) = register_contract(&env);
+    let owner = Address::generate(&env);
+    let mut signers = Vec::new(&env);
+    for _ in 0..15 {
+        signers.push_back(Address::generate(&env));
+    }
+    client.initialize(&owner, &signers, &15u32, &None, &None);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(10),
+        &None,
+        &None,
+    );
+    for i in 1..14 {
+        client.approve_operation(&signers.get(i).unwrap(), &op_id);
+    }
+    // A repeated approval is not counted twice.
+    client.approve_operation(&signers.get(3).unwrap(), &op_id);
+    assert_eq!(client.get_approvals(&op_id).len(), 14);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+
+    client.approve_operation(&signers.get(14).unwrap(), &op_id);
+    assert_eq!(client.get_approvals(&op_id), signers);
+    assert_eq!(
+        client.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(client.get_threshold(), 10);
+}
+
+// ==================== Storage TTL ====================
+
+/// Remaining TTL of a persistent entry. Keys are given in their encoded form,
+/// e.g. `(Symbol("Operation"), id)` for `StorageKey::Operation(id)`.
+fn persistent_ttl<K: IntoVal<Env, soroban_sdk::Val>>(env: &Env, contract: &Address, key: K) -> u32 {
+    env.as_contract(contract, || env.storage().persistent().get_ttl(&key))
+}
+
+#[test]
+fn approve_extends_state_ttl() {
+    let env = create_env();
+    let (id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let start = env.ledger().sequence();
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &None,
+    );
+    let op_key = (Symbol::new(&env, "Operation"), op_id);
+    assert_eq!(persistent_ttl(&env, &id, op_key.clone()), TTL_MAX_LEDGERS);
+
+    // Drop below the extension threshold.
+    env.ledger()
+        .set_sequence_number(start + TTL_MAX_LEDGERS - TTL_MIN_LEDGERS + 1);
+    assert_eq!(
+        persistent_ttl(&env, &id, op_key.clone()),
+        TTL_MIN_LEDGERS - 1
+    );
+
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    assert_eq!(persistent_ttl(&env, &id, op_key), TTL_MAX_LEDGERS);
+    assert_eq!(
+        persistent_ttl(&env, &id, (Symbol::new(&env, "Approvals"), op_id)),
+        TTL_MAX_LEDGERS
+    );
+    for key in ["Signers", "Threshold", "Owner"] {
+        assert_eq!(
+            persistent_ttl(&env, &id, (Symbol::new(&env, key),)),
+            TTL_MAX_LEDGERS
+        );
+    }
+    assert_eq!(
+        env.as_contract(&id, || env.storage().instance().get_ttl()),
+        TTL_MAX_LEDGERS
+    );
+}
+
+#[test]
+fn bump_state_extends_idle_wallet() {
+    let env = create_env();
+    let (id, client, _owner, signers, _guardian) = setup_initialized(&env);
+    let start = env.ledger().sequence();
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ChangeThreshold(3),
+        &None,
+        &None,
+    );
+
+    env.ledger()
+        .set_sequence_number(start + TTL_MAX_LEDGERS - TTL_MIN_LEDGERS + 1);
+    client.bump_state();
+
+    assert_eq!(
+        persistent_ttl(&env, &id, (Symbol::new(&env, "Operation"), op_id)),
+        TTL_MAX_LEDGERS
+    );
+    assert_eq!(
+        persistent_ttl(&env, &id, (Symbol::new(&env, "Signers"),)),
+        TTL_MAX_LEDGERS
+    );
+    assert_eq!(
+        persistent_ttl(
+            &env,
+            &id,
+            (
+                Symbol::new(&env, "Approval"),
+                op_id,
+                signers.get(0).unwrap()
+            )
+        ),
+        TTL_MAX_LEDGERS
+    );
 }
@@ -1,12 +1,16 @@
 #![cfg(test)]
+#![allow(deprecated)]
 
 use soroban_sdk::{
+    contract, contractimpl, symbol_short,
     testutils::Address as _,
     token::{Client as TokenClient, StellarAssetClient},
     Address, BytesN, Env, Vec,
 };
 
-use multisig::{MultisigContract, MultisigContractClient, OperationKind, OperationStatus};
+use multisig::{
+    MultisigContract, MultisigContractClient, MultisigError, OperationKind, OperationStatus,
+};
 
 fn create_env() -> Env {
     let env = Env::default();
@@ -21,6 +25,23 @@ fn register_contract(env: &Env) -> (Address, MultisigContractClient<'static>) {
     (id, client)
 }
 
+/// Minimal external contract exposing the conventional `upgrade` entrypoint.
+#[contract]
+pub struct UpgradeableMock;
+
+#[contractimpl]
+impl UpgradeableMock {
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("wasm"), &new_wasm_hash);
+    }
+
+    pub fn upgraded_to(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&symbol_short!("wasm"))
+    }
+}
+
 fn create_token_contract<'a>(env: &Env, admin: &Address) -> TokenClient<'a> {
     let token_addr = env.register_stellar_asset_contract(admin.clone());
     TokenClient::new(env, &token_addr)
@@ -47,7 +68,7 @@ fn setup_2of3(
     signers.push_back(s3.clone());
 
     let guardian = Address::generate(env);
-    client.initialize(&owner, &signers, &2u32, &Some(guardian.clone()));
+    client.initialize(&owner, &signers, &2u32, &Some(guardian.clone()), &None);
     (id, client, owner, signers, guardian)
 }
 
@@ -68,7 +89,7 @@ fn setup_1of1(
     signers.push_back(s1.clone());
 
     let guardian = Address::generate(env);
-    client.initialize(&owner, &signers, &1u32, &Some(guardian.clone()));
+    client.initialize(&owner, &signers, &1u32, &Some(guardian.clone()), &None);
     (id, client, owner, signers, guardian)
 }
 
@@ -93,7 +114,7 @@ fn setup_3of3(
     signers.push_back(s3.clone());
 
     let guardian = Address::generate(env);
-    client.initialize(&owner, &signers, &3u32, &Some(guardian.clone()));
+    client.initialize(&owner, &signers, &3u32, &Some(guardian.clone()), &None);
     (id, client, owner, signers, guardian)
 }
 
@@ -113,6 +134,8 @@ fn one_of_one_auto_executes_on_propose() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
     );
 
     // Should auto-execute since threshold is 1 and proposer auto-approves
@@ -137,6 +160,8 @@ fn three_of_three_requires_all_approvals() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
     );
 
     // 1 approval (proposer) - not enough
@@ -165,6 +190,8 @@ fn duplicate_approval_is_ignored() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::DisputeResolution(Address::generate(&env), 1u128, 10, 0),
+        &None,
+        &None,
     );
 
     // Same signer approves twice
@@ -191,8 +218,10 @@ fn non_signer_cannot_propose() {
     let res = client.try_propose_operation(
         &non_signer,
         &OperationKind::DisputeResolution(Address::generate(&env), 1u128, 10, 0),
+        &None,
+        &None,
     );
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(MultisigError::NotSigner)));
 }
 
 #[test]
@@ -203,11 +232,13 @@ fn non_signer_cannot_approve() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::DisputeResolution(Address::generate(&env), 1u128, 10, 0),
+        &None,
+        &None,
     );
 
     let non_signer = Address::generate(&env);
     let res = client.try_approve_operation(&non_signer, &op_id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(MultisigError::NotSigner)));
 }
 
 // ==================== Already-Executed Rejection ====================
@@ -226,6 +257,8 @@ fn cannot_approve_already_executed_operation() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
     );
 
     // Execute by reaching threshold
@@ -233,8 +266,9 @@ fn cannot_approve_already_executed_operation() {
     let op = client.get_operation(&op_id).unwrap();
     assert_eq!(op.status, OperationStatus::Executed);
 
-    // Third signer tries to approve - should be silently ignored (not pending)
-    client.approve_operation(&signers.get(2).unwrap(), &op_id);
+    // Third signer tries to approve - rejected as the operation is no longer pending
+    let res = client.try_approve_operation(&signers.get(2).unwrap(), &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::OperationNotPending)));
     let approvals = client.get_approvals(&op_id);
     assert_eq!(approvals.len(), 2); // Still only 2 approvals
 }
@@ -253,30 +287,34 @@ fn cannot_cancel_already_executed_operation() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
     );
 
     client.approve_operation(&signers.get(1).unwrap(), &op_id);
 
     // Owner tries to cancel executed operation
     let res = client.try_cancel_operation(&owner, &op_id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(MultisigError::OperationNotPending)));
 }
 
 // ==================== Guardian-Only Rescue ====================
 
 #[test]
-fn non_guardian_cannot_emergency_execute() {
+fn non_guardian_cannot_emergency_queue() {
     let env = create_env();
     let (_id, client, _owner, signers, _guardian) = setup_2of3(&env);
 
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::DisputeResolution(Address::generate(&env), 1u128, 10, 0),
+        &None,
+        &None,
     );
 
     let fake_guardian = Address::generate(&env);
-    let res = client.try_emergency_execute(&fake_guardian, &op_id);
-    assert!(res.is_err());
+    let res = client.try_emergency_queue(&fake_guardian, &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::NotGuardian)));
 }
 
 #[test]
@@ -293,14 +331,16 @@ fn guardian_cannot_execute_already_executed() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
     );
 
     // Execute normally
     client.approve_operation(&signers.get(1).unwrap(), &op_id);
 
     // Guardian tries emergency execute on already executed op
-    let res = client.try_emergency_execute(&guardian, &op_id);
-    assert!(res.is_err());
+    let res = client.try_emergency_queue(&guardian, &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::OperationNotPending)));
 }
 
 #[test]
@@ -311,14 +351,16 @@ fn guardian_cannot_execute_cancelled_operation() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::DisputeResolution(Address::generate(&env), 1u128, 10, 0),
+        &None,
+        &None,
     );
 
     // Cancel the operation
     client.cancel_operation(&signers.get(0).unwrap(), &op_id);
 
     // Guardian tries emergency execute
-    let res = client.try_emergency_execute(&guardian, &op_id);
-    assert!(res.is_err());
+    let res = client.try_emergency_queue(&guardian, &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::OperationNotPending)));
 }
 
 // ==================== Security: Threshold Changes Mid-Flight ====================
@@ -338,6 +380,8 @@ fn approve_with_threshold_higher_than_current_still_counts() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 100i128),
+        &None,
+        &None,
     );
 
     // Approve (1 of 2)
@@ -364,11 +408,15 @@ fn multiple_operations_independent() {
     let op1 = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), r1.clone(), 100i128),
+        &None,
+        &None,
     );
 
     let op2 = client.propose_operation(
         &signers.get(1).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), r2.clone(), 200i128),
+        &None,
+        &None,
     );
 
     // Only approve op1 (threshold reached)
@@ -399,11 +447,13 @@ fn large_payment_rejects_zero_amount() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::LargePayment(token.address.clone(), recipient.clone(), 0i128),
+        &None,
+        &None,
     );
 
     // Second approval triggers execution which should fail
     let res = client.try_approve_operation(&signers.get(1).unwrap(), &op_id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(MultisigError::InvalidAmount)));
 }
 
 // ==================== Duplicate Signer Rejection ====================
@@ -419,8 +469,8 @@ fn initialize_rejects_duplicate_signers() {
     signers.push_back(s1.clone());
     signers.push_back(s1.clone()); // duplicate
 
-    let res = client.try_initialize(&owner, &signers, &1u32, &None);
-    assert!(res.is_err());
+    let res = client.try_initialize(&owner, &signers, &1u32, &None, &None);
+    assert_eq!(res, Err(Ok(MultisigError::DuplicateSigner)));
 }
 
 // ==================== ContractUpgrade Flow ====================
@@ -430,24 +480,54 @@ fn contract_upgrade_proposal_and_execute() {
     let env = create_env();
     let (_id, client, _owner, signers, _guardian) = setup_2of3(&env);
 
-    let target = Address::generate(&env);
+    let target = env.register(UpgradeableMock, ());
+    let target_client = UpgradeableMockClient::new(&env, &target);
     let hash: BytesN<32> = BytesN::from_array(&env, &[0xAB; 32]);
 
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::ContractUpgrade(target.clone(), hash.clone()),
+        &None,
+        &None,
     );
+    assert_eq!(target_client.upgraded_to(), None);
 
     client.approve_operation(&signers.get(1).unwrap(), &op_id);
 
     let op = client.get_operation(&op_id).unwrap();
     assert_eq!(op.status, OperationStatus::Executed);
+    assert_eq!(target_client.upgraded_to(), Some(hash));
+}
+
+#[test]
+fn failed_external_upgrade_keeps_operation_pending() {
+    let env = create_env();
+    let (_id, client, _owner, signers, _guardian) = setup_2of3(&env);
+
+    // Target without an `upgrade` entrypoint.
+    let target = Address::generate(&env);
+    let hash: BytesN<32> = BytesN::from_array(&env, &[0xAB; 32]);
+
+    let op_id = client.propose_operation(
+        &signers.get(0).unwrap(),
+        &OperationKind::ContractUpgrade(target, hash),
+        &None,
+        &None,
+    );
+
+    // The failed call is recorded; the approval itself goes through.
+    client.approve_operation(&signers.get(1).unwrap(), &op_id);
+
+    let op = client.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.execution_result, Some(false));
+    assert_eq!(client.get_approvals(&op_id).len(), 2);
 }
 
 // ==================== DisputeResolution Flow ====================
 
 #[test]
-fn dispute_resolution_proposal_and_execute() {
+fn dispute_resolution_without_payroll_contract_stays_pending() {
     let env = create_env();
     let (_id, client, _owner, signers, _guardian) = setup_2of3(&env);
 
@@ -456,12 +536,16 @@ fn dispute_resolution_proposal_and_execute() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::DisputeResolution(payroll_contract, 42u128, 500, 200),
+        &None,
+        &None,
     );
 
     client.approve_operation(&signers.get(1).unwrap(), &op_id);
 
     let op = client.get_operation(&op_id).unwrap();
-    assert_eq!(op.status, OperationStatus::Executed);
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.execution_result, Some(false));
+    assert!(!client.get_execution_record(&op_id).unwrap().success);
 }
 
 // ==================== Query Functions ====================
@@ -480,6 +564,8 @@ fn query_functions_return_correct_data() {
     let op_id = client.propose_operation(
         &signers.get(0).unwrap(),
         &OperationKind::DisputeResolution(Address::generate(&env), 1u128, 10, 0),
+        &None,
+        &None,
     );
 
     let op = client.get_operation(&op_id).unwrap();
@@ -0,0 +1,211 @@
+//! Multisig `Invoke` and `SchedulerControl` operations against the payment
+//! scheduler contract.
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, IntoVal, Symbol, Vec};
+
+use multisig::{
+    MultisigContract, MultisigContractClient, MultisigError, OperationKind, OperationStatus,
+    SCHEDULER_CANCEL, SCHEDULER_PAUSE, SCHEDULER_RESUME,
+};
+use payment_scheduler::{JobStatus, PaymentSchedulerContract, PaymentSchedulerContractClient};
+
+struct Setup {
+    env: Env,
+    multisig: MultisigContractClient<'static>,
+    owner: Address,
+    signers: Vec<Address>,
+    scheduler: PaymentSchedulerContractClient<'static>,
+    job_id: u128,
+}
+
+/// 2-of-3 multisig acting as the employer of an active scheduler job.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let multisig_id = env.register(MultisigContract, ());
+    let multisig = MultisigContractClient::new(&env, &multisig_id);
+    let mut signers = Vec::new(&env);
+    for _ in 0..3 {
+        signers.push_back(Address::generate(&env));
+    }
+    let owner = Address::generate(&env);
+    multisig.initialize(&owner, &signers, &2u32, &None, &None);
+
+    let scheduler_id = env.register(PaymentSchedulerContract, ());
+    let scheduler = PaymentSchedulerContractClient::new(&env, &scheduler_id);
+    scheduler.initialize(&Address::generate(&env));
+    let job_id = scheduler.create_job(
+        &multisig_id,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &100i128,
+        &86_400u64,
+        &0u64,
+        &None,
+        &3u32,
+    );
+
+    Setup {
+        env,
+        multisig,
+        owner,
+        signers,
+        scheduler,
+        job_id,
+    }
+}
+
+fn pause_job_kind(s: &Setup) -> OperationKind {
+    OperationKind::Invoke(
+        s.scheduler.address.clone(),
+        Symbol::new(&s.env, "pause_job"),
+        vec![
+            &s.env,
+            s.multisig.address.into_val(&s.env),
+            s.job_id.into_val(&s.env),
+        ],
+    )
+}
+
+#[test]
+fn invoke_pauses_scheduler_job_after_threshold() {
+    let s = setup();
+
+    let op_id = s.multisig.propose_operation(
+        &s.signers.get(0).unwrap(),
+        &pause_job_kind(&s),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        s.scheduler.get_job(&s.job_id).unwrap().status,
+        JobStatus::Active
+    );
+
+    s.multisig
+        .approve_operation(&s.signers.get(1).unwrap(), &op_id);
+
+    let op = s.multisig.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Executed);
+    assert_eq!(
+        s.scheduler.get_job(&s.job_id).unwrap().status,
+        JobStatus::Paused
+    );
+}
+
+#[test]
+fn failed_invoke_is_recorded_on_manual_execution() {
+    let s = setup();
+    s.multisig.set_auto_execute(&s.owner, &false);
+
+    // Pausing an already-paused job fails inside the scheduler.
+    s.scheduler.pause_job(&s.multisig.address, &s.job_id);
+    let op_id = s.multisig.propose_operation(
+        &s.signers.get(0).unwrap(),
+        &pause_job_kind(&s),
+        &None,
+        &None,
+    );
+    s.multisig
+        .approve_operation(&s.signers.get(1).unwrap(), &op_id);
+    s.multisig.execute_operation(&s.owner, &op_id);
+
+    let op = s.multisig.get_operation(&op_id).unwrap();
+    assert_eq!(op.status, OperationStatus::Pending);
+    assert_eq!(op.execution_result, Some(false));
+
+    s.scheduler.resume_job(&s.multisig.address, &s.job_id);
+    s.multisig.execute_operation(&s.owner, &op_id);
+    assert_eq!(
+        s.multisig.get_operation(&op_id).unwrap().status,
+        OperationStatus::Executed
+    );
+    assert_eq!(
+        s.scheduler.get_job(&s.job_id).unwrap().status,
+        JobStatus::Paused
+    );
+}
+
+#[test]
+fn self_invoke_requires_owner_consent() {
+    let s = setup();
+    let kind = OperationKind::Invoke(
+        s.multisig.address.clone(),
+        Symbol::new(&s.env, "get_threshold"),
+        Vec::new(&s.env),
+    );
+
+    let res = s
+        .multisig
+        .try_propose_operation(&s.signers.get(0).unwrap(), &kind, &None, &None);
+    assert_eq!(res, Err(Ok(MultisigError::SelfInvokeNotAllowed)));
+
+    s.multisig.set_allow_self_invoke(&s.owner, &true);
+    assert!(s.multisig.get_allow_self_invoke());
+    let op_id = s
+        .multisig
+        .propose_operation(&s.signers.get(0).unwrap(), &kind, &None, &None);
+
+    // Revoking consent also blocks execution of an already-proposed call.
+    s.multisig.set_allow_self_invoke(&s.owner, &false);
+    let res = s
+        .multisig
+        .try_approve_operation(&s.signers.get(1).unwrap(), &op_id);
+    assert_eq!(res, Err(Ok(MultisigError::SelfInvokeNotAllowed)));
+    assert_eq!(
+        s.multisig.get_operation(&op_id).unwrap().status,
+        OperationStatus::Pending
+    );
+}
+
+#[test]
+fn scheduler_control_pauses_resumes_and_cancels_job() {
+    let s = setup();
+    let scheduler = s.scheduler.address.clone();
+
+    let pause = s.multisig.propose_operation(
+        &s.signers.get(0).unwrap(),
+        &OperationKind::SchedulerControl(scheduler.clone(), s.job_id, SCHEDULER_PAUSE),
+        &None,
+        &None,
+    );
+    assert_eq!(
+        s.scheduler.get_job(&s.job_id).unwrap().status,
+        JobStatus::Active
+    );
+    s.multisig
+        .approve_operation(&s.signers.get(2).unwrap(), &pause);
+    assert_eq!(
+        s.scheduler.get_job(&s.job_id).unwrap().status,
+        JobStatus::Paused
+    );
+
+    for (action, expected) in [
+        (SCHEDULER_RESUME, JobStatus::Active),
+        (SCHEDULER_CANCEL, JobStatus::Cancelled),
+    ] {
+        let op_id = s.multisig.propose_operation(
+            &s.signers.get(1).unwrap(),
+            &OperationKind::SchedulerControl(scheduler.clone(), s.job_id, action),
+            &None,
+            &None,
+        );
+        s.multisig
+            .approve_operation(&s.signers.get(0).unwrap(), &op_id);
+        assert_eq!(s.scheduler.get_job(&s.job_id).unwrap().status, expected);
+    }
+}
+
+#[test]
+fn scheduler_control_rejects_unknown_action() {
+    let s = setup();
+    let res = s.multisig.try_propose_operation(
+        &s.signers.get(0).unwrap(),
+        &OperationKind::SchedulerControl(s.scheduler.address.clone(), s.job_id, 3),
+        &None,
+        &None,
+    );
+    assert_eq!(res, Err(Ok(MultisigError::InvalidSchedulerAction)));
+}
@@ -2,18 +2,22 @@
 //!
 //! This contract manages cron-like payment jobs for StelloPay's payroll system.
 //! Each job encodes a recurring or one-time token transfer from a pre-funded
-//! escrow (the scheduler contract itself) to a recipient. An off-chain keeper
-//! or any caller invokes `process_due_payments` to execute all due jobs in a
-//! single transaction.
+//! escrow (the scheduler contract itself) to a recipient. Escrow is accounted
+//! per job: `fund_job` credits the job's own balance and each execution is
+//! paid from it, so one job can never spend another job's funding, even in
+//! the same token. An off-chain keeper or any caller invokes
+//! `process_due_payments` to execute all due jobs in a single transaction.
 //!
 //! ## Deterministic Schedule IDs
 //!
 //! Every job is assigned a **deterministic `schedule_id`** — a `BytesN<32>`
 //! SHA-256 fingerprint derived from the tuple
-//! `(employer, recipient, token, amount, start_time)`.  This fingerprint is
-//! used as the idempotency key: attempting to create two jobs with identical
-//! inputs (same employer, recipient, token, amount, and start time) returns
-//! `Err(SchedulerError::DuplicateSchedule)` without consuming a new job ID.
+//! `(employer, recipient, token, amount, start_time)`, extended with the
+//! `split_id` for jobs routed through a payment splitter and the recipient
+//! list for multi-recipient jobs.  This fingerprint is used as the
+//! idempotency key: attempting to create two jobs with identical inputs
+//! returns `Err(SchedulerError::DuplicateSchedule)` without consuming a new
+//! job ID.
 //!
 //! The deterministic-ID scheme means that:
 //! * Off-chain systems can predict the schedule key before submitting the
@@ -34,9 +38,12 @@
 //!
 //! * `initialize` is one-time only; subsequent calls return
 //!   `Err(AlreadyInitialized)`.
-//! * `create_job` requires employer authentication. The employer must
-//!   separately fund the scheduler via `fund_job` or a direct token transfer.
-//! * `pause_job`, `resume_job`, `cancel_job`, and `fund_job` are gated on the
+//! * `create_job` requires employer authentication. The job must then be
+//!   funded via `fund_job`; tokens sent to the scheduler directly are not
+//!   credited to any job.
+//! * `withdraw_job_funds` returns surplus job balance to the employer, and
+//!   `cancel_job` refunds whatever balance remains.
+//! * `pause_job`, `resume_job`, `update_job`, and `cancel_job` are gated on the
 //!   employer address stored inside the `PaymentJob` record, preventing any
 //!   other address from controlling the job.
 //! * `process_due_payments` is intentionally **permissionless**: any actor can
@@ -50,17 +57,39 @@
 //! the following events:
 //! * `job_created`   — new payment schedule registered.
 //! * `job_executed`  — payment transferred; contains `execution_index` and `amount`.
-//! * `job_failed`    — insufficient funds; contains `retry_count` / `max_retries`.
+//! * `job_failed`    — insufficient funds; contains `retry_count` / `max_retries`
+//!   and `next_retry_time`.
+//! * `job_skipped`   — percent-of-balance amount below the job's minimum.
+//! * `job_recipient_paid` — one transfer of a split or multi-recipient execution.
+//! * `job_updated`   — amount or interval changed; contains old and new values.
+//! * `job_completed` — all scheduled executions done.
+//! * `job_paused` / `job_resumed` — employer suspended or reactivated the job.
+//! * `job_transfer_proposed` / `job_transferred` — job ownership handed over.
+//! * `job_funded`    — tokens deposited into the job's escrow.
 //! * `job_cancelled` — schedule permanently removed by employer.
+//!
+//! Lifecycle events (`job_paused`, `job_resumed`, `job_funded`,
+//! `job_completed`, `job_cancelled`) carry the ledger `timestamp`.
 
 #![no_std]
 #![allow(deprecated)] // env.events().publish() — codebase-wide pattern
+#![allow(clippy::too_many_arguments)] // generated clients mirror the create_job entry points
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, token, xdr::ToXdr, Address, Bytes,
-    BytesN, Env,
+    contract, contracterror, contractimpl, contracttype, token, vec, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, IntoVal, Symbol, Vec,
 };
 
+/// Upper bound on the page size accepted by `get_jobs_by_employer`, keeping
+/// the read footprint of a single call well within ledger limits.
+pub const MAX_JOBS_PAGE_SIZE: u32 = 50;
+
+/// Basis points in 100%, the upper bound for `AmountKind::PercentOfBalance`.
+pub const MAX_BPS: u32 = 10_000;
+
+/// Maximum number of recipients of a multi-recipient job.
+pub const MAX_RECIPIENTS: u32 = 10;
+
 // ─── Error Types ─────────────────────────────────────────────────────────────
 
 /// Errors returned by the payment scheduler contract.
@@ -93,6 +122,26 @@ pub enum SchedulerError {
     AlreadyCancelled = 10,
     /// The job is not in a cancellable state (must be `Active` or `Paused`).
     JobNotCancellable = 11,
+    /// The job's escrow balance does not cover the requested amount.
+    InsufficientJobBalance = 12,
+    /// The job is not in an updatable state (must be `Active` or `Paused`).
+    JobNotUpdatable = 13,
+    /// `end_time` must be strictly after `start_time`.
+    InvalidEndTime = 14,
+    /// A percentage must be between 1 and 10 000 basis points.
+    InvalidPercentage = 15,
+    /// The split does not exist on the splitter contract, cannot pay the
+    /// job's amount (e.g. a fixed split whose shares do not sum to it), or is
+    /// combined with a percentage-of-balance amount.
+    InvalidSplit = 16,
+    /// The recipient list is empty, longer than `MAX_RECIPIENTS`, has a
+    /// non-positive amount, does not start with `recipient` or sum to
+    /// `amount`, or is combined with a split or percentage amount.
+    InvalidRecipients = 17,
+    /// `accept_job_transfer` without a pending proposal for the caller.
+    NoPendingTransfer = 18,
+    /// `retry_delay_seconds` is zero.
+    InvalidRetryDelay = 19,
 }
 
 // ─── Domain Types ─────────────────────────────────────────────────────────────
@@ -113,6 +162,18 @@ pub enum JobStatus {
     Cancelled,
 }
 
+/// How the amount paid on each execution is determined.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AmountKind {
+    /// Pay `PaymentJob.amount` on every execution.
+    Fixed,
+    /// Pay this many basis points of the job's escrow balance at execution
+    /// time. `PaymentJob.amount` is the minimum payment; below it the
+    /// execution is skipped rather than failed.
+    PercentOfBalance(u32),
+}
+
 /// A payment job record stored on-chain.
 ///
 /// # Idempotency note
@@ -126,7 +187,8 @@ pub enum JobStatus {
 pub struct PaymentJob {
     /// Sequential identifier assigned at creation time.
     pub id: u128,
-    /// Deterministic SHA-256 fingerprint of `(employer, recipient, token, amount, start_time)`.
+    /// Deterministic SHA-256 fingerprint of `(employer, recipient, token, amount, start_time)`,
+    /// plus the `split_id` and recipient list when set.
     /// Used as the idempotency key for deduplication.
     pub schedule_id: BytesN<32>,
     /// Employer address that created and funds this job.
@@ -135,7 +197,8 @@ pub struct PaymentJob {
     pub recipient: Address,
     /// Token contract address used for transfers.
     pub token: Address,
-    /// Amount transferred per execution cycle (must be > 0).
+    /// Amount transferred per execution cycle (must be > 0). For
+    /// `AmountKind::PercentOfBalance` jobs this is the minimum payment.
     pub amount: i128,
     /// Seconds between execution cycles. Zero is only allowed for one-time jobs
     /// (`max_executions == Some(1)`).
@@ -152,6 +215,67 @@ pub struct PaymentJob {
     pub retry_count: u32,
     /// Current lifecycle status.
     pub status: JobStatus,
+    /// Optional ledger timestamp at which the job completes, even if
+    /// `max_executions` has not been reached. No execution is paid at or
+    /// after this time.
+    pub end_time: Option<u64>,
+    /// When `true`, every missed interval is paid: `next_scheduled_time`
+    /// advances by exact multiples of `interval_seconds` from the original
+    /// schedule rather than from the processing time.
+    pub catch_up: bool,
+    /// Earliest timestamp for retrying a catch-up job after a failed attempt;
+    /// `0` when no retry is pending.
+    pub retry_after: u64,
+    /// How each execution's amount is computed.
+    pub amount_kind: AmountKind,
+    /// When set, `recipient` is a `PaymentSplitterContract` and each
+    /// execution is distributed according to this split definition.
+    pub split_id: Option<u128>,
+    /// When set, each execution pays every listed `(recipient, amount)`;
+    /// `amount` is their sum and `recipient` the first entry.
+    pub recipients: Option<Vec<(Address, i128)>>,
+    /// Wait after a failed attempt before retrying. `None` retries one
+    /// `interval_seconds` later.
+    pub retry_delay_seconds: Option<u64>,
+}
+
+/// Optional settings accepted by `create_job_with_options`.
+///
+/// Kept separate from the positional `create_job` parameters so new settings
+/// can be added without exceeding Soroban's contract function argument limit.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct JobOptions {
+    /// Ledger timestamp at which the job completes; must be after `start_time`.
+    pub end_time: Option<u64>,
+    /// Pay one execution per elapsed-but-unpaid interval instead of skipping
+    /// missed intervals.
+    pub catch_up: bool,
+    /// When set, each execution pays this many basis points of the job
+    /// balance (`AmountKind::PercentOfBalance`) and `amount` becomes the
+    /// minimum payment.
+    pub percent_of_balance_bps: Option<u32>,
+    /// Route each execution through this split on the `PaymentSplitterContract`
+    /// passed as `recipient`. Not combinable with `percent_of_balance_bps`.
+    pub split_id: Option<u128>,
+    /// Pay several recipients their listed amounts each interval, at most
+    /// `MAX_RECIPIENTS`. `recipient` must be the first entry and `amount` the
+    /// sum of all entries.
+    pub recipients: Option<Vec<(Address, i128)>>,
+    /// Retry a failed attempt after this many seconds instead of a full
+    /// interval. Must be positive.
+    pub retry_delay_seconds: Option<u64>,
+}
+
+/// Outcome of a `process_due_payments` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessResult {
+    /// Number of due jobs evaluated (paid or failed).
+    pub processed: u32,
+    /// Id of the last job examined; the next call resumes after it.
+    /// `0` when no job has been examined yet.
+    pub last_job_id: u128,
 }
 
 // ─── Storage Keys ─────────────────────────────────────────────────────────────
@@ -170,6 +294,20 @@ enum StorageKey {
     /// Idempotency sentinel keyed by deterministic `schedule_id`.
     /// Stores the sequential job id (`u128`) assigned at creation time.
     ScheduleId(BytesN<32>),
+    /// Escrowed tokens attributed to a job (`i128`), zero when absent.
+    JobBalance(u128),
+    /// Ids of every job created by an employer, in creation order
+    /// (`Vec<u128>`). Completed, failed and cancelled jobs are retained.
+    EmployerJobs(Address),
+    /// Ids of all `Active` jobs in ascending order (`Vec<u128>`). This is the
+    /// only set `process_due_payments` scans.
+    ActiveJobs,
+    /// Id of the last job examined by `process_due_payments` (`u128`); the
+    /// next call resumes after it.
+    ProcessCursor,
+    /// Proposed new employer of a job (`Address`), awaiting
+    /// `accept_job_transfer`.
+    PendingTransfer(u128),
 }
 
 // ─── Events ───────────────────────────────────────────────────────────────────
@@ -191,19 +329,56 @@ pub struct JobExecutedEvent {
     pub job_id: u128,
     /// 1-based execution index (equals `job.executions` after the transfer).
     pub execution_index: u32,
+    /// Amount actually transferred, computed at execution time for
+    /// `AmountKind::PercentOfBalance` jobs.
+    pub amount: i128,
+}
+
+/// Emitted for each transfer of a split or multi-recipient execution, before
+/// the `job_executed` summary.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobRecipientPaidEvent {
+    pub job_id: u128,
+    pub execution_index: u32,
+    pub recipient: Address,
     pub amount: i128,
 }
 
-/// Emitted when a payment attempt fails due to insufficient escrow balance.
+/// Emitted when a `PercentOfBalance` execution is skipped because the
+/// computed amount is below the job's minimum.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobSkippedEvent {
+    pub job_id: u128,
+    pub computed_amount: i128,
+    pub min_amount: i128,
+    pub timestamp: u64,
+}
+
+/// Emitted when a payment attempt fails due to insufficient job balance.
 ///
 /// Off-chain payroll systems should use this event to alert employers to top up
-/// the scheduler's escrow before `retry_count` exceeds `max_retries`.
+/// the job via `fund_job` before `retry_count` exceeds `max_retries`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct JobFailedEvent {
     pub job_id: u128,
     pub retry_count: u32,
     pub max_retries: u32,
+    /// Earliest timestamp of the next attempt; `0` once the job has `Failed`.
+    pub next_retry_time: u64,
+}
+
+/// Emitted when an employer changes a job's parameters via `update_job`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobUpdatedEvent {
+    pub job_id: u128,
+    pub old_amount: i128,
+    pub new_amount: i128,
+    pub old_interval_seconds: u64,
+    pub new_interval_seconds: u64,
 }
 
 /// Emitted when an employer permanently cancels a job via `cancel_job`.
@@ -212,6 +387,69 @@ pub struct JobFailedEvent {
 pub struct JobCancelledEvent {
     pub job_id: u128,
     pub employer: Address,
+    /// Remaining job balance returned to the employer.
+    pub refunded: i128,
+    pub timestamp: u64,
+}
+
+/// Emitted when an employer pauses a job via `pause_job`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobPausedEvent {
+    pub job_id: u128,
+    pub employer: Address,
+    pub timestamp: u64,
+}
+
+/// Emitted when an employer resumes a paused job via `resume_job`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobResumedEvent {
+    pub job_id: u128,
+    pub employer: Address,
+    pub timestamp: u64,
+}
+
+/// Emitted when an employer proposes a new owner via `propose_job_transfer`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobTransferProposedEvent {
+    pub job_id: u128,
+    pub employer: Address,
+    pub new_employer: Address,
+    pub timestamp: u64,
+}
+
+/// Emitted when the proposed employer takes over a job via
+/// `accept_job_transfer`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobTransferredEvent {
+    pub job_id: u128,
+    pub old_employer: Address,
+    pub new_employer: Address,
+    pub timestamp: u64,
+}
+
+/// Emitted when tokens are deposited into a job's escrow via `fund_job`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobFundedEvent {
+    pub job_id: u128,
+    pub from: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Emitted when a job reaches `Completed` status inside `process_due_payments`,
+/// either after its last execution or once its `end_time` is reached.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobCompletedEvent {
+    pub job_id: u128,
+    /// Total successful executions of the job.
+    pub executions: u32,
+    pub timestamp: u64,
 }
 
 // ─── Internal Helpers ─────────────────────────────────────────────────────────
@@ -255,10 +493,327 @@ fn write_job(env: &Env, job: &PaymentJob) {
         .set(&StorageKey::Job(job.id), job);
 }
 
+fn read_job_balance(env: &Env, id: u128) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<_, i128>(&StorageKey::JobBalance(id))
+        .unwrap_or(0)
+}
+
+fn write_job_balance(env: &Env, id: u128, balance: i128) {
+    let key = StorageKey::JobBalance(id);
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &balance);
+    }
+}
+
+fn read_active_jobs(env: &Env) -> Vec<u128> {
+    env.storage()
+        .persistent()
+        .get::<_, Vec<u128>>(&StorageKey::ActiveJobs)
+        .unwrap_or(Vec::new(env))
+}
+
+fn write_active_jobs(env: &Env, ids: &Vec<u128>) {
+    env.storage().persistent().set(&StorageKey::ActiveJobs, ids);
+}
+
+/// Inserts `id` into the active-jobs index, keeping it sorted.
+fn add_active_job(env: &Env, id: u128) {
+    let mut ids = read_active_jobs(env);
+    if let Err(pos) = ids.binary_search(id) {
+        ids.insert(pos, id);
+        write_active_jobs(env, &ids);
+    }
+}
+
+fn remove_active_job(env: &Env, id: u128) {
+    let mut ids = read_active_jobs(env);
+    if let Ok(pos) = ids.binary_search(id) {
+        ids.remove(pos);
+        write_active_jobs(env, &ids);
+    }
+}
+
+fn read_employer_jobs(env: &Env, employer: &Address) -> Vec<u128> {
+    env.storage()
+        .persistent()
+        .get::<_, Vec<u128>>(&StorageKey::EmployerJobs(employer.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn write_employer_jobs(env: &Env, employer: &Address, ids: &Vec<u128>) {
+    env.storage()
+        .persistent()
+        .set(&StorageKey::EmployerJobs(employer.clone()), ids);
+}
+
+/// Seconds to wait before retrying a failed attempt of `job`.
+fn retry_delay(job: &PaymentJob) -> u64 {
+    job.retry_delay_seconds.unwrap_or(job.interval_seconds)
+}
+
+/// Checks a multi-recipient list against the job's `recipient` and `amount`.
+fn validate_recipients(
+    recipient: &Address,
+    amount: i128,
+    recipients: &Vec<(Address, i128)>,
+) -> Result<(), SchedulerError> {
+    if recipients.is_empty() || recipients.len() > MAX_RECIPIENTS {
+        return Err(SchedulerError::InvalidRecipients);
+    }
+    if recipients.get_unchecked(0).0 != *recipient {
+        return Err(SchedulerError::InvalidRecipients);
+    }
+    let mut total: i128 = 0;
+    for (_, share) in recipients.iter() {
+        if share <= 0 {
+            return Err(SchedulerError::InvalidRecipients);
+        }
+        total = total
+            .checked_add(share)
+            .ok_or(SchedulerError::InvalidRecipients)?;
+    }
+    if total != amount {
+        return Err(SchedulerError::InvalidRecipients);
+    }
+    Ok(())
+}
+
+/// Returns `bps` basis points of `balance`, rounded down. Split into quotient
+/// and remainder so large balances cannot overflow.
+fn percent_of(balance: i128, bps: u32) -> i128 {
+    let bps = bps as i128;
+    let scale = MAX_BPS as i128;
+    balance / scale * bps + balance % scale * bps / scale
+}
+
+/// Asks the splitter at `splitter` whether split `split_id` can pay `amount`.
+fn validate_split(
+    env: &Env,
+    splitter: &Address,
+    split_id: u128,
+    amount: i128,
+) -> Result<(), SchedulerError> {
+    let valid = env.try_invoke_contract::<bool, soroban_sdk::Error>(
+        splitter,
+        &Symbol::new(env, "validate_split_for_amount"),
+        vec![env, split_id.into_val(env), amount.into_val(env)],
+    );
+    if !matches!(valid, Ok(Ok(true))) {
+        return Err(SchedulerError::InvalidSplit);
+    }
+    Ok(())
+}
+
+/// Returns the `(recipient, amount)` transfers for one execution of `job`.
+///
+/// Multi-recipient jobs pay their stored list. Split jobs ask the splitter
+/// contract for the portions. `None` if the call fails or the portions are
+/// negative or do not add up to `amount`, so a broken or malicious splitter
+/// can only fail its own job.
+fn payout_shares(env: &Env, job: &PaymentJob, amount: i128) -> Option<Vec<(Address, i128)>> {
+    if let Some(recipients) = &job.recipients {
+        return Some(recipients.clone());
+    }
+    let Some(split_id) = job.split_id else {
+        return Some(vec![env, (job.recipient.clone(), amount)]);
+    };
+
+    let shares = match env.try_invoke_contract::<Vec<(Address, i128)>, soroban_sdk::Error>(
+        &job.recipient,
+        &Symbol::new(env, "compute_split"),
+        vec![env, split_id.into_val(env), amount.into_val(env)],
+    ) {
+        Ok(Ok(shares)) => shares,
+        _ => return None,
+    };
+
+    let mut total: i128 = 0;
+    for (_, share) in shares.iter() {
+        if share < 0 {
+            return None;
+        }
+        total = total.checked_add(share)?;
+    }
+    if total != amount {
+        return None;
+    }
+    Some(shares)
+}
+
+/// Returns `true` once a job with an `end_time` must complete. Catch-up jobs
+/// first pay every missed period that started before `end_time`.
+fn term_over(job: &PaymentJob, now: u64) -> bool {
+    match job.end_time {
+        Some(end_time) => now >= end_time && (!job.catch_up || job.next_scheduled_time >= end_time),
+        None => false,
+    }
+}
+
+fn is_due(job: &PaymentJob, now: u64) -> bool {
+    now >= job.next_scheduled_time && now >= job.retry_after
+}
+
+/// Performs one payment attempt for a due job and returns the updated record.
+///
+/// * If the job's escrow balance covers `amount`, the job is advanced and
+///   persisted before the transfer (state-before-interaction), and
+///   `job_executed` (plus `job_completed` on completion) is emitted.
+/// * Otherwise (including when a split job's splitter call fails)
+///   `retry_count` is incremented, the job is rescheduled or marked
+///   `Failed`, and `job_failed` is emitted.
+fn attempt_payment(env: &Env, mut job: PaymentJob, now: u64) -> PaymentJob {
+    let balance = read_job_balance(env, job.id);
+    let amount = match job.amount_kind {
+        AmountKind::Fixed => job.amount,
+        AmountKind::PercentOfBalance(bps) => percent_of(balance, bps),
+    };
+
+    if let AmountKind::PercentOfBalance(_) = job.amount_kind {
+        if amount < job.amount {
+            // Below the minimum: skip this period without consuming a retry.
+            let base = if job.catch_up {
+                job.next_scheduled_time
+            } else {
+                now
+            };
+            job.next_scheduled_time = base.saturating_add(job.interval_seconds);
+            job.retry_after = 0;
+            write_job(env, &job);
+
+            env.events().publish(
+                ("job_skipped", job.id),
+                JobSkippedEvent {
+                    job_id: job.id,
+                    computed_amount: amount,
+                    min_amount: job.amount,
+                    timestamp: now,
+                },
+            );
+            return job;
+        }
+    }
+
+    // Only ask a splitter for shares when the job can actually pay.
+    let shares = if balance >= amount {
+        payout_shares(env, &job, amount)
+    } else {
+        None
+    };
+
+    if let Some(shares) = shares {
+        // Checks-effects-interactions:
+        // commit job progress before transfer so reentrant
+        // callbacks cannot re-execute the same due payment.
+        job.executions = job.executions.saturating_add(1);
+        job.retry_count = 0;
+        job.retry_after = 0;
+        // Catch-up jobs stay on the original schedule; others restart from now.
+        let base = if job.catch_up {
+            job.next_scheduled_time
+        } else {
+            now
+        };
+        job.next_scheduled_time = base.saturating_add(job.interval_seconds);
+
+        if let Some(max_exec) = job.max_executions {
+            if job.executions >= max_exec {
+                job.status = JobStatus::Completed;
+            }
+        }
+
+        // State-before-interaction: persist before token transfer.
+        write_job(env, &job);
+        write_job_balance(env, job.id, balance - amount);
+        if job.status == JobStatus::Completed {
+            remove_active_job(env, job.id);
+        }
+
+        let token_client = token::Client::new(env, &job.token);
+        let itemize = job.split_id.is_some() || job.recipients.is_some();
+        for (to, share) in shares.iter() {
+            if share > 0 {
+                token_client.transfer(&env.current_contract_address(), &to, &share);
+            }
+            if itemize {
+                env.events().publish(
+                    ("job_recipient_paid", job.id),
+                    JobRecipientPaidEvent {
+                        job_id: job.id,
+                        execution_index: job.executions,
+                        recipient: to,
+                        amount: share,
+                    },
+                );
+            }
+        }
+
+        env.events().publish(
+            ("job_executed", job.id),
+            JobExecutedEvent {
+                job_id: job.id,
+                execution_index: job.executions,
+                amount,
+            },
+        );
+
+        if job.status == JobStatus::Completed {
+            publish_job_completed(env, &job, now);
+        }
+    } else {
+        // Insufficient funds or unusable split: schedule retry or mark failed.
+        job.retry_count = job.retry_count.saturating_add(1);
+
+        let next_retry_time = if job.retry_count > job.max_retries {
+            job.status = JobStatus::Failed;
+            remove_active_job(env, job.id);
+            0
+        } else if job.catch_up {
+            // Keep the unpaid period owed, but gate the retry so repeated
+            // calls cannot burn through `max_retries`.
+            job.retry_after = now.saturating_add(retry_delay(&job));
+            job.retry_after
+        } else {
+            job.next_scheduled_time = now.saturating_add(retry_delay(&job));
+            job.next_scheduled_time
+        };
+
+        write_job(env, &job);
+
+        env.events().publish(
+            ("job_failed", job.id),
+            JobFailedEvent {
+                job_id: job.id,
+                retry_count: job.retry_count,
+                max_retries: job.max_retries,
+                next_retry_time,
+            },
+        );
+    }
+
+    job
+}
+
+fn publish_job_completed(env: &Env, job: &PaymentJob, now: u64) {
+    env.events().publish(
+        ("job_completed", job.id),
+        JobCompletedEvent {
+            job_id: job.id,
+            executions: job.executions,
+            timestamp: now,
+        },
+    );
+}
+
 /// Derives the deterministic schedule fingerprint from the job's immutable parameters.
 ///
 /// The fingerprint is a SHA-256 hash over the concatenation of the canonical
-/// XDR encodings of `(employer, recipient, token, amount_le_bytes, start_time_le_bytes)`.
+/// XDR encodings of `(employer, recipient, token, amount_le_bytes, start_time_le_bytes)`,
+/// followed by `split_id_le_bytes` for jobs routed through a payment splitter
+/// and the XDR of the recipient list for multi-recipient jobs.
 /// Because Soroban's SHA-256 operates on raw `Bytes`, we encode numeric values
 /// as little-endian byte slices to ensure a fixed-length, canonical encoding.
 ///
@@ -272,9 +827,12 @@ fn compute_schedule_id(
     token: &Address,
     amount: i128,
     start_time: u64,
+    split_id: Option<u128>,
+    recipients: &Option<Vec<(Address, i128)>>,
 ) -> BytesN<32> {
     // Build a deterministic byte buffer:
-    //   [employer_xdr | recipient_xdr | token_xdr | amount_le(16) | start_time_le(8)]
+    //   [employer_xdr | recipient_xdr | token_xdr | amount_le(16) | start_time_le(8)
+    //    | split_id_le(16)? | recipients_xdr?]
     let mut buf = Bytes::new(env);
 
     // Address XDR encoding via to_xdr
@@ -294,6 +852,18 @@ fn compute_schedule_id(
         buf.push_back(*byte);
     }
 
+    // split_id (u128) as 16-byte little-endian; absent for direct payments so
+    // their fingerprints are unchanged.
+    if let Some(split_id) = split_id {
+        for byte in split_id.to_le_bytes().iter() {
+            buf.push_back(*byte);
+        }
+    }
+
+    if let Some(recipients) = recipients {
+        buf.append(&recipients.clone().to_xdr(env));
+    }
+
     env.crypto().sha256(&buf).into()
 }
 
@@ -337,13 +907,14 @@ impl PaymentSchedulerContract {
 
     /// @notice Creates a new recurring or one-time payment job.
     /// @dev Derives a deterministic `schedule_id` from `(employer, recipient,
-    ///      token, amount, start_time)` and uses it as an idempotency key.
+    ///      token, amount, start_time)`, plus the `split_id` and recipient
+    ///      list set through `create_job_with_options`, and uses it as an
+    ///      idempotency key.
     ///      Attempting to create a job with identical parameters returns
     ///      `Err(DuplicateSchedule)` without consuming a new job ID.
     ///
-    ///      The employer must separately fund the scheduler (via `fund_job` or
-    ///      a direct token transfer) before the first execution becomes due;
-    ///      otherwise the scheduler will increment `retry_count`.
+    ///      The job must be funded via `fund_job` before the first execution
+    ///      becomes due; otherwise the scheduler will increment `retry_count`.
     ///
     ///      For one-time payments (`max_executions == Some(1)`) `interval_seconds`
     ///      may be zero. For all other jobs it must be > 0.
@@ -370,6 +941,58 @@ impl PaymentSchedulerContract {
         start_time: u64,
         max_executions: Option<u32>,
         max_retries: u32,
+    ) -> Result<u128, SchedulerError> {
+        Self::create_job_with_options(
+            env,
+            employer,
+            recipient,
+            token,
+            amount,
+            interval_seconds,
+            start_time,
+            max_executions,
+            max_retries,
+            JobOptions::default(),
+        )
+    }
+
+    /// @notice Creates a payment job with additional settings.
+    /// @dev Same as `create_job`, plus the optional settings in `JobOptions`:
+    ///      * `end_time` — the job completes once `now >= end_time`, without
+    ///        paying the period that starts at `end_time`, even if
+    ///        `max_executions` has not been reached. Must be `> start_time`.
+    ///      * `catch_up` — pay every missed interval (see `process_due_payments`).
+    ///      * `percent_of_balance_bps` — pay a share of the job balance on each
+    ///        execution; `amount` is then the minimum payment.
+    ///      * `split_id` — `recipient` is a `PaymentSplitterContract`; each
+    ///        execution cross-calls its `compute_split(split_id, amount)` and
+    ///        pays every portion. The split must exist and accept `amount`
+    ///        (`validate_split_for_amount`), otherwise `Err(InvalidSplit)`.
+    ///        Not combinable with `percent_of_balance_bps`, whose amount
+    ///        changes every execution (`Err(InvalidSplit)`). The split id is
+    ///        part of the schedule fingerprint.
+    ///      * `recipients` — pay each listed `(recipient, amount)` every
+    ///        interval, all or nothing. Up to `MAX_RECIPIENTS` positive
+    ///        entries; `recipient` must be the first entry and `amount` their
+    ///        sum. Not combinable with `split_id` or `percent_of_balance_bps`.
+    ///        The list is part of the schedule fingerprint.
+    ///      * `retry_delay_seconds` — wait this long after a failed attempt
+    ///        instead of `interval_seconds`; successful executions still
+    ///        advance by the interval. Zero returns `Err(InvalidRetryDelay)`.
+    /// @param options Additional settings; see `JobOptions`.
+    /// @return The newly assigned sequential job id.
+    /// @security Same as `create_job`.
+    pub fn create_job_with_options(
+        env: Env,
+        employer: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        interval_seconds: u64,
+        start_time: u64,
+        max_executions: Option<u32>,
+        max_retries: u32,
+        options: JobOptions,
     ) -> Result<u128, SchedulerError> {
         require_initialized(&env)?;
         employer.require_auth();
@@ -383,9 +1006,49 @@ impl PaymentSchedulerContract {
             return Err(SchedulerError::IntervalRequired);
         }
 
+        if let Some(end_time) = options.end_time {
+            if end_time <= start_time {
+                return Err(SchedulerError::InvalidEndTime);
+            }
+        }
+
+        if options.retry_delay_seconds == Some(0) {
+            return Err(SchedulerError::InvalidRetryDelay);
+        }
+
+        let amount_kind = match options.percent_of_balance_bps {
+            Some(bps) if bps == 0 || bps > MAX_BPS => {
+                return Err(SchedulerError::InvalidPercentage)
+            }
+            Some(bps) => AmountKind::PercentOfBalance(bps),
+            None => AmountKind::Fixed,
+        };
+
+        if let Some(recipients) = &options.recipients {
+            validate_recipients(&recipient, amount, recipients)?;
+            if options.split_id.is_some() || options.percent_of_balance_bps.is_some() {
+                return Err(SchedulerError::InvalidRecipients);
+            }
+        }
+
+        if let Some(split_id) = options.split_id {
+            if options.percent_of_balance_bps.is_some() {
+                return Err(SchedulerError::InvalidSplit);
+            }
+            validate_split(&env, &recipient, split_id, amount)?;
+        }
+
         // Derive and check the deterministic idempotency key.
-        let schedule_id =
-            compute_schedule_id(&env, &employer, &recipient, &token, amount, start_time);
+        let schedule_id = compute_schedule_id(
+            &env,
+            &employer,
+            &recipient,
+            &token,
+            amount,
+            start_time,
+            options.split_id,
+            &options.recipients,
+        );
 
         let id_key = StorageKey::ScheduleId(schedule_id.clone());
         if env.storage().persistent().has(&id_key) {
@@ -407,12 +1070,25 @@ impl PaymentSchedulerContract {
             max_retries,
             retry_count: 0,
             status: JobStatus::Active,
+            end_time: options.end_time,
+            catch_up: options.catch_up,
+            retry_after: 0,
+            amount_kind,
+            split_id: options.split_id,
+            recipients: options.recipients,
+            retry_delay_seconds: options.retry_delay_seconds,
         };
         write_job(&env, &job);
 
         // Register the idempotency sentinel.
         env.storage().persistent().set(&id_key, &id);
 
+        add_active_job(&env, id);
+
+        let mut employer_jobs = read_employer_jobs(&env, &employer);
+        employer_jobs.push_back(id);
+        write_employer_jobs(&env, &employer, &employer_jobs);
+
         env.events().publish(
             ("job_created", id),
             JobCreatedEvent {
@@ -426,6 +1102,87 @@ impl PaymentSchedulerContract {
         Ok(id)
     }
 
+    /// @notice Changes the amount and/or interval of an existing job.
+    /// @dev Intended for salary changes without losing execution history.
+    ///      Only `Active` and `Paused` jobs may be updated; `Completed` and
+    ///      `Failed` jobs return `Err(JobNotUpdatable)` and cancelled jobs
+    ///      return `Err(AlreadyCancelled)`. The amount of a multi-recipient
+    ///      job is the sum of its list and cannot be changed
+    ///      (`Err(InvalidRecipients)`). A new amount for a split job is
+    ///      checked against the split again (`Err(InvalidSplit)`).
+    ///
+    ///      `next_scheduled_time` is left unchanged: the pending execution
+    ///      keeps its due time and pays the new amount, and the new interval
+    ///      applies from that execution onward. The `schedule_id` keeps the
+    ///      fingerprint of the original creation parameters.
+    ///
+    /// @param employer Current employer of the job. Must authenticate.
+    /// @param job_id   Sequential job identifier.
+    /// @param new_amount Optional new positive amount per execution (the
+    ///                  minimum payment for `PercentOfBalance` jobs).
+    /// @param new_interval_seconds Optional new interval. Must be > 0 unless
+    ///                  the job is one-time (`max_executions == Some(1)`).
+    /// @return Ok(()) on success.
+    /// @security Requires `employer` authentication and matches it against
+    ///           the stored `job.employer`.
+    pub fn update_job(
+        env: Env,
+        employer: Address,
+        job_id: u128,
+        new_amount: Option<i128>,
+        new_interval_seconds: Option<u64>,
+    ) -> Result<(), SchedulerError> {
+        require_initialized(&env)?;
+        employer.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        if job.employer != employer {
+            return Err(SchedulerError::NotEmployer);
+        }
+        match job.status {
+            JobStatus::Cancelled => return Err(SchedulerError::AlreadyCancelled),
+            JobStatus::Completed | JobStatus::Failed => {
+                return Err(SchedulerError::JobNotUpdatable)
+            }
+            JobStatus::Active | JobStatus::Paused => {}
+        }
+
+        let amount = new_amount.unwrap_or(job.amount);
+        if amount <= 0 {
+            return Err(SchedulerError::AmountNotPositive);
+        }
+        // A multi-recipient job's amount is fixed by its recipient list.
+        if job.recipients.is_some() && amount != job.amount {
+            return Err(SchedulerError::InvalidRecipients);
+        }
+        if let Some(split_id) = job.split_id {
+            if amount != job.amount {
+                validate_split(&env, &job.recipient, split_id, amount)?;
+            }
+        }
+        let interval_seconds = new_interval_seconds.unwrap_or(job.interval_seconds);
+        if job.max_executions != Some(1) && interval_seconds == 0 {
+            return Err(SchedulerError::IntervalRequired);
+        }
+
+        let event = JobUpdatedEvent {
+            job_id,
+            old_amount: job.amount,
+            new_amount: amount,
+            old_interval_seconds: job.interval_seconds,
+            new_interval_seconds: interval_seconds,
+        };
+
+        job.amount = amount;
+        job.interval_seconds = interval_seconds;
+        write_job(&env, &job);
+
+        env.events().publish(("job_updated", job_id), event);
+
+        Ok(())
+    }
+
     /// @notice Permanently cancels a payment job.
     /// @dev Only the original employer may cancel their own job. Jobs in
     ///      `Active` or `Paused` status may be cancelled; jobs already in
@@ -433,16 +1190,14 @@ impl PaymentSchedulerContract {
     ///      Attempting to cancel an already-`Cancelled` job returns
     ///      `Err(AlreadyCancelled)` (idempotency guard).
     ///
-    ///      Cancellation does **not** return any pre-funded tokens; the employer
-    ///      must reclaim these externally (e.g. by withdrawing from the
-    ///      scheduler's escrow balance via a separate sweep call).
+    ///      The job's remaining escrow balance is refunded to the employer.
     ///
-    /// @param employer Employer that created the job. Must authenticate.
+    /// @param employer Current employer of the job. Must authenticate.
     /// @param job_id   Sequential identifier returned by `create_job`.
     /// @return Ok(()) on success.
     /// @security Requires `employer` authentication. The stored `job.employer`
     ///           is compared against the caller to prevent cross-employer
-    ///           cancellation.
+    ///           cancellation. State is written before the refund transfer.
     pub fn cancel_job(env: Env, employer: Address, job_id: u128) -> Result<(), SchedulerError> {
         require_initialized(&env)?;
         employer.require_auth();
@@ -461,14 +1216,29 @@ impl PaymentSchedulerContract {
             JobStatus::Active | JobStatus::Paused => {}
         }
 
+        if job.status == JobStatus::Active {
+            remove_active_job(&env, job_id);
+        }
         job.status = JobStatus::Cancelled;
         write_job(&env, &job);
 
+        let refunded = read_job_balance(&env, job_id);
+        if refunded > 0 {
+            write_job_balance(&env, job_id, 0);
+            token::Client::new(&env, &job.token).transfer(
+                &env.current_contract_address(),
+                &employer,
+                &refunded,
+            );
+        }
+
         env.events().publish(
             ("job_cancelled", job_id),
             JobCancelledEvent {
                 job_id,
                 employer,
+                refunded,
+                timestamp: env.ledger().timestamp(),
             },
         );
 
@@ -476,9 +1246,10 @@ impl PaymentSchedulerContract {
     }
 
     /// @notice Pauses an active job, preventing automatic execution.
-    /// @dev Only the employer that created the job may pause it.
-    ///      The job must be in `Active` status.
-    /// @param employer Employer that created the job. Must authenticate.
+    /// @dev Only the job's current employer may pause it.
+    ///      The job must be in `Active` status; cancelled jobs return
+    ///      `Err(AlreadyCancelled)`.
+    /// @param employer Current employer of the job. Must authenticate.
     /// @param job_id   Sequential job identifier.
     /// @return Ok(()) on success.
     pub fn pause_job(env: Env, employer: Address, job_id: u128) -> Result<(), SchedulerError> {
@@ -490,20 +1261,34 @@ impl PaymentSchedulerContract {
         if job.employer != employer {
             return Err(SchedulerError::NotEmployer);
         }
+        if job.status == JobStatus::Cancelled {
+            return Err(SchedulerError::AlreadyCancelled);
+        }
         if job.status != JobStatus::Active {
             return Err(SchedulerError::JobNotActive);
         }
 
         job.status = JobStatus::Paused;
         write_job(&env, &job);
+        remove_active_job(&env, job_id);
+
+        env.events().publish(
+            ("job_paused", job_id),
+            JobPausedEvent {
+                job_id,
+                employer,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
         Ok(())
     }
 
     /// @notice Resumes a previously paused job.
-    /// @dev Only the employer that created the job may resume it.
-    ///      The job must be in `Paused` status.
-    /// @param employer Employer that created the job. Must authenticate.
+    /// @dev Only the job's current employer may resume it.
+    ///      The job must be in `Paused` status; cancelled jobs return
+    ///      `Err(AlreadyCancelled)`.
+    /// @param employer Current employer of the job. Must authenticate.
     /// @param job_id   Sequential job identifier.
     /// @return Ok(()) on success.
     pub fn resume_job(env: Env, employer: Address, job_id: u128) -> Result<(), SchedulerError> {
@@ -515,137 +1300,275 @@ impl PaymentSchedulerContract {
         if job.employer != employer {
             return Err(SchedulerError::NotEmployer);
         }
+        if job.status == JobStatus::Cancelled {
+            return Err(SchedulerError::AlreadyCancelled);
+        }
         if job.status != JobStatus::Paused {
             return Err(SchedulerError::JobNotPaused);
         }
 
         job.status = JobStatus::Active;
         write_job(&env, &job);
+        add_active_job(&env, job_id);
+
+        env.events().publish(
+            ("job_resumed", job_id),
+            JobResumedEvent {
+                job_id,
+                employer,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// @notice Proposes handing a job over to another employer address.
+    /// @dev First step of a two-step transfer; nothing changes until
+    ///      `new_employer` calls `accept_job_transfer`. A new proposal
+    ///      replaces any pending one, and proposing the current employer
+    ///      withdraws it. Cancelled jobs return `Err(AlreadyCancelled)`.
+    /// @param employer     Current employer of the job. Must authenticate.
+    /// @param job_id       Sequential job identifier.
+    /// @param new_employer Address that will own the job once it accepts.
+    /// @return Ok(()) on success.
+    pub fn propose_job_transfer(
+        env: Env,
+        employer: Address,
+        job_id: u128,
+        new_employer: Address,
+    ) -> Result<(), SchedulerError> {
+        require_initialized(&env)?;
+        employer.require_auth();
+
+        let job = read_job(&env, job_id)?;
+
+        if job.employer != employer {
+            return Err(SchedulerError::NotEmployer);
+        }
+        if job.status == JobStatus::Cancelled {
+            return Err(SchedulerError::AlreadyCancelled);
+        }
+
+        let key = StorageKey::PendingTransfer(job_id);
+        if new_employer == employer {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &new_employer);
+        }
+
+        env.events().publish(
+            ("job_transfer_proposed", job_id),
+            JobTransferProposedEvent {
+                job_id,
+                employer,
+                new_employer,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// @notice Accepts a pending job transfer, making the caller its employer.
+    /// @dev Moves the job from the old employer's index to the end of the new
+    ///      one's. Status, schedule, escrow balance and execution history are
+    ///      unchanged; from here on only `new_employer` may manage the job and
+    ///      receives refunds and withdrawals.
+    /// @param new_employer Address named in `propose_job_transfer`. Must
+    ///        authenticate.
+    /// @param job_id       Sequential job identifier.
+    /// @return Ok(()) on success; `Err(NoPendingTransfer)` if the job has no
+    ///         proposal for `new_employer`.
+    /// @security The old employer loses all rights over the job immediately.
+    pub fn accept_job_transfer(
+        env: Env,
+        new_employer: Address,
+        job_id: u128,
+    ) -> Result<(), SchedulerError> {
+        require_initialized(&env)?;
+        new_employer.require_auth();
+
+        let mut job = read_job(&env, job_id)?;
+
+        let key = StorageKey::PendingTransfer(job_id);
+        let proposed: Option<Address> = env.storage().persistent().get(&key);
+        if proposed != Some(new_employer.clone()) {
+            return Err(SchedulerError::NoPendingTransfer);
+        }
+        if job.status == JobStatus::Cancelled {
+            return Err(SchedulerError::AlreadyCancelled);
+        }
+        env.storage().persistent().remove(&key);
+
+        let old_employer = job.employer.clone();
+        job.employer = new_employer.clone();
+        write_job(&env, &job);
+
+        let mut old_jobs = read_employer_jobs(&env, &old_employer);
+        if let Some(pos) = old_jobs.first_index_of(job_id) {
+            old_jobs.remove(pos);
+        }
+        write_employer_jobs(&env, &old_employer, &old_jobs);
+
+        let mut new_jobs = read_employer_jobs(&env, &new_employer);
+        new_jobs.push_back(job_id);
+        write_employer_jobs(&env, &new_employer, &new_jobs);
+
+        env.events().publish(
+            ("job_transferred", job_id),
+            JobTransferredEvent {
+                job_id,
+                old_employer,
+                new_employer,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
         Ok(())
     }
 
     // ── Execution ─────────────────────────────────────────────────────────────
 
-    /// @notice Processes due payments across all registered jobs.
+    /// @notice Processes due payments across all active jobs.
     /// @dev Permissionless — any caller may invoke this function (keeper, cron
     ///      service, or any Stellar account). Processes at most `max_jobs` jobs
     ///      per call to bound ledger resource consumption.
     ///
-    ///      For each `Active` job whose `next_scheduled_time <= now`:
-    ///      * If the scheduler's escrow balance covers `amount`:
+    ///      Only the active-jobs index is scanned, in ascending id order, so
+    ///      paused and terminal jobs cost nothing no matter how many exist.
+    ///      Jobs leave the index when they complete or fail.
+    ///
+    ///      A rotating cursor is persisted between calls: each call starts
+    ///      after the last job examined by the previous one and wraps around
+    ///      to the lowest id, so when more jobs are due than `max_jobs`,
+    ///      successive calls cover all of them instead of starving later ids.
+    ///
+    ///      An `Active` job whose `end_time` has been reached becomes
+    ///      `Completed` without executing and emits `job_completed`.
+    ///
+    ///      For each other `Active` job whose `next_scheduled_time <= now`:
+    ///      * If the job's own escrow balance covers `amount`:
+    ///        - Split jobs pay each portion returned by the splitter's
+    ///          `compute_split`, multi-recipient jobs pay every listed
+    ///          recipient, and both emit `job_recipient_paid` per transfer.
+    ///          Other jobs pay `recipient`.
     ///        - State is written before the transfer (state-before-interaction).
+    ///        - `amount` is deducted from the job balance.
     ///        - `executions` is incremented; `retry_count` is reset to 0.
-    ///        - `next_scheduled_time` is advanced by `interval_seconds`.
+    ///        - `next_scheduled_time` is advanced by `interval_seconds`, from
+    ///          `now` or, for catch-up jobs, from the previous due time.
     ///        - If `max_executions` is reached, status becomes `Completed`.
-    ///        - Emits `job_executed`.
-    ///      * If the escrow balance is insufficient:
+    ///        - Emits `job_executed`, plus `job_completed` on completion.
+    ///      * If the job balance is insufficient, or a split job's splitter call
+    ///        fails or returns portions that do not add up to the amount:
     ///        - `retry_count` is incremented.
     ///        - If `retry_count > max_retries`, status becomes `Failed`.
-    ///        - Otherwise `next_scheduled_time` is advanced and the job retries.
+    ///        - Otherwise the job retries after `retry_delay_seconds`, one
+    ///          interval by default. Catch-up jobs keep the unpaid period
+    ///          owed and set `retry_after` instead of moving
+    ///          `next_scheduled_time`.
     ///        - Emits `job_failed`.
     ///
+    ///      For `PercentOfBalance` jobs the amount is computed from the job
+    ///      balance at execution time. If it is below the job's minimum
+    ///      (`amount`), the period is skipped: `next_scheduled_time` advances,
+    ///      `executions` and `retry_count` are unchanged, and `job_skipped` is
+    ///      emitted.
+    ///
+    ///      Catch-up jobs are paid once per elapsed interval in the same call,
+    ///      each payment counting against `max_jobs`. If the budget runs out
+    ///      first, the cursor is left so the next call resumes with that job.
+    ///      Missed periods starting before `end_time` are still paid.
+    ///
     /// @param max_jobs Maximum number of jobs to evaluate in this call.
-    ///                 Pass a small value (e.g. 10–50) to stay within ledger limits.
-    /// @return Number of jobs that were actually evaluated (not necessarily paid).
-    pub fn process_due_payments(env: Env, max_jobs: u32) -> u32 {
-        if require_initialized(&env).is_err() {
-            return 0;
-        }
-
-        let now = env.ledger().timestamp();
-        let mut processed: u32 = 0;
-
-        let highest_id = env
+    ///                 Pass a small value (e.g. 10–20) to stay within ledger limits.
+    /// @return `ProcessResult` with the number of jobs that were actually
+    ///         evaluated (not necessarily paid) and the last job id examined,
+    ///         so keepers can chain calls.
+    pub fn process_due_payments(env: Env, max_jobs: u32) -> ProcessResult {
+        let cursor = env
             .storage()
             .persistent()
-            .get::<_, u128>(&StorageKey::NextJobId)
+            .get::<_, u128>(&StorageKey::ProcessCursor)
             .unwrap_or(0);
+        let mut result = ProcessResult {
+            processed: 0,
+            last_job_id: cursor,
+        };
 
-        if highest_id == 0 || max_jobs == 0 {
-            return 0;
+        if require_initialized(&env).is_err() || max_jobs == 0 {
+            return result;
         }
 
-        let mut job_id: u128 = 1;
-        while job_id <= highest_id && processed < max_jobs {
-            if let Some(job) = env
-                .storage()
-                .persistent()
-                .get::<_, PaymentJob>(&StorageKey::Job(job_id))
-            {
-                if job.status == JobStatus::Active && now >= job.next_scheduled_time {
-                    let mut job_mut = job;
-                    let token_client = token::Client::new(&env, &job_mut.token);
-                    let balance = token_client.balance(&env.current_contract_address());
-
-                    if balance >= job_mut.amount {
-                        // Checks-effects-interactions:
-                        // commit job progress before transfer so reentrant
-                        // callbacks cannot re-execute the same due payment.
-                        job_mut.executions = job_mut.executions.saturating_add(1);
-                        job_mut.retry_count = 0;
-                        job_mut.next_scheduled_time = now.saturating_add(job_mut.interval_seconds);
-
-                        if let Some(max_exec) = job_mut.max_executions {
-                            if job_mut.executions >= max_exec {
-                                job_mut.status = JobStatus::Completed;
-                            }
-                        }
-
-                        // State-before-interaction: persist before token transfer.
-                        write_job(&env, &job_mut);
-
-                        token_client.transfer(
-                            &env.current_contract_address(),
-                            &job_mut.recipient,
-                            &job_mut.amount,
-                        );
-
-                        env.events().publish(
-                            ("job_executed", job_mut.id),
-                            JobExecutedEvent {
-                                job_id: job_mut.id,
-                                execution_index: job_mut.executions,
-                                amount: job_mut.amount,
-                            },
-                        );
-                    } else {
-                        // Insufficient funds: schedule retry or mark failed.
-                        job_mut.retry_count = job_mut.retry_count.saturating_add(1);
-
-                        if job_mut.retry_count > job_mut.max_retries {
-                            job_mut.status = JobStatus::Failed;
-                        } else {
-                            job_mut.next_scheduled_time =
-                                now.saturating_add(job_mut.interval_seconds);
-                        }
-
-                        write_job(&env, &job_mut);
-
-                        env.events().publish(
-                            ("job_failed", job_mut.id),
-                            JobFailedEvent {
-                                job_id: job_mut.id,
-                                retry_count: job_mut.retry_count,
-                                max_retries: job_mut.max_retries,
-                            },
-                        );
-                    }
-                    processed = processed.saturating_add(1);
+        let now = env.ledger().timestamp();
+        let ids = read_active_jobs(&env);
+        let start = match ids.binary_search(cursor) {
+            Ok(pos) => pos + 1,
+            Err(pos) => pos,
+        };
+
+        for offset in 0..ids.len() {
+            if result.processed >= max_jobs {
+                break;
+            }
+            let job_id = ids.get((start + offset) % ids.len()).unwrap();
+            result.last_job_id = job_id;
+            let Ok(mut job) = read_job(&env, job_id) else {
+                continue;
+            };
+            if job.status != JobStatus::Active {
+                continue;
+            }
+
+            // Non-catch-up jobs get at most one attempt per call; catch-up
+            // jobs keep paying missed periods until caught up or out of budget.
+            loop {
+                if term_over(&job, now) {
+                    // The term is over: complete without paying another period.
+                    job.status = JobStatus::Completed;
+                    write_job(&env, &job);
+                    remove_active_job(&env, job.id);
+                    publish_job_completed(&env, &job, now);
+                    result.processed = result.processed.saturating_add(1);
+                    break;
                 }
+                if !is_due(&job, now) {
+                    break;
+                }
+                job = attempt_payment(&env, job, now);
+                result.processed = result.processed.saturating_add(1);
+                if !job.catch_up || job.status != JobStatus::Active || result.processed >= max_jobs
+                {
+                    break;
+                }
+            }
+
+            // A catch-up job that still owes periods is resumed first next call.
+            if job.status == JobStatus::Active && (is_due(&job, now) || term_over(&job, now)) {
+                result.last_job_id = ids
+                    .get((start + offset + ids.len() - 1) % ids.len())
+                    .unwrap();
             }
+        }
 
-            job_id = job_id.saturating_add(1);
+        if result.last_job_id != cursor {
+            env.storage()
+                .persistent()
+                .set(&StorageKey::ProcessCursor, &result.last_job_id);
         }
 
-        processed
+        result
     }
 
     // ── Funding ───────────────────────────────────────────────────────────────
 
-    /// @notice Deposits tokens from `from` into the scheduler contract's escrow.
-    /// @dev The token is inferred from the job record. Multiple calls accumulate.
-    ///      Any party may fund a job, not only the employer.
+    /// @notice Deposits tokens from `from` into the escrow balance of a job.
+    /// @dev The token is inferred from the job record. Multiple calls accumulate
+    ///      in the job's balance, which only that job's executions draw on.
+    ///      Any party may fund a job, not only the employer. Cancelled jobs
+    ///      return `Err(AlreadyCancelled)` so no new funds are sent to a job
+    ///      that will never pay out.
     /// @param from     Funding address. Must authenticate.
     /// @param job_id   Job whose token should be funded.
     /// @param amount   Positive token amount to transfer.
@@ -664,8 +1587,63 @@ impl PaymentSchedulerContract {
         }
 
         let job = read_job(&env, job_id)?;
+        if job.status == JobStatus::Cancelled {
+            return Err(SchedulerError::AlreadyCancelled);
+        }
         let token_client = token::Client::new(&env, &job.token);
-        token_client.transfer(&from, &env.current_contract_address(), &amount);
+        token_client.transfer(&from, env.current_contract_address(), &amount);
+        write_job_balance(&env, job_id, read_job_balance(&env, job_id) + amount);
+
+        env.events().publish(
+            ("job_funded", job_id),
+            JobFundedEvent {
+                job_id,
+                from,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// @notice Returns surplus escrow from a job's balance to its employer.
+    /// @dev Allowed in any status, e.g. after an over-funded job completes.
+    ///      The balance is reduced before the transfer
+    ///      (state-before-interaction).
+    /// @param employer Current employer of the job. Must authenticate.
+    /// @param job_id   Job whose balance is withdrawn.
+    /// @param amount   Positive token amount, at most the job balance.
+    /// @return Ok(()) on success, `Err(InsufficientJobBalance)` if `amount`
+    ///         exceeds the job balance.
+    pub fn withdraw_job_funds(
+        env: Env,
+        employer: Address,
+        job_id: u128,
+        amount: i128,
+    ) -> Result<(), SchedulerError> {
+        require_initialized(&env)?;
+        employer.require_auth();
+
+        if amount <= 0 {
+            return Err(SchedulerError::AmountNotPositive);
+        }
+
+        let job = read_job(&env, job_id)?;
+        if job.employer != employer {
+            return Err(SchedulerError::NotEmployer);
+        }
+        let balance = read_job_balance(&env, job_id);
+        if amount > balance {
+            return Err(SchedulerError::InsufficientJobBalance);
+        }
+
+        write_job_balance(&env, job_id, balance - amount);
+        token::Client::new(&env, &job.token).transfer(
+            &env.current_contract_address(),
+            &employer,
+            &amount,
+        );
 
         Ok(())
     }
@@ -679,6 +1657,48 @@ impl PaymentSchedulerContract {
         env.storage().persistent().get(&StorageKey::Job(job_id))
     }
 
+    /// @notice Returns a page of the jobs created by an employer.
+    /// @dev Jobs are returned in creation order and include completed, failed
+    ///      and cancelled jobs so the full history stays enumerable.
+    /// @param employer Employer whose jobs are listed.
+    /// @param start    Zero-based offset into the employer's job list.
+    /// @param limit    Page size, capped at `MAX_JOBS_PAGE_SIZE`.
+    /// @return Up to `limit` jobs; empty when `start` is past the end.
+    pub fn get_jobs_by_employer(
+        env: Env,
+        employer: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<PaymentJob> {
+        let ids = read_employer_jobs(&env, &employer);
+        let end = start
+            .saturating_add(limit.min(MAX_JOBS_PAGE_SIZE))
+            .min(ids.len());
+
+        let mut jobs = Vec::new(&env);
+        for i in start..end {
+            if let Ok(job) = read_job(&env, ids.get(i).unwrap()) {
+                jobs.push_back(job);
+            }
+        }
+        jobs
+    }
+
+    /// @notice Returns the number of jobs ever created by an employer.
+    /// @param employer Employer whose jobs are counted.
+    /// @return Length of the employer's job index, including terminal jobs.
+    pub fn get_job_count_for_employer(env: Env, employer: Address) -> u32 {
+        read_employer_jobs(&env, &employer).len()
+    }
+
+    /// @notice Returns the escrow balance attributed to a job.
+    /// @param job_id The sequential identifier returned by `create_job`.
+    /// @return The funded amount not yet paid out or withdrawn; `0` for
+    ///         unknown jobs.
+    pub fn get_job_balance(env: Env, job_id: u128) -> i128 {
+        read_job_balance(&env, job_id)
+    }
+
     /// @notice Returns the contract owner address.
     /// @return `Some(Address)` after initialization, `None` before.
     pub fn get_owner(env: Env) -> Option<Address> {
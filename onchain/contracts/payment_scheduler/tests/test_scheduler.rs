@@ -1,33 +1,62 @@
 //! Comprehensive tests for the PaymentScheduler contract.
 //!
 //! Coverage targets:
-//! * Initialization — happy path, double-init guard
+//! * Initialization — happy path, double-init guard, calls before init return
+//!   `NotInitialized`
 //! * `create_job` — happy path, zero amount, zero interval (recurring), one-time
 //!   zero interval, duplicate schedule rejection, multiple jobs get unique IDs
 //! * `create_job` idempotency — same parameters rejected, different employer allowed,
 //!   different token allowed (same other params)
 //! * `cancel_job` — active/paused cancellable, already cancelled, terminal (completed/failed)
-//!   not cancellable, wrong employer rejected
-//! * `pause_job` / `resume_job` — happy path, wrong employer, wrong status
-//! * `fund_job` — increases scheduler balance, job not found, wrong amount
+//!   not cancellable, wrong employer rejected, mid-stream cancellation stops payments and
+//!   rejects resume/pause/fund
+//! * `pause_job` / `resume_job` — happy path, wrong employer, unknown job, wrong status
+//! * Ownership transfer — accepted proposal moves the job between employer
+//!   indexes with history intact, old employer loses pause rights, proposal
+//!   must match the caller, withdrawal by self-proposal, cancelled jobs
+//! * Lifecycle events — `job_funded`, `job_paused`, `job_resumed`, `job_completed`,
+//!   `job_cancelled` (with refund) carry the ledger timestamp
+//! * `update_job` — amount raised mid-stream pays the new amount, interval change
+//!   keeps the pending due time, validation, paused allowed, terminal rejected
+//! * `fund_job` — increases scheduler and job balance, job not found, wrong amount
+//! * Per-job escrow — a job cannot spend another job's funding in the same token,
+//!   direct transfers are not credited, `withdraw_job_funds` limits and auth,
+//!   refund of the remaining balance on cancellation
+//! * `end_time` — job completes at the boundary without paying an extra period
+//!   despite a far `max_executions`; `end_time <= start_time` rejected
+//! * `catch_up` — missed intervals paid on the original schedule (vs. default),
+//!   resumed across budget-limited calls, failed retry keeps the period owed
+//!   and is gated by `retry_after`
+//! * `AmountKind::PercentOfBalance` — 1000 at 5000 bps pays 500 then 250, skips
+//!   below the minimum without failing, resumes after top-up; bps bounds
+//! * Multi-recipient jobs — three recipients over two intervals with
+//!   per-recipient events, an underfunded interval pays no one and retries,
+//!   list validation, amount locked by the list
 //! * `process_due_payments` — empty scheduler, max_jobs=0, max_jobs bound,
 //!   recurring execution cycles & completion, one-time payment, pause prevents
 //!   execution, resume after pause, cancelled job skipped, retry on insufficient
-//!   funds, retry exhaustion → Failed, state-before-interaction (job persisted
-//!   before transfer)
+//!   funds, retry exhaustion → Failed, `retry_delay_seconds` retries a monthly
+//!   job after minutes, state-before-interaction (job persisted before
+//!   transfer), completed jobs are not rescanned (200 jobs, 195 completed),
+//!   rotating cursor covers 10 due jobs exactly once across three calls
 //! * `get_job_id_by_schedule` — lookup by deterministic ID
+//! * `get_jobs_by_employer` / `get_job_count_for_employer` — interleaved employers,
+//!   pagination, page size cap, terminal jobs retained
 //! * `get_owner` / `get_job` view helpers
 
 #![cfg(test)]
 
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    testutils::{Address as _, Events, Ledger},
     token::{Client as TokenClient, StellarAssetClient},
-    Address, Env,
+    Address, Env, String, TryFromVal, Val, Vec,
 };
 
 use payment_scheduler::{
-    JobStatus, PaymentJob, PaymentSchedulerContract, PaymentSchedulerContractClient, SchedulerError,
+    AmountKind, JobCancelledEvent, JobCompletedEvent, JobExecutedEvent, JobFailedEvent,
+    JobFundedEvent, JobOptions, JobPausedEvent, JobRecipientPaidEvent, JobResumedEvent,
+    JobSkippedEvent, JobStatus, JobTransferredEvent, PaymentJob, PaymentSchedulerContract,
+    PaymentSchedulerContractClient, SchedulerError, MAX_BPS, MAX_JOBS_PAGE_SIZE, MAX_RECIPIENTS,
 };
 
 // ─── Fixtures ─────────────────────────────────────────────────────────────────
@@ -45,11 +74,20 @@ fn register_contract(env: &Env) -> (Address, PaymentSchedulerContractClient<'sta
     (id, client)
 }
 
+#[allow(deprecated)]
 fn create_token_contract<'a>(env: &Env, admin: &Address) -> TokenClient<'a> {
     let token_addr = env.register_stellar_asset_contract(admin.clone());
     TokenClient::new(env, &token_addr)
 }
 
+/// Decodes the data of the most recent event, asserting it was published by
+/// the scheduler.
+fn last_event<T: TryFromVal<Env, Val>>(env: &Env, scheduler_id: &Address) -> T {
+    let (contract, _topics, data) = env.events().all().last().unwrap();
+    assert_eq!(&contract, scheduler_id);
+    T::try_from_val(env, &data).unwrap()
+}
+
 /// Convenience: initialize the scheduler and return (scheduler_id, client).
 fn setup(env: &Env) -> (Address, PaymentSchedulerContractClient<'static>) {
     let (id, client) = register_contract(env);
@@ -84,6 +122,29 @@ fn test_double_init_rejected() {
     );
 }
 
+#[test]
+fn test_calls_before_init_rejected() {
+    let env = create_env();
+    let (_, client) = register_contract(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let result = client.try_create_job(
+        &employer, &recipient, &token, &100i128, &10u64, &0u64, &None, &1u32,
+    );
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::NotInitialized);
+
+    let result = client.try_pause_job(&employer, &1u128);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::NotInitialized);
+
+    let result = client.try_fund_job(&employer, &1u128, &100i128);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::NotInitialized);
+
+    // The permissionless crank is a no-op rather than an error.
+    assert_eq!(client.process_due_payments(&10u32).processed, 0);
+}
+
 // ─── create_job ───────────────────────────────────────────────────────────────
 
 #[test]
@@ -373,7 +434,7 @@ fn test_cancel_wrong_employer_rejected() {
 #[test]
 fn test_cancel_completed_job_rejected() {
     let env = create_env();
-    let (scheduler_id, client) = setup(&env);
+    let (_, client) = setup(&env);
     let employer = Address::generate(&env);
     let recipient = Address::generate(&env);
     let token_admin = Address::generate(&env);
@@ -381,7 +442,6 @@ fn test_cancel_completed_job_rejected() {
     let asset_admin = StellarAssetClient::new(&env, &token.address);
 
     asset_admin.mint(&employer, &100i128);
-    token.transfer(&employer, &scheduler_id, &100i128);
 
     env.ledger().with_mut(|li| li.timestamp = 0);
 
@@ -395,6 +455,7 @@ fn test_cancel_completed_job_rejected() {
         &Some(1u32),
         &0u32,
     );
+    client.fund_job(&employer, &job_id, &100i128);
 
     client.process_due_payments(&10u32);
     assert_eq!(client.get_job(&job_id).unwrap().status, JobStatus::Completed);
@@ -419,7 +480,6 @@ fn test_pause_and_resume_job() {
     let asset_admin = StellarAssetClient::new(&env, &token.address);
 
     asset_admin.mint(&employer, &500i128);
-    token.transfer(&employer, &scheduler_id, &500i128);
 
     env.ledger().with_mut(|li| li.timestamp = 0);
 
@@ -433,19 +493,46 @@ fn test_pause_and_resume_job() {
         &None,
         &1u32,
     );
+    env.ledger().with_mut(|li| li.timestamp = 5);
+    client.fund_job(&employer, &job_id, &500i128);
+    assert_eq!(
+        last_event::<JobFundedEvent>(&env, &scheduler_id),
+        JobFundedEvent {
+            job_id,
+            from: employer.clone(),
+            amount: 500,
+            timestamp: 5,
+        }
+    );
 
     // Pause immediately
     client.pause_job(&employer, &job_id);
+    assert_eq!(
+        last_event::<JobPausedEvent>(&env, &scheduler_id),
+        JobPausedEvent {
+            job_id,
+            employer: employer.clone(),
+            timestamp: 5,
+        }
+    );
     assert_eq!(client.get_job(&job_id).unwrap().status, JobStatus::Paused);
 
     // Paused job should not be processed
     env.ledger().with_mut(|li| li.timestamp = 100);
-    let processed = client.process_due_payments(&10u32);
+    let processed = client.process_due_payments(&10u32).processed;
     assert_eq!(processed, 0);
     assert_eq!(token.balance(&recipient), 0i128);
 
     // Resume and process
     client.resume_job(&employer, &job_id);
+    assert_eq!(
+        last_event::<JobResumedEvent>(&env, &scheduler_id),
+        JobResumedEvent {
+            job_id,
+            employer: employer.clone(),
+            timestamp: 100,
+        }
+    );
     let _ = client.process_due_payments(&10u32);
     let job = client.get_job(&job_id).unwrap();
     assert_eq!(job.executions, 1);
@@ -489,91 +576,181 @@ fn test_resume_non_paused_job_rejected() {
     assert_eq!(result.unwrap_err().unwrap(), SchedulerError::JobNotPaused);
 }
 
-// ─── fund_job ─────────────────────────────────────────────────────────────────
+#[test]
+fn test_pause_resume_wrong_employer_or_unknown_job_rejected() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let job_id = client.create_job(
+        &employer, &recipient, &token, &100i128, &10u64, &0u64, &None, &1u32,
+    );
+
+    let result = client.try_pause_job(&attacker, &job_id);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::NotEmployer);
+
+    client.pause_job(&employer, &job_id);
+    let result = client.try_resume_job(&attacker, &job_id);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::NotEmployer);
+
+    let result = client.try_pause_job(&employer, &99u128);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::JobNotFound);
+    let result = client.try_resume_job(&employer, &99u128);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::JobNotFound);
+}
+
+// ─── Ownership transfer ───────────────────────────────────────────────────────
 
 #[test]
-fn test_fund_job_increases_scheduler_balance() {
+fn test_job_transfer_moves_ownership() {
     let env = create_env();
     let (scheduler_id, client) = setup(&env);
-    let employer = Address::generate(&env);
+    let old_employer = Address::generate(&env);
+    let new_employer = Address::generate(&env);
     let recipient = Address::generate(&env);
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
-    let asset_admin = StellarAssetClient::new(&env, &token.address);
+    StellarAssetClient::new(&env, &token.address).mint(&old_employer, &300i128);
 
-    asset_admin.mint(&employer, &300i128);
+    env.ledger().with_mut(|li| li.timestamp = 0);
 
     let job_id = client.create_job(
-        &employer,
+        &old_employer,
         &recipient,
         &token.address,
         &100i128,
         &10u64,
         &0u64,
         &None,
-        &1u32,
+        &0u32,
+    );
+    let kept = client.create_job(
+        &old_employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &5u64,
+        &None,
+        &0u32,
     );
+    client.fund_job(&old_employer, &job_id, &300i128);
+    client.process_due_payments(&1u32);
 
-    client.fund_job(&employer, &job_id, &200i128);
-    assert_eq!(token.balance(&scheduler_id), 200i128);
-}
+    client.propose_job_transfer(&old_employer, &job_id, &new_employer);
+    // Nothing changes until the proposal is accepted.
+    assert_eq!(client.get_job(&job_id).unwrap().employer, old_employer);
 
-// ─── process_due_payments ─────────────────────────────────────────────────────
+    env.ledger().with_mut(|li| li.timestamp = 7);
+    client.accept_job_transfer(&new_employer, &job_id);
+    assert_eq!(
+        last_event::<JobTransferredEvent>(&env, &scheduler_id),
+        JobTransferredEvent {
+            job_id,
+            old_employer: old_employer.clone(),
+            new_employer: new_employer.clone(),
+            timestamp: 7,
+        }
+    );
 
-#[test]
-fn test_process_no_jobs_returns_zero() {
-    let env = create_env();
-    let (_, client) = setup(&env);
-    let result = client.process_due_payments(&10u32);
-    assert_eq!(result, 0);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.employer, new_employer);
+    assert_eq!(job.executions, 1);
+    assert_eq!(client.get_job_balance(&job_id), 200i128);
+    let job_ids = |employer: &Address| -> std::vec::Vec<u128> {
+        client
+            .get_jobs_by_employer(employer, &0u32, &10u32)
+            .iter()
+            .map(|job| job.id)
+            .collect()
+    };
+    assert_eq!(job_ids(&old_employer), [kept]);
+    assert_eq!(job_ids(&new_employer), [job_id]);
+
+    let result = client.try_pause_job(&old_employer, &job_id);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::NotEmployer);
+    client.pause_job(&new_employer, &job_id);
+    assert_eq!(client.get_job(&job_id).unwrap().status, JobStatus::Paused);
+
+    // A completed proposal cannot be accepted twice.
+    let result = client.try_accept_job_transfer(&new_employer, &job_id);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::NoPendingTransfer
+    );
 }
 
 #[test]
-fn test_process_max_jobs_bound() {
+fn test_job_transfer_requires_matching_proposal() {
     let env = create_env();
-    let (scheduler_id, client) = setup(&env);
+    let (_, client) = setup(&env);
     let employer = Address::generate(&env);
-    let token_admin = Address::generate(&env);
-    let token = create_token_contract(&env, &token_admin);
-    let asset_admin = StellarAssetClient::new(&env, &token.address);
+    let proposed = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = Address::generate(&env);
 
-    asset_admin.mint(&employer, &1000i128);
-    token.transfer(&employer, &scheduler_id, &1000i128);
+    let job_id = client.create_job(
+        &employer, &recipient, &token, &100i128, &10u64, &0u64, &None, &1u32,
+    );
 
-    env.ledger().with_mut(|li| li.timestamp = 0);
+    let result = client.try_accept_job_transfer(&proposed, &job_id);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::NoPendingTransfer
+    );
 
-    // Create 5 jobs all due at t=0
-    for _ in 0..5u32 {
-        let recipient = Address::generate(&env);
-        client.create_job(
-            &employer,
-            &recipient,
-            &token.address,
-            &100i128,
-            &10u64,
-            &0u64,
-            &None,
-            &0u32,
-        );
-    }
+    let result = client.try_propose_job_transfer(&stranger, &job_id, &stranger);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::NotEmployer);
 
-    // Process with max_jobs=3 — only 3 should be evaluated
-    let processed = client.process_due_payments(&3u32);
-    assert_eq!(processed, 3);
+    client.propose_job_transfer(&employer, &job_id, &proposed);
+    let result = client.try_accept_job_transfer(&stranger, &job_id);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::NoPendingTransfer
+    );
+
+    // Proposing the current employer withdraws the proposal.
+    client.propose_job_transfer(&employer, &job_id, &employer);
+    let result = client.try_accept_job_transfer(&proposed, &job_id);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::NoPendingTransfer
+    );
+
+    client.propose_job_transfer(&employer, &job_id, &proposed);
+    client.cancel_job(&employer, &job_id);
+    let result = client.try_accept_job_transfer(&proposed, &job_id);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::AlreadyCancelled
+    );
+    let result = client.try_propose_job_transfer(&employer, &job_id, &proposed);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::AlreadyCancelled
+    );
+
+    let result = client.try_accept_job_transfer(&proposed, &999u128);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::JobNotFound);
 }
 
+// ─── update_job ───────────────────────────────────────────────────────────────
+
 #[test]
-fn test_basic_recurring_job_execution() {
+fn test_update_amount_mid_stream() {
     let env = create_env();
-    let (scheduler_id, client) = setup(&env);
+    let (_, client) = setup(&env);
     let employer = Address::generate(&env);
     let recipient = Address::generate(&env);
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
     let asset_admin = StellarAssetClient::new(&env, &token.address);
 
-    asset_admin.mint(&employer, &300i128);
-    token.transfer(&employer, &scheduler_id, &300i128);
+    asset_admin.mint(&employer, &500i128);
 
     env.ledger().with_mut(|li| li.timestamp = 0);
 
@@ -584,37 +761,96 @@ fn test_basic_recurring_job_execution() {
         &100i128,
         &10u64,
         &0u64,
-        &Some(3u32),
+        &None,
         &1u32,
     );
+    client.fund_job(&employer, &job_id, &500i128);
+    client.process_due_payments(&10u32);
+    assert_eq!(token.balance(&recipient), 100i128);
 
-    // Execution 1 at t=0
-    let processed = client.process_due_payments(&10u32);
-    assert_eq!(processed, 1);
+    // Raise the salary and stretch the interval; the pending execution at
+    // t=10 keeps its due time.
+    client.update_job(&employer, &job_id, &Some(150i128), &Some(20u64));
     let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.amount, 150);
+    assert_eq!(job.interval_seconds, 20);
+    assert_eq!(job.next_scheduled_time, 10);
     assert_eq!(job.executions, 1);
-    assert_eq!(job.status, JobStatus::Active);
-    assert_eq!(token.balance(&recipient), 100i128);
 
-    // Execution 2 at t=10
     env.ledger().with_mut(|li| li.timestamp = 10);
     client.process_due_payments(&10u32);
-    assert_eq!(client.get_job(&job_id).unwrap().executions, 2);
-    assert_eq!(token.balance(&recipient), 200i128);
+    assert_eq!(token.balance(&recipient), 250i128);
+    assert_eq!(client.get_job(&job_id).unwrap().next_scheduled_time, 30);
 
-    // Execution 3 at t=20 — completes the job
+    // Not due yet under the new interval.
     env.ledger().with_mut(|li| li.timestamp = 20);
-    client.process_due_payments(&10u32);
-    let job = client.get_job(&job_id).unwrap();
-    assert_eq!(job.executions, 3);
-    assert_eq!(job.status, JobStatus::Completed);
-    assert_eq!(token.balance(&recipient), 300i128);
+    assert_eq!(client.process_due_payments(&10u32).processed, 0);
+
+    // Only the interval changes when the amount is omitted.
+    client.update_job(&employer, &job_id, &None, &Some(5u64));
+    assert_eq!(client.get_job(&job_id).unwrap().amount, 150);
 }
 
 #[test]
-fn test_one_time_payment() {
+fn test_update_job_rejections() {
     let env = create_env();
-    let (scheduler_id, client) = setup(&env);
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let job_id = client.create_job(
+        &employer, &recipient, &token, &100i128, &10u64, &0u64, &None, &1u32,
+    );
+
+    assert_eq!(
+        client
+            .try_update_job(&Address::generate(&env), &job_id, &Some(200i128), &None)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::NotEmployer
+    );
+    assert_eq!(
+        client
+            .try_update_job(&employer, &job_id, &Some(0i128), &None)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::AmountNotPositive
+    );
+    assert_eq!(
+        client
+            .try_update_job(&employer, &job_id, &None, &Some(0u64))
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::IntervalRequired
+    );
+    assert_eq!(
+        client
+            .try_update_job(&employer, &99u128, &Some(200i128), &None)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::JobNotFound
+    );
+
+    // Paused jobs can still be updated.
+    client.pause_job(&employer, &job_id);
+    client.update_job(&employer, &job_id, &Some(200i128), &None);
+    assert_eq!(client.get_job(&job_id).unwrap().amount, 200);
+
+    client.cancel_job(&employer, &job_id);
+    assert_eq!(
+        client
+            .try_update_job(&employer, &job_id, &Some(300i128), &None)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::AlreadyCancelled
+    );
+}
+
+#[test]
+fn test_update_completed_job_rejected() {
+    let env = create_env();
+    let (_, client) = setup(&env);
     let employer = Address::generate(&env);
     let recipient = Address::generate(&env);
     let token_admin = Address::generate(&env);
@@ -622,7 +858,6 @@ fn test_one_time_payment() {
     let asset_admin = StellarAssetClient::new(&env, &token.address);
 
     asset_admin.mint(&employer, &100i128);
-    token.transfer(&employer, &scheduler_id, &100i128);
 
     env.ledger().with_mut(|li| li.timestamp = 0);
 
@@ -634,25 +869,24 @@ fn test_one_time_payment() {
         &0u64,
         &0u64,
         &Some(1u32),
-        &1u32,
+        &0u32,
     );
+    client.fund_job(&employer, &job_id, &100i128);
+    client.process_due_payments(&10u32);
 
-    let processed = client.process_due_payments(&10u32);
-    assert_eq!(processed, 1);
-
-    let job = client.get_job(&job_id).unwrap();
-    assert_eq!(job.executions, 1);
-    assert_eq!(job.status, JobStatus::Completed);
-    assert_eq!(token.balance(&recipient), 100i128);
-
-    // Same timestamp second processing must not execute again.
-    let second = client.process_due_payments(&10u32);
-    assert_eq!(second, 0);
-    assert_eq!(token.balance(&recipient), 100i128);
+    assert_eq!(
+        client
+            .try_update_job(&employer, &job_id, &Some(200i128), &None)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::JobNotUpdatable
+    );
 }
 
+// ─── fund_job ─────────────────────────────────────────────────────────────────
+
 #[test]
-fn test_cancelled_job_skipped_by_processor() {
+fn test_fund_job_increases_scheduler_balance() {
     let env = create_env();
     let (scheduler_id, client) = setup(&env);
     let employer = Address::generate(&env);
@@ -661,10 +895,7 @@ fn test_cancelled_job_skipped_by_processor() {
     let token = create_token_contract(&env, &token_admin);
     let asset_admin = StellarAssetClient::new(&env, &token.address);
 
-    asset_admin.mint(&employer, &500i128);
-    token.transfer(&employer, &scheduler_id, &500i128);
-
-    env.ledger().with_mut(|li| li.timestamp = 0);
+    asset_admin.mint(&employer, &300i128);
 
     let job_id = client.create_job(
         &employer,
@@ -677,53 +908,1235 @@ fn test_cancelled_job_skipped_by_processor() {
         &1u32,
     );
 
-    client.cancel_job(&employer, &job_id);
-
-    // Even at t=100 the cancelled job must not be processed
-    env.ledger().with_mut(|li| li.timestamp = 100);
-    let processed = client.process_due_payments(&10u32);
-    assert_eq!(processed, 0);
-    assert_eq!(token.balance(&recipient), 0i128);
+    client.fund_job(&employer, &job_id, &200i128);
+    assert_eq!(token.balance(&scheduler_id), 200i128);
+    assert_eq!(client.get_job_balance(&job_id), 200i128);
 }
 
 #[test]
-fn test_insufficient_funds_then_retry_success() {
+fn test_fund_job_rejections() {
     let env = create_env();
-    let (scheduler_id, client) = setup(&env);
+    let (_, client) = setup(&env);
     let employer = Address::generate(&env);
     let recipient = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let job_id = client.create_job(
+        &employer, &recipient, &token, &100i128, &10u64, &0u64, &None, &1u32,
+    );
+
+    let result = client.try_fund_job(&employer, &99u128, &100i128);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::JobNotFound);
+
+    let result = client.try_fund_job(&employer, &job_id, &0i128);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::AmountNotPositive
+    );
+    let result = client.try_fund_job(&employer, &job_id, &-5i128);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::AmountNotPositive
+    );
+}
+
+#[test]
+fn test_job_cannot_spend_another_jobs_funding() {
+    let env = create_env();
+    let (scheduler_id, client) = setup(&env);
+    let employer_a = Address::generate(&env);
+    let employer_b = Address::generate(&env);
+    let recipient_a = Address::generate(&env);
+    let recipient_b = Address::generate(&env);
     let token_admin = Address::generate(&env);
     let token = create_token_contract(&env, &token_admin);
     let asset_admin = StellarAssetClient::new(&env, &token.address);
 
-    // Fund 50 but job needs 100
-    asset_admin.mint(&employer, &50i128);
-    token.transfer(&employer, &scheduler_id, &50i128);
+    asset_admin.mint(&employer_a, &300i128);
+    asset_admin.mint(&employer_b, &50i128);
 
     env.ledger().with_mut(|li| li.timestamp = 0);
 
-    let job_id = client.create_job(
-        &employer,
-        &recipient,
+    let job_a = client.create_job(
+        &employer_a,
+        &recipient_a,
         &token.address,
         &100i128,
         &10u64,
         &0u64,
-        &Some(1u32),
-        &2u32,
+        &None,
+        &1u32,
     );
-
-    // First attempt fails due to insufficient funds
-    let processed = client.process_due_payments(&5u32);
-    assert_eq!(processed, 1);
-    let mut job = client.get_job(&job_id).unwrap();
-    assert_eq!(job.retry_count, 1);
+    let job_b = client.create_job(
+        &employer_b,
+        &recipient_b,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+    );
+    client.fund_job(&employer_a, &job_a, &300i128);
+    client.fund_job(&employer_b, &job_b, &50i128);
+
+    // The scheduler holds 350 in the shared token, but job B only has 50.
+    assert_eq!(client.process_due_payments(&10u32).processed, 2);
+    assert_eq!(token.balance(&recipient_a), 100i128);
+    assert_eq!(token.balance(&recipient_b), 0i128);
+    assert_eq!(client.get_job(&job_b).unwrap().retry_count, 1);
+    assert_eq!(client.get_job_balance(&job_a), 200i128);
+    assert_eq!(client.get_job_balance(&job_b), 50i128);
+    assert_eq!(token.balance(&scheduler_id), 250i128);
+
+    // Direct transfers to the scheduler are not credited to any job.
+    asset_admin.mint(&token_admin, &100i128);
+    token.transfer(&token_admin, &scheduler_id, &100i128);
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    client.process_due_payments(&10u32);
+    assert_eq!(token.balance(&recipient_b), 0i128);
+    assert_eq!(client.get_job(&job_b).unwrap().retry_count, 2);
+}
+
+#[test]
+fn test_withdraw_job_funds() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &300i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &Some(1u32),
+        &1u32,
+    );
+    client.fund_job(&employer, &job_id, &300i128);
+    client.process_due_payments(&10u32);
+    assert_eq!(client.get_job(&job_id).unwrap().status, JobStatus::Completed);
+
+    // The surplus of the completed job can be recovered.
+    client.withdraw_job_funds(&employer, &job_id, &150i128);
+    assert_eq!(token.balance(&employer), 150i128);
+    assert_eq!(client.get_job_balance(&job_id), 50i128);
+
+    assert_eq!(
+        client
+            .try_withdraw_job_funds(&employer, &job_id, &100i128)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::InsufficientJobBalance
+    );
+    assert_eq!(
+        client
+            .try_withdraw_job_funds(&Address::generate(&env), &job_id, &50i128)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::NotEmployer
+    );
+    assert_eq!(
+        client
+            .try_withdraw_job_funds(&employer, &job_id, &0i128)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::AmountNotPositive
+    );
+    client.withdraw_job_funds(&employer, &job_id, &50i128);
+    assert_eq!(client.get_job_balance(&job_id), 0i128);
+    assert_eq!(token.balance(&employer), 200i128);
+}
+
+// ─── Employer index ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_jobs_indexed_by_employer() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer_a = Address::generate(&env);
+    let employer_b = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    // Interleave creation: A, B, A, B, A.
+    let mut ids_a = std::vec::Vec::new();
+    let mut ids_b = std::vec::Vec::new();
+    for i in 0..5u64 {
+        let employer = if i % 2 == 0 { &employer_a } else { &employer_b };
+        let id = client.create_job(
+            employer,
+            &Address::generate(&env),
+            &token,
+            &100i128,
+            &10u64,
+            &i,
+            &None,
+            &1u32,
+        );
+        if i % 2 == 0 {
+            ids_a.push(id);
+        } else {
+            ids_b.push(id);
+        }
+    }
+
+    assert_eq!(client.get_job_count_for_employer(&employer_a), 3);
+    assert_eq!(client.get_job_count_for_employer(&employer_b), 2);
+    assert_eq!(
+        client.get_job_count_for_employer(&Address::generate(&env)),
+        0
+    );
+
+    let jobs_a = client.get_jobs_by_employer(&employer_a, &0u32, &10u32);
+    assert_eq!(jobs_a.len(), 3);
+    for (job, id) in jobs_a.iter().zip(ids_a.iter()) {
+        assert_eq!(job.id, *id);
+        assert_eq!(job.employer, employer_a);
+    }
+    let jobs_b = client.get_jobs_by_employer(&employer_b, &0u32, &10u32);
+    assert_eq!(jobs_b.len(), 2);
+    for (job, id) in jobs_b.iter().zip(ids_b.iter()) {
+        assert_eq!(job.id, *id);
+        assert_eq!(job.employer, employer_b);
+    }
+
+    // Pagination.
+    let page = client.get_jobs_by_employer(&employer_a, &1u32, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, ids_a[1]);
+    assert_eq!(
+        client
+            .get_jobs_by_employer(&employer_a, &3u32, &10u32)
+            .len(),
+        0
+    );
+
+    // Terminal jobs stay in the history.
+    client.cancel_job(&employer_a, &ids_a[0]);
+    assert_eq!(client.get_job_count_for_employer(&employer_a), 3);
+    assert_eq!(
+        client
+            .get_jobs_by_employer(&employer_a, &0u32, &1u32)
+            .get(0)
+            .unwrap()
+            .status,
+        JobStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_jobs_by_employer_page_size_capped() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    for i in 0..(MAX_JOBS_PAGE_SIZE as u64 + 5) {
+        client.create_job(
+            &employer,
+            &Address::generate(&env),
+            &token,
+            &100i128,
+            &10u64,
+            &i,
+            &None,
+            &1u32,
+        );
+    }
+
+    assert_eq!(
+        client
+            .get_jobs_by_employer(&employer, &0u32, &u32::MAX)
+            .len(),
+        MAX_JOBS_PAGE_SIZE
+    );
+    assert_eq!(
+        client
+            .get_jobs_by_employer(&employer, &MAX_JOBS_PAGE_SIZE, &u32::MAX)
+            .len(),
+        5
+    );
+}
+
+// ─── process_due_payments ─────────────────────────────────────────────────────
+
+#[test]
+fn test_process_no_jobs_returns_zero() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let result = client.process_due_payments(&10u32).processed;
+    assert_eq!(result, 0);
+}
+
+#[test]
+fn test_process_max_jobs_bound() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &1000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    // Create 5 funded jobs all due at t=0
+    for _ in 0..5u32 {
+        let recipient = Address::generate(&env);
+        let job_id = client.create_job(
+            &employer,
+            &recipient,
+            &token.address,
+            &100i128,
+            &10u64,
+            &0u64,
+            &None,
+            &0u32,
+        );
+        client.fund_job(&employer, &job_id, &200i128);
+    }
+
+    // Process with max_jobs=3 — only 3 should be evaluated
+    let processed = client.process_due_payments(&3u32).processed;
+    assert_eq!(processed, 3);
+}
+
+#[test]
+fn test_cursor_resumes_where_previous_call_stopped() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &1_000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    // 10 due jobs with an interval long enough that none is due twice.
+    for i in 0..10u64 {
+        let job_id = client.create_job(
+            &employer,
+            &recipient,
+            &token.address,
+            &100i128,
+            &1_000u64,
+            &i,
+            &None,
+            &0u32,
+        );
+        client.fund_job(&employer, &job_id, &100i128);
+    }
+    env.ledger().with_mut(|li| li.timestamp = 10);
+
+    let first = client.process_due_payments(&4u32);
+    assert_eq!(first.processed, 4);
+    assert_eq!(first.last_job_id, 4);
+
+    let second = client.process_due_payments(&4u32);
+    assert_eq!(second.processed, 4);
+    assert_eq!(second.last_job_id, 8);
+
+    // Jobs 9 and 10 are paid, then the scan wraps around over 1..=8, which
+    // are no longer due.
+    let third = client.process_due_payments(&4u32);
+    assert_eq!(third.processed, 2);
+    assert_eq!(third.last_job_id, 8);
+
+    for job_id in 1..=10u128 {
+        assert_eq!(client.get_job(&job_id).unwrap().executions, 1);
+    }
+    assert_eq!(token.balance(&recipient), 1_000i128);
+}
+
+#[test]
+fn test_completed_jobs_not_rescanned() {
+    let env = create_env();
+    // The test env budget is shared across calls; setting up 200 jobs exceeds it.
+    env.cost_estimate().budget().reset_unlimited();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &20_000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    // 195 one-time jobs, all paid out and completed.
+    for i in 0..195u64 {
+        let job_id = client.create_job(
+            &employer,
+            &recipient,
+            &token.address,
+            &100i128,
+            &0u64,
+            &i,
+            &Some(1u32),
+            &0u32,
+        );
+        client.fund_job(&employer, &job_id, &100i128);
+    }
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    for _ in 0..10 {
+        client.process_due_payments(&20u32);
+    }
+    assert_eq!(token.balance(&recipient), 19_500i128);
+    assert_eq!(
+        client.get_job(&195u128).unwrap().status,
+        JobStatus::Completed
+    );
+
+    // 5 recurring jobs created afterwards, due later.
+    let late_recipient = Address::generate(&env);
+    for i in 0..5u64 {
+        let job_id = client.create_job(
+            &employer,
+            &late_recipient,
+            &token.address,
+            &100i128,
+            &10u64,
+            &(2_000 + i),
+            &None,
+            &0u32,
+        );
+        client.fund_job(&employer, &job_id, &100i128);
+    }
+
+    // Each run fits the default budget because completed jobs are skipped
+    // without being read.
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    env.cost_estimate().budget().reset_default();
+    assert_eq!(client.process_due_payments(&10u32).processed, 1);
+    env.ledger().with_mut(|li| li.timestamp = 2_004);
+    env.cost_estimate().budget().reset_default();
+    assert_eq!(client.process_due_payments(&10u32).processed, 4);
+    assert_eq!(token.balance(&late_recipient), 500i128);
+    for job_id in 196..=200u128 {
+        assert_eq!(client.get_job(&job_id).unwrap().executions, 1);
+    }
+}
+
+#[test]
+fn test_basic_recurring_job_execution() {
+    let env = create_env();
+    let (scheduler_id, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &300i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &Some(3u32),
+        &1u32,
+    );
+    client.fund_job(&employer, &job_id, &300i128);
+
+    // Execution 1 at t=0
+    let processed = client.process_due_payments(&10u32).processed;
+    assert_eq!(processed, 1);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.executions, 1);
+    assert_eq!(job.status, JobStatus::Active);
+    assert_eq!(token.balance(&recipient), 100i128);
+
+    // Execution 2 at t=10
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    client.process_due_payments(&10u32);
+    assert_eq!(client.get_job(&job_id).unwrap().executions, 2);
+    assert_eq!(token.balance(&recipient), 200i128);
+
+    // Execution 3 at t=20 — completes the job
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    client.process_due_payments(&10u32);
+    assert_eq!(
+        last_event::<JobCompletedEvent>(&env, &scheduler_id),
+        JobCompletedEvent {
+            job_id,
+            executions: 3,
+            timestamp: 20,
+        }
+    );
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.executions, 3);
+    assert_eq!(job.status, JobStatus::Completed);
+    assert_eq!(token.balance(&recipient), 300i128);
+}
+
+#[test]
+fn test_end_time_completes_job_at_boundary() {
+    let env = create_env();
+    let (scheduler_id, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &1_000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    // Far execution cap, but the contract ends at t=30.
+    let job_id = client.create_job_with_options(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &Some(100u32),
+        &1u32,
+        &JobOptions {
+            end_time: Some(30),
+            ..Default::default()
+        },
+    );
+    client.fund_job(&employer, &job_id, &1_000i128);
+    assert_eq!(client.get_job(&job_id).unwrap().end_time, Some(30));
+
+    for t in [0u64, 10, 20] {
+        env.ledger().with_mut(|li| li.timestamp = t);
+        client.process_due_payments(&10u32);
+    }
+    assert_eq!(token.balance(&recipient), 300i128);
+    assert_eq!(client.get_job(&job_id).unwrap().status, JobStatus::Active);
+
+    // At the boundary the job completes without paying the t=30 period.
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    assert_eq!(client.process_due_payments(&10u32).processed, 1);
+    assert_eq!(
+        last_event::<JobCompletedEvent>(&env, &scheduler_id),
+        JobCompletedEvent {
+            job_id,
+            executions: 3,
+            timestamp: 30,
+        }
+    );
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.status, JobStatus::Completed);
+    assert_eq!(job.executions, 3);
+    assert_eq!(token.balance(&recipient), 300i128);
+    assert_eq!(client.get_job_balance(&job_id), 700i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 40);
+    assert_eq!(client.process_due_payments(&10u32).processed, 0);
+    assert_eq!(token.balance(&recipient), 300i128);
+}
+
+#[test]
+fn test_end_time_must_follow_start_time() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let result = client.try_create_job_with_options(
+        &employer,
+        &recipient,
+        &token,
+        &100i128,
+        &10u64,
+        &100u64,
+        &None,
+        &1u32,
+        &JobOptions {
+            end_time: Some(100),
+            ..Default::default()
+        },
+    );
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::InvalidEndTime);
+
+    let job_id = client.create_job_with_options(
+        &employer,
+        &recipient,
+        &token,
+        &100i128,
+        &10u64,
+        &100u64,
+        &None,
+        &1u32,
+        &JobOptions {
+            end_time: Some(101),
+            ..Default::default()
+        },
+    );
+    assert_eq!(client.get_job(&job_id).unwrap().end_time, Some(101));
+
+    // Plain create_job has no end time.
+    let job_id = client.create_job(
+        &employer, &recipient, &token, &100i128, &10u64, &0u64, &None, &1u32,
+    );
+    assert_eq!(client.get_job(&job_id).unwrap().end_time, None);
+}
+
+/// Creates a funded job paying 100 every 10s from t=0.
+fn create_catch_up_job(
+    env: &Env,
+    client: &PaymentSchedulerContractClient,
+    catch_up: bool,
+    funding: i128,
+) -> (u128, Address, Address, TokenClient<'static>) {
+    let employer = Address::generate(env);
+    let recipient = Address::generate(env);
+    let token_admin = Address::generate(env);
+    let token = create_token_contract(env, &token_admin);
+    StellarAssetClient::new(env, &token.address).mint(&employer, &1_000i128);
+
+    let job_id = client.create_job_with_options(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &JobOptions {
+            catch_up,
+            ..Default::default()
+        },
+    );
+    client.fund_job(&employer, &job_id, &funding);
+    (job_id, employer, recipient, token)
+}
+
+#[test]
+fn test_catch_up_pays_missed_intervals() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (catch_up_id, _, catch_up_recipient, catch_up_token) =
+        create_catch_up_job(&env, &client, true, 1_000);
+    let (plain_id, _, plain_recipient, plain_token) =
+        create_catch_up_job(&env, &client, false, 1_000);
+
+    // Nobody processes at t=0, 10 or 20.
+    env.ledger().with_mut(|li| li.timestamp = 25);
+    assert_eq!(client.process_due_payments(&10u32).processed, 4);
+
+    // The catch-up job pays all three periods and stays on schedule.
+    assert_eq!(catch_up_token.balance(&catch_up_recipient), 300i128);
+    let job = client.get_job(&catch_up_id).unwrap();
+    assert_eq!(job.executions, 3);
+    assert_eq!(job.next_scheduled_time, 30);
+
+    // The default job pays once and restarts its schedule from now.
+    assert_eq!(plain_token.balance(&plain_recipient), 100i128);
+    let job = client.get_job(&plain_id).unwrap();
+    assert_eq!(job.executions, 1);
+    assert_eq!(job.next_scheduled_time, 35);
+
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    client.process_due_payments(&10u32);
+    assert_eq!(catch_up_token.balance(&catch_up_recipient), 400i128);
+}
+
+#[test]
+fn test_catch_up_resumes_across_calls() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (job_id, _, recipient, token) = create_catch_up_job(&env, &client, true, 1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 25);
+    let first = client.process_due_payments(&2u32);
+    assert_eq!(first.processed, 2);
+    assert_eq!(token.balance(&recipient), 200i128);
+
+    let second = client.process_due_payments(&2u32);
+    assert_eq!(second.processed, 1);
+    assert_eq!(second.last_job_id, job_id);
+    assert_eq!(token.balance(&recipient), 300i128);
+
+    // Caught up: nothing more is owed at t=25.
+    assert_eq!(client.process_due_payments(&2u32).processed, 0);
+    assert_eq!(client.get_job(&job_id).unwrap().next_scheduled_time, 30);
+}
+
+#[test]
+fn test_catch_up_retry_keeps_period_owed() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    env.ledger().with_mut(|li| li.timestamp = 0);
+    let (job_id, employer, recipient, token) = create_catch_up_job(&env, &client, true, 100);
+
+    // Period 0 is paid, period 10 fails for lack of funds.
+    env.ledger().with_mut(|li| li.timestamp = 25);
+    assert_eq!(client.process_due_payments(&10u32).processed, 2);
+    assert_eq!(token.balance(&recipient), 100i128);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.retry_count, 1);
+    assert_eq!(job.next_scheduled_time, 10);
+    assert_eq!(job.retry_after, 35);
+
+    // Repeated calls cannot burn retries before the retry time.
+    assert_eq!(client.process_due_payments(&10u32).processed, 0);
+    assert_eq!(client.get_job(&job_id).unwrap().retry_count, 1);
+
+    // After topping up, periods 10, 20 and 30 are all paid.
+    client.fund_job(&employer, &job_id, &500i128);
+    env.ledger().with_mut(|li| li.timestamp = 35);
+    assert_eq!(client.process_due_payments(&10u32).processed, 3);
+    assert_eq!(token.balance(&recipient), 400i128);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.retry_count, 0);
+    assert_eq!(job.retry_after, 0);
+    assert_eq!(job.next_scheduled_time, 40);
+}
+
+#[test]
+fn test_percent_of_balance_amounts() {
+    let env = create_env();
+    let (scheduler_id, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &1_000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    // 50% of the pot each period, with a minimum payment of 200.
+    let job_id = client.create_job_with_options(
+        &employer,
+        &recipient,
+        &token.address,
+        &200i128,
+        &10u64,
+        &0u64,
+        &None,
+        &0u32,
+        &JobOptions {
+            percent_of_balance_bps: Some(5_000),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        client.get_job(&job_id).unwrap().amount_kind,
+        AmountKind::PercentOfBalance(5_000)
+    );
+    client.fund_job(&employer, &job_id, &1_000i128);
+
+    client.process_due_payments(&10u32);
+    assert_eq!(
+        last_event::<JobExecutedEvent>(&env, &scheduler_id),
+        JobExecutedEvent {
+            job_id,
+            execution_index: 1,
+            amount: 500,
+        }
+    );
+    assert_eq!(token.balance(&recipient), 500i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    client.process_due_payments(&10u32);
+    assert_eq!(token.balance(&recipient), 750i128);
+    assert_eq!(client.get_job_balance(&job_id), 250i128);
+
+    // 50% of 250 is below the minimum: skipped, not failed.
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    client.process_due_payments(&10u32);
+    assert_eq!(
+        last_event::<JobSkippedEvent>(&env, &scheduler_id),
+        JobSkippedEvent {
+            job_id,
+            computed_amount: 125,
+            min_amount: 200,
+            timestamp: 20,
+        }
+    );
+    assert_eq!(token.balance(&recipient), 750i128);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.status, JobStatus::Active);
+    assert_eq!(job.executions, 2);
+    assert_eq!(job.retry_count, 0);
+    assert_eq!(job.next_scheduled_time, 30);
+
+    // Topping up the pot resumes payments.
+    asset_admin.mint(&employer, &750i128);
+    client.fund_job(&employer, &job_id, &750i128);
+    env.ledger().with_mut(|li| li.timestamp = 30);
+    client.process_due_payments(&10u32);
+    assert_eq!(token.balance(&recipient), 1_250i128);
+}
+
+#[test]
+fn test_percent_of_balance_bounds() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    for bps in [0u32, MAX_BPS + 1] {
+        let result = client.try_create_job_with_options(
+            &employer,
+            &recipient,
+            &token,
+            &100i128,
+            &10u64,
+            &0u64,
+            &None,
+            &1u32,
+            &JobOptions {
+                percent_of_balance_bps: Some(bps),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            SchedulerError::InvalidPercentage
+        );
+    }
+
+    let job_id = client.create_job(
+        &employer, &recipient, &token, &100i128, &10u64, &0u64, &None, &1u32,
+    );
+    assert_eq!(
+        client.get_job(&job_id).unwrap().amount_kind,
+        AmountKind::Fixed
+    );
+}
+
+fn recipient_list(env: &Env, entries: &[(&Address, i128)]) -> Vec<(Address, i128)> {
+    let mut list = Vec::new(env);
+    for (recipient, amount) in entries {
+        list.push_back(((*recipient).clone(), *amount));
+    }
+    list
+}
+
+#[test]
+fn test_multi_recipient_job_pays_everyone() {
+    let env = create_env();
+    let (scheduler_id, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    StellarAssetClient::new(&env, &token.address).mint(&employer, &1_200i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job_with_options(
+        &employer,
+        &a,
+        &token.address,
+        &600i128,
+        &10u64,
+        &0u64,
+        &Some(2u32),
+        &0u32,
+        &JobOptions {
+            recipients: Some(recipient_list(&env, &[(&a, 100), (&b, 200), (&c, 300)])),
+            ..Default::default()
+        },
+    );
+    client.fund_job(&employer, &job_id, &1_200i128);
+
+    client.process_due_payments(&10u32);
+    let paid: std::vec::Vec<JobRecipientPaidEvent> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            String::try_from_val(&env, &topics.get_unchecked(0))
+                == Ok(String::from_str(&env, "job_recipient_paid"))
+        })
+        .map(|(_, _, data)| JobRecipientPaidEvent::try_from_val(&env, &data).unwrap())
+        .collect();
+    assert_eq!(paid.len(), 3);
+    assert_eq!(
+        paid[1],
+        JobRecipientPaidEvent {
+            job_id,
+            execution_index: 1,
+            recipient: b.clone(),
+            amount: 200,
+        }
+    );
+    assert_eq!(
+        last_event::<JobExecutedEvent>(&env, &scheduler_id),
+        JobExecutedEvent {
+            job_id,
+            execution_index: 1,
+            amount: 600,
+        }
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    client.process_due_payments(&10u32);
+    assert_eq!(token.balance(&a), 200i128);
+    assert_eq!(token.balance(&b), 400i128);
+    assert_eq!(token.balance(&c), 600i128);
+    assert_eq!(client.get_job_balance(&job_id), 0i128);
+    assert_eq!(client.get_job(&job_id).unwrap().status, JobStatus::Completed);
+}
+
+#[test]
+fn test_multi_recipient_underfunded_interval_pays_no_one() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+    asset_admin.mint(&employer, &600i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job_with_options(
+        &employer,
+        &a,
+        &token.address,
+        &600i128,
+        &10u64,
+        &0u64,
+        &None,
+        &3u32,
+        &JobOptions {
+            recipients: Some(recipient_list(&env, &[(&a, 100), (&b, 200), (&c, 300)])),
+            ..Default::default()
+        },
+    );
+    // Enough for the first two recipients, not the third.
+    client.fund_job(&employer, &job_id, &500i128);
+
+    client.process_due_payments(&10u32);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.retry_count, 1);
+    assert_eq!(job.executions, 0);
+    assert_eq!(job.status, JobStatus::Active);
+    assert_eq!(token.balance(&a), 0i128);
+    assert_eq!(token.balance(&b), 0i128);
+    assert_eq!(client.get_job_balance(&job_id), 500i128);
+
+    client.fund_job(&employer, &job_id, &100i128);
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    client.process_due_payments(&10u32);
+    assert_eq!(token.balance(&a), 100i128);
+    assert_eq!(token.balance(&b), 200i128);
+    assert_eq!(token.balance(&c), 300i128);
+    assert_eq!(client.get_job(&job_id).unwrap().retry_count, 0);
+}
+
+#[test]
+fn test_multi_recipient_validation() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let token = Address::generate(&env);
+
+    let too_many: std::vec::Vec<Address> = (0..=MAX_RECIPIENTS)
+        .map(|_| Address::generate(&env))
+        .collect();
+    let too_many: std::vec::Vec<(&Address, i128)> = too_many.iter().map(|r| (r, 1)).collect();
+
+    let invalid = [
+        // (recipient, amount, list)
+        (a.clone(), 0i128, recipient_list(&env, &[])),
+        (
+            a.clone(),
+            300,
+            recipient_list(&env, &[(&a, 100), (&b, 100)]),
+        ),
+        (
+            b.clone(),
+            300,
+            recipient_list(&env, &[(&a, 100), (&b, 200)]),
+        ),
+        (a.clone(), 100, recipient_list(&env, &[(&a, 100), (&b, 0)])),
+        (
+            too_many[0].0.clone(),
+            too_many.len() as i128,
+            recipient_list(&env, &too_many),
+        ),
+    ];
+    for (recipient, amount, list) in invalid {
+        let result = client.try_create_job_with_options(
+            &employer,
+            &recipient,
+            &token,
+            &amount.max(1),
+            &10u64,
+            &0u64,
+            &None,
+            &1u32,
+            &JobOptions {
+                recipients: Some(list),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            result.unwrap_err().unwrap(),
+            SchedulerError::InvalidRecipients
+        );
+    }
+
+    // Not combinable with percent-of-balance amounts.
+    let list = recipient_list(&env, &[(&a, 100), (&b, 200)]);
+    let result = client.try_create_job_with_options(
+        &employer,
+        &a,
+        &token,
+        &300i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &JobOptions {
+            recipients: Some(list.clone()),
+            percent_of_balance_bps: Some(5_000),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::InvalidRecipients
+    );
+
+    // The amount is fixed by the list.
+    let job_id = client.create_job_with_options(
+        &employer,
+        &a,
+        &token,
+        &300i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &JobOptions {
+            recipients: Some(list),
+            ..Default::default()
+        },
+    );
+    let result = client.try_update_job(&employer, &job_id, &Some(400i128), &None);
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::InvalidRecipients
+    );
+    client.update_job(&employer, &job_id, &None, &Some(20u64));
+}
+
+#[test]
+fn test_one_time_payment() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &100i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &0u64,
+        &0u64,
+        &Some(1u32),
+        &1u32,
+    );
+    client.fund_job(&employer, &job_id, &100i128);
+
+    let processed = client.process_due_payments(&10u32).processed;
+    assert_eq!(processed, 1);
+
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.executions, 1);
+    assert_eq!(job.status, JobStatus::Completed);
+    assert_eq!(token.balance(&recipient), 100i128);
+
+    // Same timestamp second processing must not execute again.
+    let second = client.process_due_payments(&10u32).processed;
+    assert_eq!(second, 0);
+    assert_eq!(token.balance(&recipient), 100i128);
+}
+
+#[test]
+fn test_cancelled_job_skipped_by_processor() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &500i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+    );
+    client.fund_job(&employer, &job_id, &500i128);
+
+    client.cancel_job(&employer, &job_id);
+
+    // Even at t=100 the cancelled job must not be processed
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let processed = client.process_due_payments(&10u32).processed;
+    assert_eq!(processed, 0);
+    assert_eq!(token.balance(&recipient), 0i128);
+}
+
+#[test]
+fn test_cancel_mid_stream_is_terminal() {
+    let env = create_env();
+    let (scheduler_id, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    asset_admin.mint(&employer, &500i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+    );
+    client.fund_job(&employer, &job_id, &300i128);
+
+    // One payment goes out, then the contract is terminated and the
+    // remaining 200 are refunded.
+    client.process_due_payments(&10u32);
+    assert_eq!(token.balance(&recipient), 100i128);
+    env.ledger().with_mut(|li| li.timestamp = 5);
+    client.cancel_job(&employer, &job_id);
+    assert_eq!(
+        last_event::<JobCancelledEvent>(&env, &scheduler_id),
+        JobCancelledEvent {
+            job_id,
+            employer: employer.clone(),
+            refunded: 200,
+            timestamp: 5,
+        }
+    );
+    assert_eq!(token.balance(&employer), 400i128);
+    assert_eq!(client.get_job_balance(&job_id), 0i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 50);
+    assert_eq!(client.process_due_payments(&10u32).processed, 0);
+    assert_eq!(token.balance(&recipient), 100i128);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.executions, 1);
+    assert_eq!(job.status, JobStatus::Cancelled);
+
+    // The job cannot be brought back or receive new funds.
+    assert_eq!(
+        client.try_resume_job(&employer, &job_id).unwrap_err().unwrap(),
+        SchedulerError::AlreadyCancelled
+    );
+    assert_eq!(
+        client.try_pause_job(&employer, &job_id).unwrap_err().unwrap(),
+        SchedulerError::AlreadyCancelled
+    );
+    assert_eq!(
+        client
+            .try_fund_job(&employer, &job_id, &100i128)
+            .unwrap_err()
+            .unwrap(),
+        SchedulerError::AlreadyCancelled
+    );
+    assert_eq!(token.balance(&employer), 400i128);
+}
+
+#[test]
+fn test_insufficient_funds_then_retry_success() {
+    let env = create_env();
+    let (_, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+
+    // Fund 50 but job needs 100
+    asset_admin.mint(&employer, &50i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let job_id = client.create_job(
+        &employer,
+        &recipient,
+        &token.address,
+        &100i128,
+        &10u64,
+        &0u64,
+        &Some(1u32),
+        &2u32,
+    );
+    client.fund_job(&employer, &job_id, &50i128);
+
+    // First attempt fails due to insufficient funds
+    let processed = client.process_due_payments(&5u32).processed;
+    assert_eq!(processed, 1);
+    let mut job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.retry_count, 1);
     assert_eq!(job.status, JobStatus::Active);
     assert_eq!(token.balance(&recipient), 0i128);
 
     // Top up and advance to retry time
     asset_admin.mint(&employer, &200i128);
-    token.transfer(&employer, &scheduler_id, &200i128);
+    client.fund_job(&employer, &job_id, &200i128);
     env.ledger().with_mut(|li| li.timestamp = job.next_scheduled_time);
     client.process_due_payments(&5u32);
 
@@ -775,6 +2188,78 @@ fn test_retry_exhaustion_marks_failed() {
     assert_eq!(job.status, JobStatus::Failed);
 }
 
+#[test]
+fn test_retry_delay_shorter_than_interval() {
+    let env = create_env();
+    let (scheduler_id, client) = setup(&env);
+    let employer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let asset_admin = StellarAssetClient::new(&env, &token.address);
+    asset_admin.mint(&employer, &1_000i128);
+
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let month = 30 * 24 * 60 * 60u64;
+    let job_id = client.create_job_with_options(
+        &employer,
+        &recipient,
+        &token.address,
+        &1_000i128,
+        &month,
+        &0u64,
+        &None,
+        &3u32,
+        &JobOptions {
+            retry_delay_seconds: Some(300),
+            ..Default::default()
+        },
+    );
+    client.fund_job(&employer, &job_id, &900i128);
+
+    client.process_due_payments(&5u32);
+    assert_eq!(
+        last_event::<JobFailedEvent>(&env, &scheduler_id),
+        JobFailedEvent {
+            job_id,
+            retry_count: 1,
+            max_retries: 3,
+            next_retry_time: 300,
+        }
+    );
+
+    client.fund_job(&employer, &job_id, &100i128);
+    env.ledger().with_mut(|li| li.timestamp = 299);
+    assert_eq!(client.process_due_payments(&5u32).processed, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    assert_eq!(client.process_due_payments(&5u32).processed, 1);
+    assert_eq!(token.balance(&recipient), 1_000i128);
+    let job = client.get_job(&job_id).unwrap();
+    assert_eq!(job.retry_count, 0);
+    assert_eq!(job.next_scheduled_time, 300 + month);
+
+    let result = client.try_create_job_with_options(
+        &employer,
+        &recipient,
+        &token.address,
+        &500i128,
+        &month,
+        &0u64,
+        &None,
+        &3u32,
+        &JobOptions {
+            retry_delay_seconds: Some(0),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        SchedulerError::InvalidRetryDelay
+    );
+}
+
 #[test]
 fn test_conflict_detection_prevents_duplicates() {
     let env = create_env();
@@ -0,0 +1,266 @@
+//! Scheduler jobs routed through the payment splitter contract.
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env, Vec,
+};
+
+use payment_scheduler::{
+    JobOptions, PaymentSchedulerContract, PaymentSchedulerContractClient, SchedulerError,
+};
+use payment_splitter::{
+    PaymentSplitterContract, PaymentSplitterContractClient, RecipientShare, ShareKind,
+};
+
+struct Setup {
+    env: Env,
+    scheduler: PaymentSchedulerContractClient<'static>,
+    splitter_id: Address,
+    splitter: PaymentSplitterContractClient<'static>,
+    employer: Address,
+    token: TokenClient<'static>,
+}
+
+#[allow(deprecated)]
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let scheduler_id = env.register(PaymentSchedulerContract, ());
+    let scheduler = PaymentSchedulerContractClient::new(&env, &scheduler_id);
+    scheduler.initialize(&Address::generate(&env));
+
+    let splitter_id = env.register(PaymentSplitterContract, ());
+    let splitter = PaymentSplitterContractClient::new(&env, &splitter_id);
+    splitter.initialize(&Address::generate(&env));
+
+    let token_admin = Address::generate(&env);
+    let token = TokenClient::new(&env, &env.register_stellar_asset_contract(token_admin));
+    let employer = Address::generate(&env);
+    StellarAssetClient::new(&env, &token.address).mint(&employer, &10_000i128);
+
+    Setup {
+        env,
+        scheduler,
+        splitter_id,
+        splitter,
+        employer,
+        token,
+    }
+}
+
+fn create_split(s: &Setup, shares: &[(&Address, ShareKind)]) -> u128 {
+    let mut recipients = Vec::new(&s.env);
+    for (recipient, kind) in shares {
+        recipients.push_back(RecipientShare {
+            recipient: (*recipient).clone(),
+            kind: kind.clone(),
+        });
+    }
+    s.splitter
+        .create_split(&Address::generate(&s.env), &recipients)
+}
+
+fn split_options(split_id: u128) -> JobOptions {
+    JobOptions {
+        split_id: Some(split_id),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_execution_pays_every_split_recipient() {
+    let s = setup();
+    let a = Address::generate(&s.env);
+    let b = Address::generate(&s.env);
+    let split_id = create_split(
+        &s,
+        &[
+            (&a, ShareKind::Percent(6_000)),
+            (&b, ShareKind::Percent(4_000)),
+        ],
+    );
+
+    let job_id = s.scheduler.create_job_with_options(
+        &s.employer,
+        &s.splitter_id,
+        &s.token.address,
+        &1_000i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &split_options(split_id),
+    );
+    assert_eq!(
+        s.scheduler.get_job(&job_id).unwrap().split_id,
+        Some(split_id)
+    );
+    s.scheduler.fund_job(&s.employer, &job_id, &2_000i128);
+
+    assert_eq!(s.scheduler.process_due_payments(&10u32).processed, 1);
+    assert_eq!(s.token.balance(&a), 600i128);
+    assert_eq!(s.token.balance(&b), 400i128);
+    assert_eq!(s.token.balance(&s.splitter_id), 0i128);
+    assert_eq!(s.scheduler.get_job_balance(&job_id), 1_000i128);
+
+    s.env.ledger().with_mut(|li| li.timestamp = 10);
+    s.scheduler.process_due_payments(&10u32);
+    assert_eq!(s.token.balance(&a), 1_200i128);
+    assert_eq!(s.token.balance(&b), 800i128);
+}
+
+#[test]
+fn test_split_validated_at_creation() {
+    let s = setup();
+    let a = Address::generate(&s.env);
+    let b = Address::generate(&s.env);
+    let fixed_split = create_split(
+        &s,
+        &[(&a, ShareKind::Fixed(300)), (&b, ShareKind::Fixed(200))],
+    );
+
+    // Unknown split id.
+    let result = s.scheduler.try_create_job_with_options(
+        &s.employer,
+        &s.splitter_id,
+        &s.token.address,
+        &500i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &split_options(99),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::InvalidSplit);
+
+    // Fixed shares that do not add up to the job amount.
+    let result = s.scheduler.try_create_job_with_options(
+        &s.employer,
+        &s.splitter_id,
+        &s.token.address,
+        &1_000i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &split_options(fixed_split),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::InvalidSplit);
+
+    // Recipient is not a splitter contract.
+    let result = s.scheduler.try_create_job_with_options(
+        &s.employer,
+        &Address::generate(&s.env),
+        &s.token.address,
+        &500i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &split_options(fixed_split),
+    );
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::InvalidSplit);
+
+    s.scheduler.create_job_with_options(
+        &s.employer,
+        &s.splitter_id,
+        &s.token.address,
+        &500i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &split_options(fixed_split),
+    );
+}
+
+#[test]
+fn test_percent_job_with_split_rejected() {
+    let s = setup();
+    let a = Address::generate(&s.env);
+    let b = Address::generate(&s.env);
+    let fixed_split = create_split(
+        &s,
+        &[(&a, ShareKind::Fixed(300)), (&b, ShareKind::Fixed(200))],
+    );
+
+    // Valid for the minimum 500, but a percentage-of-balance job pays a
+    // different amount on every execution, which a fixed split would reject.
+    let result = s.scheduler.try_create_job_with_options(
+        &s.employer,
+        &s.splitter_id,
+        &s.token.address,
+        &500i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &JobOptions {
+            percent_of_balance_bps: Some(5_000),
+            split_id: Some(fixed_split),
+            ..Default::default()
+        },
+    );
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::InvalidSplit);
+    assert_eq!(s.token.balance(&s.scheduler.address), 0i128);
+}
+
+#[test]
+fn test_split_revalidated_on_amount_update() {
+    let s = setup();
+    let a = Address::generate(&s.env);
+    let b = Address::generate(&s.env);
+    let fixed_split = create_split(
+        &s,
+        &[(&a, ShareKind::Fixed(300)), (&b, ShareKind::Fixed(200))],
+    );
+    let percent_split = create_split(
+        &s,
+        &[
+            (&a, ShareKind::Percent(5_000)),
+            (&b, ShareKind::Percent(5_000)),
+        ],
+    );
+
+    let fixed_job = s.scheduler.create_job_with_options(
+        &s.employer,
+        &s.splitter_id,
+        &s.token.address,
+        &500i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &split_options(fixed_split),
+    );
+
+    // The fixed split can only pay 500.
+    let result = s
+        .scheduler
+        .try_update_job(&s.employer, &fixed_job, &Some(800i128), &None);
+    assert_eq!(result.unwrap_err().unwrap(), SchedulerError::InvalidSplit);
+    assert_eq!(s.scheduler.get_job(&fixed_job).unwrap().amount, 500i128);
+
+    // Keeping the amount does not need the split to be checked again.
+    s.scheduler
+        .update_job(&s.employer, &fixed_job, &Some(500i128), &Some(20u64));
+
+    let percent_job = s.scheduler.create_job_with_options(
+        &s.employer,
+        &s.splitter_id,
+        &s.token.address,
+        &500i128,
+        &10u64,
+        &0u64,
+        &None,
+        &1u32,
+        &split_options(percent_split),
+    );
+    s.scheduler
+        .update_job(&s.employer, &percent_job, &Some(800i128), &None);
+    assert_eq!(s.scheduler.get_job(&percent_job).unwrap().amount, 800i128);
+}
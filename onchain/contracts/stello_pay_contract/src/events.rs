@@ -199,6 +199,34 @@ pub fn emit_grace_period_extended(env: &Env, event: GracePeriodExtendedEvent) {
     event.publish(env);
 }
 
+/// Event: Completion bonus attached to an escrow agreement
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CompletionBonusSetEvent {
+    pub agreement_id: u128,
+    pub amount: i128,
+    pub vesting_seconds: u64,
+}
+
+pub fn emit_completion_bonus_set(env: &Env, event: CompletionBonusSetEvent) {
+    event.publish(env);
+}
+
+/// Event: Completion bonus turned into a vesting schedule
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CompletionBonusGrantedEvent {
+    pub agreement_id: u128,
+    pub beneficiary: Address,
+    pub vesting_contract: Address,
+    pub schedule_id: u128,
+    pub amount: i128,
+}
+
+pub fn emit_completion_bonus_granted(env: &Env, event: CompletionBonusGrantedEvent) {
+    event.publish(env);
+}
+
 /// Event: Batch payroll claimed
 #[contractevent]
 #[derive(Clone, Debug)]
@@ -8,8 +8,8 @@ use stellar_contract_utils::upgradeable::UpgradeableInternal;
 use stellar_macros::Upgradeable;
 use storage::{
     Agreement, BatchEscrowCreateResult, BatchMilestoneResult, BatchPayrollCreateResult,
-    BatchPayrollResult, DisputeStatus, EscrowCreateParams, GracePeriodExtensionPolicy, Milestone,
-    PayrollCreateParams, PayrollError, StorageKey,
+    BatchPayrollResult, CompletionBonus, DisputeStatus, EscrowCreateParams,
+    GracePeriodExtensionPolicy, Milestone, PayrollCreateParams, PayrollError, StorageKey,
 };
 
 /// Payroll Contract for managing payroll agreements with employee claiming functionality.
@@ -669,6 +669,11 @@ impl PayrollContract {
     /// - Caller must be the contributor
     /// - Cannot claim more than total periods
     /// - Works during grace period
+    ///
+    /// # Completion Bonus
+    /// Claiming the last period grants any bonus set with
+    /// `set_completion_bonus` as a vesting schedule; the claim reverts if the
+    /// vesting contract rejects it.
     pub fn claim_time_based(env: Env, agreement_id: u128) -> Result<(), storage::PayrollError> {
         payroll::claim_time_based(&env, agreement_id)
     }
@@ -790,6 +795,68 @@ impl PayrollContract {
         payroll::get_grace_extension_seconds(&env, agreement_id)
     }
 
+    // ============================================================================
+    // Completion Bonuses
+    // ============================================================================
+
+    /// Sets the token vesting contract that completion bonuses are granted on.
+    ///
+    /// # Arguments
+    /// * `caller` - Contract owner
+    /// * `vesting_contract` - `TokenVestingContract` address; it must whitelist
+    ///   this contract with `set_authorized_creator`
+    ///
+    /// # Errors
+    /// `Unauthorized` if the caller is not the owner
+    ///
+    /// # Access Control
+    /// Requires caller authentication
+    pub fn set_vesting_contract(
+        env: Env,
+        caller: Address,
+        vesting_contract: Address,
+    ) -> Result<(), PayrollError> {
+        payroll::set_vesting_contract(&env, caller, vesting_contract)
+    }
+
+    /// Gets the token vesting contract used for completion bonuses, if set.
+    pub fn get_vesting_contract(env: Env) -> Option<Address> {
+        payroll::get_vesting_contract(&env)
+    }
+
+    /// Attaches a completion bonus to an escrow agreement. The bonus is moved
+    /// from the employer into this contract and, when the contributor claims
+    /// the last period, becomes a non-revocable linear vesting schedule over
+    /// `vesting_seconds` on the configured vesting contract. If the agreement
+    /// is cancelled instead, `finalize_grace_period` returns it to the employer.
+    ///
+    /// # Arguments
+    /// * `agreement_id` - ID of the escrow agreement
+    /// * `amount` - Bonus amount in the agreement token
+    /// * `vesting_seconds` - Vesting duration, starting at completion
+    ///
+    /// # Errors
+    /// - `AgreementNotFound` / `InvalidAgreementMode` - not an escrow agreement
+    /// - `NotActivated` - agreement is not Created or Active
+    /// - `VestingNotConfigured` - no vesting contract is set
+    /// - `InvalidData` - non-positive amount or duration, or a bonus is already set
+    ///
+    /// # Access Control
+    /// Requires employer authentication
+    pub fn set_completion_bonus(
+        env: Env,
+        agreement_id: u128,
+        amount: i128,
+        vesting_seconds: u64,
+    ) -> Result<(), PayrollError> {
+        payroll::set_completion_bonus(&env, agreement_id, amount, vesting_seconds)
+    }
+
+    /// Gets the completion bonus still held for an agreement, if any.
+    pub fn get_completion_bonus(env: Env, agreement_id: u128) -> Option<CompletionBonus> {
+        payroll::get_completion_bonus(&env, agreement_id)
+    }
+
     // ============================================================================
     // Emergency Pause Functions
     // ============================================================================
@@ -3,25 +3,27 @@ use soroban_sdk::{Address, Env, Vec};
 
 use crate::events::{
     emit_agreement_activated, emit_agreement_cancelled, emit_agreement_created,
-    emit_agreement_paused, emit_agreement_resumed, emit_dsipute_raised, emit_dsipute_resolved,
+    emit_agreement_paused, emit_agreement_resumed, emit_completion_bonus_granted,
+    emit_completion_bonus_set, emit_dsipute_raised, emit_dsipute_resolved,
     emit_employee_added, emit_grace_period_extended, emit_grace_period_finalized,
     emit_payment_received, emit_payment_sent, emit_payroll_claimed, emit_set_arbiter,
     AgreementActivatedEvent, AgreementCancelledEvent, GracePeriodExtendedEvent,
     AgreementCreatedEvent, AgreementPausedEvent, AgreementResumedEvent, ArbiterSetEvent,
+    CompletionBonusGrantedEvent, CompletionBonusSetEvent,
     BatchMilestoneClaimedEvent, BatchPayrollClaimedEvent, DisputeRaisedEvent, DisputeResolvedEvent,
     EmployeeAddedEvent, GracePeriodFinalizedEvent, MilestoneAdded, MilestoneApproved,
     MilestoneClaimed, PaymentReceivedEvent, PaymentSentEvent, PayrollClaimedEvent,
 };
 use crate::storage::{
     Agreement, AgreementMode, AgreementStatus, BatchEscrowCreateResult, BatchMilestoneResult,
-    BatchPayrollCreateResult, BatchPayrollResult, DataKey, DisputeStatus, EmployeeInfo,
-    EscrowCreateParams, EscrowCreateResult, GracePeriodExtensionPolicy, Milestone,
+    BatchPayrollCreateResult, BatchPayrollResult, CompletionBonus, DataKey, DisputeStatus,
+    EmployeeInfo, EscrowCreateParams, EscrowCreateResult, GracePeriodExtensionPolicy, Milestone,
     MilestoneClaimResult, MilestoneKey, PaymentType, PayrollClaimResult, PayrollCreateParams,
-    PayrollCreateResult, PayrollError, StorageKey,
+    PayrollCreateResult, PayrollError, StorageKey, VestingScheduleOptions,
 };
 use soroban_sdk::{
     auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
-    token, IntoVal, Symbol, Val,
+    token, vec, IntoVal, Symbol, Val,
 };
 
 /// Fixed-point scaling factor for FX rates: 1e6 precision.
@@ -2213,6 +2215,10 @@ pub fn claim_time_based(env: &Env, agreement_id: u128) -> Result<(), PayrollErro
         .persistent()
         .set(&StorageKey::Agreement(agreement_id), &agreement);
 
+    if agreement.status == AgreementStatus::Completed {
+        grant_completion_bonus(env, &agreement, &contributor)?;
+    }
+
     emit_payment_sent(
         env,
         PaymentSentEvent {
@@ -2597,6 +2603,21 @@ pub fn finalize_grace_period(env: &Env, agreement_id: u128) {
         DataKey::set_agreement_escrow_balance(env, agreement_id, &agreement.token, 0);
     }
 
+    // A bonus for an agreement that never completed goes back to the employer.
+    let bonus_key = StorageKey::CompletionBonus(agreement_id);
+    if let Some(bonus) = env
+        .storage()
+        .persistent()
+        .get::<_, CompletionBonus>(&bonus_key)
+    {
+        env.storage().persistent().remove(&bonus_key);
+        token::Client::new(env, &agreement.token).transfer(
+            &env.current_contract_address(),
+            &agreement.employer,
+            &bonus.amount,
+        );
+    }
+
     emit_grace_period_finalized(env, GracePeriodFinalizedEvent { agreement_id });
 }
 
@@ -2661,6 +2682,169 @@ pub fn get_grace_period_end(env: &Env, agreement_id: u128) -> Option<u64> {
     cancelled_at.checked_add(effective_grace)
 }
 
+// ============================================================================
+// Completion Bonuses
+// ============================================================================
+
+/// Sets the token vesting contract that completion bonuses are granted on.
+///
+/// Access control: contract owner only. This contract must also be
+/// whitelisted on the vesting contract with `set_authorized_creator`.
+pub fn set_vesting_contract(
+    env: &Env,
+    caller: Address,
+    vesting_contract: Address,
+) -> Result<(), PayrollError> {
+    let owner: Address = env
+        .storage()
+        .persistent()
+        .get(&StorageKey::Owner)
+        .ok_or(PayrollError::Unauthorized)?;
+
+    caller.require_auth();
+
+    if caller != owner {
+        return Err(PayrollError::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&StorageKey::VestingContract, &vesting_contract);
+
+    Ok(())
+}
+
+/// Gets the token vesting contract used for completion bonuses, if set.
+pub fn get_vesting_contract(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&StorageKey::VestingContract)
+}
+
+/// Attaches a completion bonus to an escrow agreement, moving `amount` of the
+/// agreement token from the employer into this contract.
+///
+/// # Requirements
+/// - Caller must be the employer
+/// - Agreement must be an Escrow agreement in Created or Active status
+/// - A vesting contract must be configured
+/// - `amount` and `vesting_seconds` must be positive, and the agreement must
+///   not already have a bonus
+pub fn set_completion_bonus(
+    env: &Env,
+    agreement_id: u128,
+    amount: i128,
+    vesting_seconds: u64,
+) -> Result<(), PayrollError> {
+    let agreement = get_agreement(env, agreement_id).ok_or(PayrollError::AgreementNotFound)?;
+
+    agreement.employer.require_auth();
+
+    if agreement.mode != AgreementMode::Escrow {
+        return Err(PayrollError::InvalidAgreementMode);
+    }
+    if agreement.status != AgreementStatus::Created && agreement.status != AgreementStatus::Active {
+        return Err(PayrollError::NotActivated);
+    }
+    if get_vesting_contract(env).is_none() {
+        return Err(PayrollError::VestingNotConfigured);
+    }
+
+    let key = StorageKey::CompletionBonus(agreement_id);
+    if amount <= 0 || vesting_seconds == 0 || env.storage().persistent().has(&key) {
+        return Err(PayrollError::InvalidData);
+    }
+
+    token::Client::new(env, &agreement.token).transfer(
+        &agreement.employer,
+        env.current_contract_address(),
+        &amount,
+    );
+    env.storage().persistent().set(
+        &key,
+        &CompletionBonus {
+            amount,
+            vesting_seconds,
+        },
+    );
+
+    emit_completion_bonus_set(
+        env,
+        CompletionBonusSetEvent {
+            agreement_id,
+            amount,
+            vesting_seconds,
+        },
+    );
+
+    Ok(())
+}
+
+/// Gets the completion bonus held for an agreement, if any.
+pub fn get_completion_bonus(env: &Env, agreement_id: u128) -> Option<CompletionBonus> {
+    env.storage()
+        .persistent()
+        .get(&StorageKey::CompletionBonus(agreement_id))
+}
+
+/// Turns a completed agreement's bonus, if any, into a linear vesting
+/// schedule for `beneficiary` that starts now. This contract approves the
+/// vesting contract to pull the bonus and calls `create_schedule_for` as the
+/// authorized creator; the employer is recorded as the schedule employer.
+fn grant_completion_bonus(
+    env: &Env,
+    agreement: &Agreement,
+    beneficiary: &Address,
+) -> Result<(), PayrollError> {
+    let key = StorageKey::CompletionBonus(agreement.id);
+    let Some(bonus) = env.storage().persistent().get::<_, CompletionBonus>(&key) else {
+        return Ok(());
+    };
+    let vesting_contract = get_vesting_contract(env).ok_or(PayrollError::VestingNotConfigured)?;
+    env.storage().persistent().remove(&key);
+
+    let start_time = env.ledger().timestamp();
+    let end_time = start_time
+        .checked_add(bonus.vesting_seconds)
+        .ok_or(PayrollError::InvalidData)?;
+
+    let contract_address = env.current_contract_address();
+    token::Client::new(env, &agreement.token).approve(
+        &contract_address,
+        &vesting_contract,
+        &bonus.amount,
+        &env.ledger().sequence(),
+    );
+    let schedule_id: u128 = env.invoke_contract(
+        &vesting_contract,
+        &Symbol::new(env, "create_schedule_for"),
+        vec![
+            env,
+            contract_address.into_val(env),
+            agreement.employer.into_val(env),
+            beneficiary.into_val(env),
+            agreement.token.into_val(env),
+            bonus.amount.into_val(env),
+            start_time.into_val(env),
+            end_time.into_val(env),
+            None::<u64>.into_val(env),
+            false.into_val(env),
+            VestingScheduleOptions::default().into_val(env),
+        ],
+    );
+
+    emit_completion_bonus_granted(
+        env,
+        CompletionBonusGrantedEvent {
+            agreement_id: agreement.id,
+            beneficiary: beneficiary.clone(),
+            vesting_contract,
+            schedule_id,
+            amount: bonus.amount,
+        },
+    );
+
+    Ok(())
+}
+
 // ============================================================================
 // Emergency Pause Functions
 // ============================================================================
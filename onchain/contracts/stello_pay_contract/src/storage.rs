@@ -169,6 +169,32 @@ pub enum StorageKey {
     GracePeriodExtensionSeconds(u128),
     /// Owner-configurable caps for `extend_grace_period` (singleton).
     GracePeriodExtensionPolicy,
+    /// Token vesting contract that receives completion bonuses (singleton).
+    VestingContract,
+    /// Bonus held for an escrow agreement until it completes
+    /// (`agreement_id` -> `CompletionBonus`).
+    CompletionBonus(u128),
+}
+
+/// Bonus an employer attaches to an escrow agreement. It is held by this
+/// contract and becomes a linear vesting grant for the contributor when the
+/// last period is claimed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompletionBonus {
+    pub amount: i128,
+    /// Vesting duration, starting at completion.
+    pub vesting_seconds: u64,
+}
+
+/// Mirror of the vesting contract's `ScheduleOptions`, with the same field
+/// names so it encodes identically, passed to `create_schedule_for`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VestingScheduleOptions {
+    pub claim_deadline: Option<u64>,
+    pub revoker: Option<Address>,
+    pub initial_unlock_bps: u32,
 }
 
 #[contracttype]
@@ -314,6 +340,8 @@ pub enum PayrollError {
     GraceExtensionInvalid = 31,
     /// Extension would exceed owner-configured cumulative cap
     GraceExtensionCapExceeded = 32,
+    /// No vesting contract is configured for completion bonuses
+    VestingNotConfigured = 33,
 }
 
 /// Caps for how much a cancelled agreement's grace/dispute window may be extended on-chain.
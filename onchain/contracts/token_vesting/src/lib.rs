@@ -1,10 +1,48 @@
 #![no_std]
+#![allow(deprecated)] // env.events().publish() — codebase-wide pattern
+#![allow(clippy::too_many_arguments)] // generated clients mirror the creation entry points
 
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, token, Address, Env,
+    Map, Vec,
+};
+
+/// Maximum number of grants accepted by a single `create_linear_schedules_bulk` call.
+const MAX_BULK_GRANTS: u32 = 20;
+
+/// Maximum number of schedules returned by a single `list_schedules` call.
+/// Keeps the query well under Soroban instruction limits.
+const MAX_LIST_LIMIT: u32 = 50;
+
+/// Maximum number of timestamps accepted by `preview_vesting`.
+const MAX_PREVIEW_POINTS: u32 = 100;
+
+/// Upper bound on the protocol fee taken from claims (5%).
+const MAX_FEE_BPS: u32 = 500;
+
+/// Default time a beneficiary has to accept an approved early release (7 days).
+const DEFAULT_EARLY_RELEASE_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// Default delay between an owner-initiated recovery and its finalization (7 days).
+const DEFAULT_RECOVERY_TIMELOCK: u64 = 7 * 24 * 60 * 60;
 
 #[contract]
 pub struct TokenVestingContract;
 
+/// Typed errors raised (via `panic_with_error!`) where callers need to
+/// distinguish the failure mode programmatically.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VestingError {
+    /// The contract is paused; state-changing operations are rejected.
+    ContractPaused = 1,
+    /// Eligibility gating is enabled and the beneficiary is not marked eligible.
+    BeneficiaryNotEligible = 2,
+    /// The contract's token balance cannot cover the requested payout.
+    InsufficientEscrow = 3,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum VestingKind {
@@ -50,6 +88,82 @@ pub struct VestingSchedule {
     pub status: VestingStatus,
     pub revocable: bool,
     pub revoked_at: Option<u64>,
+    /// End of the post-revocation claim window, fixed by `revoke` from the
+    /// revocation grace configured at that time. `None` keeps the vested
+    /// remainder claimable forever.
+    pub grace_end: Option<u64>,
+    /// Seconds after `end_time` the beneficiary has to claim before the
+    /// employer may sweep unclaimed vested tokens. `None` disables sweeping.
+    pub claim_deadline: Option<u64>,
+    /// Unclaimed vested amount returned to the employer by `sweep_expired` or
+    /// `reclaim_after_grace`.
+    pub swept_amount: i128,
+    /// Optional address allowed to revoke in addition to the employer.
+    pub revoker: Option<Address>,
+    /// Share of `total_amount` (basis points) unlocked at `start_time` for
+    /// linear schedules; the remainder vests linearly. Always 0 for other kinds.
+    pub initial_unlock_bps: u32,
+}
+
+/// Optional settings accepted by the schedule creation functions.
+///
+/// Grouped into one argument so creation functions stay within Soroban's
+/// limit of 10 contract function parameters.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScheduleOptions {
+    /// Claim window after `end_time` (seconds) before unclaimed vested tokens
+    /// can be swept back to the employer. `None` disables sweeping.
+    pub claim_deadline: Option<u64>,
+    /// Address that may revoke in addition to the employer.
+    pub revoker: Option<Address>,
+    /// Share of `total_amount` (basis points, max 10000) vested at
+    /// `start_time` by linear schedules. Must be 0 for cliff and custom
+    /// schedules.
+    pub initial_unlock_bps: u32,
+}
+
+/// Protocol fee applied to every payout to a beneficiary.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    /// Fee in basis points (1/10000), at most 500.
+    pub fee_bps: u32,
+    /// Address receiving the fee portion.
+    pub treasury: Address,
+}
+
+/// An owner-approved early release waiting for beneficiary acceptance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingEarlyRelease {
+    pub schedule_id: u128,
+    pub admin: Address,
+    /// Maximum amount approved by the owner.
+    pub amount: i128,
+    pub approved_at: u64,
+}
+
+/// An employer-proposed settlement waiting for beneficiary acceptance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingCancellation {
+    pub schedule_id: u128,
+    pub employer: Address,
+    /// Total amount the beneficiary keeps, including anything already released.
+    pub vested_to_beneficiary: i128,
+    pub proposed_at: u64,
+}
+
+/// An owner-initiated beneficiary recovery waiting out its timelock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRecovery {
+    pub schedule_id: u128,
+    pub admin: Address,
+    pub new_beneficiary: Address,
+    /// Earliest timestamp at which `finalize_recovery` may be called.
+    pub executable_at: u64,
 }
 
 #[contracttype]
@@ -59,6 +173,19 @@ enum StorageKey {
     Owner,
     NextScheduleId,
     Schedule(u128),
+    ClaimDelegate(u128),
+    PendingEarlyRelease(u128),
+    EarlyReleaseWindow,
+    TotalLocked(Address),
+    FeeConfig,
+    Paused,
+    AuthorizedCreator(Address),
+    RequireEligibility,
+    ClaimEligible(Address),
+    RevocationGrace,
+    PendingCancellation(u128),
+    PendingRecovery(u128),
+    RecoveryTimelock,
 }
 
 // ============================================================================
@@ -84,6 +211,19 @@ pub struct ClaimedEvent {
     pub amount: i128,
 }
 
+/// Detailed payout event carrying the post-payout balances, so indexers do
+/// not need a follow-up read.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingClaimedEvent {
+    pub schedule_id: u128,
+    pub beneficiary: Address,
+    pub amount: i128,
+    pub total_released: i128,
+    pub remaining_unvested: i128,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RevokedEvent {
@@ -93,6 +233,39 @@ pub struct RevokedEvent {
     pub at: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevokerSetEvent {
+    pub id: u128,
+    pub employer: Address,
+    pub revoker: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMigratedEvent {
+    pub id: u128,
+    pub old_token: Address,
+    pub new_token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GraceReclaimedEvent {
+    pub id: u128,
+    pub employer: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweptEvent {
+    pub id: u128,
+    pub employer: Address,
+    pub amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct EarlyReleaseEvent {
@@ -101,6 +274,118 @@ pub struct EarlyReleaseEvent {
     pub amount: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyReleaseApprovedEvent {
+    pub id: u128,
+    pub admin: Address,
+    pub amount: i128,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationProposedEvent {
+    pub id: u128,
+    pub employer: Address,
+    pub vested_to_beneficiary: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationWithdrawnEvent {
+    pub id: u128,
+    pub employer: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationAcceptedEvent {
+    pub id: u128,
+    pub beneficiary: Address,
+    /// Amount paid to the beneficiary on acceptance.
+    pub paid: i128,
+    /// Amount refunded to the employer on acceptance.
+    pub refunded: i128,
+    pub at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyReleaseCancelledEvent {
+    pub id: u128,
+    pub admin: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfigUpdatedEvent {
+    pub fee_bps: u32,
+    pub treasury: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheckpointsUpdatedEvent {
+    pub id: u128,
+    pub employer: Address,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizedCreatorSetEvent {
+    pub creator: Address,
+    pub authorized: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EligibilityChangedEvent {
+    pub beneficiary: Address,
+    pub eligible: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseChangedEvent {
+    pub admin: Address,
+    pub paused: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimDelegateSetEvent {
+    pub id: u128,
+    pub beneficiary: Address,
+    pub delegate: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryInitiatedEvent {
+    pub id: u128,
+    pub admin: Address,
+    pub new_beneficiary: Address,
+    pub executable_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryCancelledEvent {
+    pub id: u128,
+    pub beneficiary: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BeneficiaryChangedEvent {
+    pub id: u128,
+    pub old_beneficiary: Address,
+    pub new_beneficiary: Address,
+}
+
 fn require_initialized(env: &Env) {
     let initialized = env
         .storage()
@@ -110,6 +395,53 @@ fn require_initialized(env: &Env) {
     assert!(initialized, "Contract not initialized");
 }
 
+fn read_paused(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get::<_, bool>(&StorageKey::Paused)
+        .unwrap_or(false)
+}
+
+fn require_not_paused(env: &Env) {
+    if read_paused(env) {
+        panic_with_error!(env, VestingError::ContractPaused);
+    }
+}
+
+fn read_require_eligibility(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get::<_, bool>(&StorageKey::RequireEligibility)
+        .unwrap_or(false)
+}
+
+/// Returns whether `beneficiary` may receive payouts.
+///
+/// Everyone is eligible unless the owner has turned on eligibility gating,
+/// in which case only explicitly flagged beneficiaries are.
+fn read_claim_eligible(env: &Env, beneficiary: &Address) -> bool {
+    if !read_require_eligibility(env) {
+        return true;
+    }
+    env.storage()
+        .persistent()
+        .get::<_, bool>(&StorageKey::ClaimEligible(beneficiary.clone()))
+        .unwrap_or(false)
+}
+
+fn require_claim_eligible(env: &Env, beneficiary: &Address) {
+    if !read_claim_eligible(env, beneficiary) {
+        panic_with_error!(env, VestingError::BeneficiaryNotEligible);
+    }
+}
+
+fn read_authorized_creator(env: &Env, creator: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<_, bool>(&StorageKey::AuthorizedCreator(creator.clone()))
+        .unwrap_or(false)
+}
+
 fn read_owner(env: &Env) -> Address {
     env.storage()
         .persistent()
@@ -143,11 +475,229 @@ fn write_schedule(env: &Env, schedule: &VestingSchedule) {
         .set(&StorageKey::Schedule(schedule.id), schedule);
 }
 
+fn read_claim_delegate(env: &Env, id: u128) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<_, Address>(&StorageKey::ClaimDelegate(id))
+}
+
+fn write_claim_delegate(env: &Env, id: u128, delegate: &Option<Address>) {
+    match delegate {
+        Some(addr) => env
+            .storage()
+            .persistent()
+            .set(&StorageKey::ClaimDelegate(id), addr),
+        None => env
+            .storage()
+            .persistent()
+            .remove(&StorageKey::ClaimDelegate(id)),
+    }
+}
+
+fn read_early_release_window(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<_, u64>(&StorageKey::EarlyReleaseWindow)
+        .unwrap_or(DEFAULT_EARLY_RELEASE_WINDOW)
+}
+
+fn read_revocation_grace(env: &Env) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get::<_, u64>(&StorageKey::RevocationGrace)
+}
+
+/// Returns the timestamp at which the post-revocation claim window closes, or
+/// `None` if the schedule is not revoked or was revoked without a grace period.
+fn revocation_grace_end(schedule: &VestingSchedule) -> Option<u64> {
+    if schedule.status != VestingStatus::Revoked {
+        return None;
+    }
+    schedule.grace_end
+}
+
+fn read_recovery_timelock(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<_, u64>(&StorageKey::RecoveryTimelock)
+        .unwrap_or(DEFAULT_RECOVERY_TIMELOCK)
+}
+
+fn read_pending_recovery(env: &Env, id: u128) -> Option<PendingRecovery> {
+    env.storage()
+        .persistent()
+        .get::<_, PendingRecovery>(&StorageKey::PendingRecovery(id))
+}
+
+/// Moves `schedule` to `new_beneficiary`, clearing the claim delegate and any
+/// pending recovery so neither carries over to the new address.
+fn apply_beneficiary_change(env: &Env, mut schedule: VestingSchedule, new_beneficiary: Address) {
+    let old_beneficiary = schedule.beneficiary.clone();
+    schedule.beneficiary = new_beneficiary.clone();
+    write_schedule(env, &schedule);
+    write_claim_delegate(env, schedule.id, &None);
+    env.storage()
+        .persistent()
+        .remove(&StorageKey::PendingRecovery(schedule.id));
+
+    env.events().publish(
+        ("vesting_beneficiary_changed", schedule.id),
+        BeneficiaryChangedEvent {
+            id: schedule.id,
+            old_beneficiary,
+            new_beneficiary,
+        },
+    );
+}
+
+fn read_pending_cancellation(env: &Env, id: u128) -> Option<PendingCancellation> {
+    env.storage()
+        .persistent()
+        .get::<_, PendingCancellation>(&StorageKey::PendingCancellation(id))
+}
+
+fn read_pending_early_release(env: &Env, id: u128) -> Option<PendingEarlyRelease> {
+    env.storage()
+        .persistent()
+        .get::<_, PendingEarlyRelease>(&StorageKey::PendingEarlyRelease(id))
+}
+
+fn read_total_locked(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<_, i128>(&StorageKey::TotalLocked(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Adjusts the tracked amount of `token` still owed to beneficiaries.
+///
+/// Positive `delta` on escrow (schedule creation), negative on every payout
+/// or refund out of the contract.
+fn adjust_total_locked(env: &Env, token: &Address, delta: i128) {
+    let next = read_total_locked(env, token)
+        .checked_add(delta)
+        .expect("Total locked overflow");
+    assert!(next >= 0, "Total locked underflow");
+    env.storage()
+        .persistent()
+        .set(&StorageKey::TotalLocked(token.clone()), &next);
+}
+
+/// Returns the contract's actual balance of `token`.
+fn escrow_balance(env: &Env, token: &Address) -> i128 {
+    token::Client::new(env, token).balance(&env.current_contract_address())
+}
+
+/// Fails with `VestingError::InsufficientEscrow` if the contract's balance of
+/// `token` cannot cover `amount`, instead of letting the token transfer panic.
+fn require_escrow(env: &Env, token: &Address, amount: i128) {
+    if escrow_balance(env, token) < amount {
+        panic_with_error!(env, VestingError::InsufficientEscrow);
+    }
+}
+
+fn read_fee_config(env: &Env) -> Option<FeeConfig> {
+    env.storage()
+        .persistent()
+        .get::<_, FeeConfig>(&StorageKey::FeeConfig)
+}
+
+/// Transfers `gross` of `token` out of the contract to `beneficiary`, routing
+/// the protocol fee (if configured) to the treasury.
+///
+/// The fee is rounded down, and the beneficiary receives `gross - fee`, so the
+/// two transfers always sum to exactly `gross`.
+fn pay_beneficiary(env: &Env, token: &Address, beneficiary: &Address, gross: i128) {
+    require_escrow(env, token, gross);
+    let token_client = token::Client::new(env, token);
+    let contract = env.current_contract_address();
+
+    let fee = match read_fee_config(env) {
+        Some(cfg) if cfg.fee_bps > 0 => {
+            let fee = gross
+                .checked_mul(i128::from(cfg.fee_bps))
+                .expect("Fee overflow")
+                / 10_000;
+            if fee > 0 {
+                token_client.transfer(&contract, &cfg.treasury, &fee);
+            }
+            fee
+        }
+        _ => 0,
+    };
+
+    token_client.transfer(&contract, beneficiary, &(gross - fee));
+}
+
+/// Validates a custom checkpoint list and returns its `(first, last)` times.
+///
+/// Timestamps must be strictly increasing and not in the past relative to
+/// `now`; cumulative amounts must start above zero, be non-decreasing and end
+/// exactly at `total_amount`.
+fn validate_checkpoints(
+    now: u64,
+    checkpoints: &Vec<CustomCheckpoint>,
+    total_amount: i128,
+) -> (u64, u64) {
+    assert!(!checkpoints.is_empty(), "At least one checkpoint required");
+
+    let first = checkpoints.get(0).unwrap();
+    assert!(
+        first.cumulative_amount > 0,
+        "First checkpoint amount must be positive"
+    );
+
+    let mut last_time: u64 = 0;
+    let mut last_amount: i128 = 0;
+    for i in 0..checkpoints.len() {
+        let cp = checkpoints.get(i).unwrap();
+        assert!(cp.time >= now, "Checkpoint is in the past");
+        if i > 0 {
+            assert!(cp.time > last_time, "Checkpoints must be sorted");
+        }
+        assert!(
+            cp.cumulative_amount >= last_amount,
+            "Checkpoint amounts must be non-decreasing"
+        );
+        last_time = cp.time;
+        last_amount = cp.cumulative_amount;
+    }
+    assert!(
+        last_amount == total_amount,
+        "Last checkpoint must equal total_amount"
+    );
+
+    (first.time, last_time)
+}
+
+/// Validates the timing parameters and initial unlock shared by all linear
+/// schedules.
+fn assert_linear_params(
+    start_time: u64,
+    end_time: u64,
+    cliff_time: Option<u64>,
+    options: &ScheduleOptions,
+) {
+    assert!(end_time > start_time, "End time must be after start time");
+    assert!(
+        options.initial_unlock_bps <= 10_000,
+        "Initial unlock cannot exceed 10000 bps"
+    );
+
+    if let Some(cliff) = cliff_time {
+        assert!(
+            cliff >= start_time && cliff <= end_time,
+            "Cliff must be within [start, end]"
+        );
+    }
+}
+
 /// Computes the cumulative vested amount for `schedule` at timestamp `now`.
 ///
 /// For revoked schedules the clock is frozen at `revoked_at`.
-/// - **Linear**: proportional between `start_time` and `end_time`, gated by
-///   an optional `cliff_time` (nothing vests until the cliff is reached).
+/// - **Linear**: `initial_unlock_bps` of the total vests at `start_time`; the
+///   remainder is proportional between `start_time` and `end_time`, gated by
+///   an optional `cliff_time` (none of the remainder vests until the cliff).
 /// - **Cliff**: 0 before `cliff_time`, 100% at or after `cliff_time`.
 /// - **Custom**: step function — returns the `cumulative_amount` of the last
 ///   checkpoint whose `time <= now`, capped at `total_amount`.
@@ -163,10 +713,16 @@ fn compute_vested_amount(now: u64, schedule: &VestingSchedule) -> i128 {
 
     match schedule.kind {
         VestingKind::Linear => {
-            if effective_now <= schedule.start_time {
-                0
-            } else if matches!(schedule.cliff_time, Some(cliff) if effective_now < cliff) {
-                0
+            if effective_now < schedule.start_time {
+                return 0;
+            }
+            let unlocked = schedule.total_amount * i128::from(schedule.initial_unlock_bps) / 10_000;
+            let remainder = schedule.total_amount - unlocked;
+
+            if effective_now == schedule.start_time
+                || matches!(schedule.cliff_time, Some(cliff) if effective_now < cliff)
+            {
+                unlocked
             } else if effective_now >= schedule.end_time {
                 schedule.total_amount
             } else {
@@ -175,9 +731,9 @@ fn compute_vested_amount(now: u64, schedule: &VestingSchedule) -> i128 {
                 if duration == 0 {
                     schedule.total_amount
                 } else {
-                    // Linear interpolation: total * elapsed / duration
-                    (schedule.total_amount * i128::from(elapsed as i64))
-                        / i128::from(duration as i64)
+                    // Linear interpolation of the remainder: remainder * elapsed / duration
+                    unlocked
+                        + (remainder * i128::from(elapsed as i64)) / i128::from(duration as i64)
                 }
             }
         }
@@ -186,7 +742,7 @@ fn compute_vested_amount(now: u64, schedule: &VestingSchedule) -> i128 {
             _ => 0,
         },
         VestingKind::Custom => {
-            if schedule.checkpoints.len() == 0 {
+            if schedule.checkpoints.is_empty() {
                 return 0;
             }
             let mut last_amount: i128 = 0;
@@ -207,8 +763,45 @@ fn compute_vested_amount(now: u64, schedule: &VestingSchedule) -> i128 {
     }
 }
 
-/// Returns `vested - released_amount`, floored at 0.
-///
+/// Returns the portion of `total_amount` that has neither vested nor been
+/// released early. Revoked schedules have no unvested remainder (it was
+/// refunded to the employer).
+fn compute_remaining_unvested(now: u64, schedule: &VestingSchedule) -> i128 {
+    if schedule.status == VestingStatus::Revoked {
+        return 0;
+    }
+    let vested = compute_vested_amount(now, schedule);
+    let covered = if vested > schedule.released_amount {
+        vested
+    } else {
+        schedule.released_amount
+    };
+    let remaining = schedule.total_amount - covered;
+    if remaining < 0 {
+        0
+    } else {
+        remaining
+    }
+}
+
+/// Publishes `VestingClaimedEvent` for a payout. Must be called after the
+/// schedule has been updated so the remaining figures reflect the payout.
+fn emit_vesting_claimed(env: &Env, schedule: &VestingSchedule, amount: i128, now: u64) {
+    env.events().publish(
+        ("vesting_claim_detail", schedule.id),
+        VestingClaimedEvent {
+            schedule_id: schedule.id,
+            beneficiary: schedule.beneficiary.clone(),
+            amount,
+            total_released: schedule.released_amount,
+            remaining_unvested: compute_remaining_unvested(now, schedule),
+            timestamp: now,
+        },
+    );
+}
+
+/// Returns `vested - released_amount`, floored at 0.
+///
 /// This is the amount the beneficiary can currently withdraw via `claim`.
 fn compute_releasable(now: u64, schedule: &VestingSchedule) -> i128 {
     let vested = compute_vested_amount(now, schedule);
@@ -251,6 +844,8 @@ impl TokenVestingContract {
     /// @param end_time Vesting end timestamp (must be > start_time).
     /// @param cliff_time Optional cliff timestamp.
     /// @param revocable Whether employer can revoke this schedule.
+    /// @param options Optional claim deadline, revoker and initial unlock; see
+    ///        `ScheduleOptions`.
     /// @return u128
     pub fn create_linear_schedule(
         env: Env,
@@ -262,23 +857,201 @@ impl TokenVestingContract {
         end_time: u64,
         cliff_time: Option<u64>,
         revocable: bool,
+        options: ScheduleOptions,
     ) -> u128 {
         require_initialized(&env);
+        require_not_paused(&env);
         employer.require_auth();
 
         assert!(total_amount > 0, "Total amount must be positive");
-        assert!(end_time > start_time, "End time must be after start time");
+        assert_linear_params(start_time, end_time, cliff_time, &options);
+
+        // Escrow tokens in the vesting contract.
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&employer, env.current_contract_address(), &total_amount);
+        adjust_total_locked(&env, &token, total_amount);
+
+        let id = next_schedule_id(&env);
+        let schedule = VestingSchedule {
+            id,
+            employer: employer.clone(),
+            beneficiary: beneficiary.clone(),
+            token: token.clone(),
+            kind: VestingKind::Linear,
+            total_amount,
+            released_amount: 0,
+            start_time,
+            end_time,
+            cliff_time,
+            checkpoints: Vec::new(&env),
+            status: VestingStatus::Active,
+            revocable,
+            revoked_at: None,
+            grace_end: None,
+            claim_deadline: options.claim_deadline,
+            swept_amount: 0,
+            revoker: options.revoker,
+            initial_unlock_bps: options.initial_unlock_bps,
+        };
+        write_schedule(&env, &schedule);
+
+        env.events().publish(
+            ("vesting_created", id),
+            CreatedEvent {
+                id,
+                employer,
+                beneficiary,
+                token,
+                kind: VestingKind::Linear,
+                amount: total_amount,
+            },
+        );
+
+        id
+    }
+
+    /// @notice Creates one linear vesting schedule per grant in a single call.
+    /// @dev All grants share the same timing parameters. The summed amount is
+    ///      escrowed with a single transfer. The call is atomic: if any grant is
+    ///      invalid (non-positive amount or duplicate beneficiary) nothing is created.
+    /// @param employer Funding address; must authenticate.
+    /// @param token Token contract address used for vesting.
+    /// @param grants List of `(beneficiary, total_amount)` pairs (max 20).
+    /// @param start_time Vesting start timestamp.
+    /// @param end_time Vesting end timestamp (must be > start_time).
+    /// @param cliff_time Optional cliff timestamp.
+    /// @param revocable Whether employer can revoke the schedules.
+    /// @param options Optional claim deadline, revoker and initial unlock; see
+    ///        `ScheduleOptions`.
+    /// @return Vec<u128> New schedule ids, in the same order as `grants`.
+    pub fn create_linear_schedules_bulk(
+        env: Env,
+        employer: Address,
+        token: Address,
+        grants: Vec<(Address, i128)>,
+        start_time: u64,
+        end_time: u64,
+        cliff_time: Option<u64>,
+        revocable: bool,
+        options: ScheduleOptions,
+    ) -> Vec<u128> {
+        require_initialized(&env);
+        require_not_paused(&env);
+        employer.require_auth();
 
-        if let Some(cliff) = cliff_time {
+        assert!(!grants.is_empty(), "At least one grant required");
+        assert!(grants.len() <= MAX_BULK_GRANTS, "Too many grants");
+        assert_linear_params(start_time, end_time, cliff_time, &options);
+
+        let mut seen: Map<Address, bool> = Map::new(&env);
+        let mut total: i128 = 0;
+        for (beneficiary, amount) in grants.iter() {
+            assert!(amount > 0, "Total amount must be positive");
             assert!(
-                cliff >= start_time && cliff <= end_time,
-                "Cliff must be within [start, end]"
+                !seen.contains_key(beneficiary.clone()),
+                "Duplicate beneficiary"
             );
+            seen.set(beneficiary, true);
+            total = total.checked_add(amount).expect("Total amount overflow");
         }
 
-        // Escrow tokens in the vesting contract.
+        // Escrow the whole cohort in one transfer.
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&employer, env.current_contract_address(), &total);
+        adjust_total_locked(&env, &token, total);
+
+        let mut ids: Vec<u128> = Vec::new(&env);
+        for (beneficiary, amount) in grants.iter() {
+            let id = next_schedule_id(&env);
+            let schedule = VestingSchedule {
+                id,
+                employer: employer.clone(),
+                beneficiary: beneficiary.clone(),
+                token: token.clone(),
+                kind: VestingKind::Linear,
+                total_amount: amount,
+                released_amount: 0,
+                start_time,
+                end_time,
+                cliff_time,
+                checkpoints: Vec::new(&env),
+                status: VestingStatus::Active,
+                revocable,
+                revoked_at: None,
+                grace_end: None,
+                claim_deadline: options.claim_deadline,
+                swept_amount: 0,
+                revoker: options.revoker.clone(),
+                initial_unlock_bps: options.initial_unlock_bps,
+            };
+            write_schedule(&env, &schedule);
+
+            env.events().publish(
+                ("vesting_created", id),
+                CreatedEvent {
+                    id,
+                    employer: employer.clone(),
+                    beneficiary,
+                    token: token.clone(),
+                    kind: VestingKind::Linear,
+                    amount,
+                },
+            );
+
+            ids.push_back(id);
+        }
+
+        ids
+    }
+
+    /// @notice Creates a linear vesting schedule on behalf of a whitelisted contract.
+    /// @dev Intended for contracts such as the payroll contract that turn
+    ///      completion bonuses into vesting grants. `caller_contract` must be
+    ///      whitelisted via `set_authorized_creator` and must have approved this
+    ///      contract to spend `total_amount` of `token` beforehand; the escrow is
+    ///      pulled from its balance with `transfer_from`. `employer` is recorded
+    ///      as the schedule employer and receives any revocation refund.
+    /// @param caller_contract Whitelisted creator funding the grant; must authenticate.
+    /// @param employer Address recorded as the schedule employer.
+    /// @param beneficiary Employee/recipient of vested tokens.
+    /// @param token Token contract address used for vesting.
+    /// @param total_amount Total number of tokens to vest.
+    /// @param start_time Vesting start timestamp.
+    /// @param end_time Vesting end timestamp (must be > start_time).
+    /// @param cliff_time Optional cliff timestamp.
+    /// @param revocable Whether employer can revoke this schedule.
+    /// @param options Optional claim deadline, revoker and initial unlock; see
+    ///        `ScheduleOptions`.
+    /// @return u128 Unique schedule identifier.
+    pub fn create_schedule_for(
+        env: Env,
+        caller_contract: Address,
+        employer: Address,
+        beneficiary: Address,
+        token: Address,
+        total_amount: i128,
+        start_time: u64,
+        end_time: u64,
+        cliff_time: Option<u64>,
+        revocable: bool,
+        options: ScheduleOptions,
+    ) -> u128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        caller_contract.require_auth();
+
+        assert!(
+            read_authorized_creator(&env, &caller_contract),
+            "Caller is not an authorized creator"
+        );
+        assert!(total_amount > 0, "Total amount must be positive");
+        assert_linear_params(start_time, end_time, cliff_time, &options);
+
+        // Pull escrow from the caller's pre-approved allowance.
+        let this = env.current_contract_address();
         let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&employer, &env.current_contract_address(), &total_amount);
+        token_client.transfer_from(&this, &caller_contract, &this, &total_amount);
+        adjust_total_locked(&env, &token, total_amount);
 
         let id = next_schedule_id(&env);
         let schedule = VestingSchedule {
@@ -296,6 +1069,11 @@ impl TokenVestingContract {
             status: VestingStatus::Active,
             revocable,
             revoked_at: None,
+            grace_end: None,
+            claim_deadline: options.claim_deadline,
+            swept_amount: 0,
+            revoker: options.revoker,
+            initial_unlock_bps: options.initial_unlock_bps,
         };
         write_schedule(&env, &schedule);
 
@@ -323,6 +1101,8 @@ impl TokenVestingContract {
     /// @param total_amount Total number of tokens to vest (must be > 0).
     /// @param cliff_time Absolute timestamp at which 100% of tokens vest.
     /// @param revocable Whether employer can revoke this schedule.
+    /// @param options Optional claim deadline and revoker; see `ScheduleOptions`.
+    ///        `initial_unlock_bps` must be 0.
     /// @return u128 Unique schedule identifier.
     pub fn create_cliff_schedule(
         env: Env,
@@ -332,14 +1112,21 @@ impl TokenVestingContract {
         total_amount: i128,
         cliff_time: u64,
         revocable: bool,
+        options: ScheduleOptions,
     ) -> u128 {
         require_initialized(&env);
+        require_not_paused(&env);
         employer.require_auth();
 
         assert!(total_amount > 0, "Total amount must be positive");
+        assert!(
+            options.initial_unlock_bps == 0,
+            "Initial unlock only applies to linear schedules"
+        );
 
         let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&employer, &env.current_contract_address(), &total_amount);
+        token_client.transfer(&employer, env.current_contract_address(), &total_amount);
+        adjust_total_locked(&env, &token, total_amount);
 
         let id = next_schedule_id(&env);
         let schedule = VestingSchedule {
@@ -357,6 +1144,11 @@ impl TokenVestingContract {
             status: VestingStatus::Active,
             revocable,
             revoked_at: None,
+            grace_end: None,
+            claim_deadline: options.claim_deadline,
+            swept_amount: 0,
+            revoker: options.revoker,
+            initial_unlock_bps: 0,
         };
         write_schedule(&env, &schedule);
 
@@ -376,8 +1168,9 @@ impl TokenVestingContract {
     }
 
     /// @notice Creates a custom vesting schedule with arbitrary checkpoints.
-    /// @dev `checkpoints` must be sorted ascending by `time`, with non-decreasing
-    ///      `cumulative_amount` values; the last checkpoint must equal `total_amount`.
+    /// @dev `checkpoints` must have strictly increasing `time` values that are not
+    ///      in the past, a positive first `cumulative_amount`, non-decreasing
+    ///      amounts, and a last checkpoint equal to `total_amount`.
     ///      Employer escrows the full `total_amount` at creation time.
     /// @param employer Funding address; must authenticate.
     /// @param beneficiary Employee/recipient of vested tokens.
@@ -386,6 +1179,8 @@ impl TokenVestingContract {
     /// @param checkpoints Ordered list of `CustomCheckpoint` entries defining the
     ///        step-function vesting curve.
     /// @param revocable Whether employer can revoke this schedule.
+    /// @param options Optional claim deadline and revoker; see `ScheduleOptions`.
+    ///        `initial_unlock_bps` must be 0.
     /// @return u128 Unique schedule identifier.
     pub fn create_custom_schedule(
         env: Env,
@@ -395,32 +1190,24 @@ impl TokenVestingContract {
         total_amount: i128,
         checkpoints: Vec<CustomCheckpoint>,
         revocable: bool,
+        options: ScheduleOptions,
     ) -> u128 {
         require_initialized(&env);
+        require_not_paused(&env);
         employer.require_auth();
 
         assert!(total_amount > 0, "Total amount must be positive");
-        assert!(checkpoints.len() > 0, "At least one checkpoint required");
-
-        let mut last_time: u64 = 0;
-        let mut last_amount: i128 = 0;
-        for i in 0..checkpoints.len() {
-            let cp = checkpoints.get(i).unwrap();
-            assert!(cp.time >= last_time, "Checkpoints must be sorted");
-            assert!(
-                cp.cumulative_amount >= last_amount,
-                "Checkpoint amounts must be non-decreasing"
-            );
-            last_time = cp.time;
-            last_amount = cp.cumulative_amount;
-        }
         assert!(
-            last_amount == total_amount,
-            "Last checkpoint must equal total_amount"
+            options.initial_unlock_bps == 0,
+            "Initial unlock only applies to linear schedules"
         );
 
+        let now = env.ledger().timestamp();
+        let (first_time, last_time) = validate_checkpoints(now, &checkpoints, total_amount);
+
         let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&employer, &env.current_contract_address(), &total_amount);
+        token_client.transfer(&employer, env.current_contract_address(), &total_amount);
+        adjust_total_locked(&env, &token, total_amount);
 
         let id = next_schedule_id(&env);
         let schedule = VestingSchedule {
@@ -431,13 +1218,18 @@ impl TokenVestingContract {
             kind: VestingKind::Custom,
             total_amount,
             released_amount: 0,
-            start_time: checkpoints.get(0).unwrap().time,
+            start_time: first_time,
             end_time: last_time,
             cliff_time: None,
             checkpoints,
             status: VestingStatus::Active,
             revocable,
             revoked_at: None,
+            grace_end: None,
+            claim_deadline: options.claim_deadline,
+            swept_amount: 0,
+            revoker: options.revoker,
+            initial_unlock_bps: 0,
         };
         write_schedule(&env, &schedule);
 
@@ -456,25 +1248,95 @@ impl TokenVestingContract {
         id
     }
 
-    /// @notice Claims any vested but unreleased tokens for a schedule.
-    /// @param beneficiary Schedule beneficiary; must authenticate.
+    /// @notice Replaces the checkpoints of a custom schedule before vesting starts.
+    /// @dev Allowed only while `now < start_time` and nothing has been released.
+    ///      The new checkpoints go through the same validation as
+    ///      `create_custom_schedule` and must end at the existing `total_amount`.
+    /// @param employer Employer that created the schedule; must authenticate.
     /// @param schedule_id Vesting schedule identifier.
-    /// @return amount Claimed token amount.
-    pub fn claim(env: Env, beneficiary: Address, schedule_id: u128) -> i128 {
+    /// @param new_checkpoints Replacement checkpoint list.
+    pub fn update_checkpoints(
+        env: Env,
+        employer: Address,
+        schedule_id: u128,
+        new_checkpoints: Vec<CustomCheckpoint>,
+    ) {
         require_initialized(&env);
-        beneficiary.require_auth();
+        require_not_paused(&env);
+        employer.require_auth();
 
         let mut schedule = read_schedule(&env, schedule_id);
         assert!(
-            schedule.beneficiary == beneficiary,
-            "Only beneficiary can claim"
+            schedule.employer == employer,
+            "Only employer can update checkpoints"
+        );
+        assert!(
+            schedule.kind == VestingKind::Custom,
+            "Only custom schedules have checkpoints"
+        );
+        assert!(
+            schedule.status == VestingStatus::Active,
+            "Schedule not active"
+        );
+
+        let now = env.ledger().timestamp();
+        assert!(now < schedule.start_time, "Vesting already started");
+        assert!(schedule.released_amount == 0, "Tokens already released");
+
+        let (first_time, last_time) =
+            validate_checkpoints(now, &new_checkpoints, schedule.total_amount);
+
+        schedule.start_time = first_time;
+        schedule.end_time = last_time;
+        schedule.checkpoints = new_checkpoints;
+        write_schedule(&env, &schedule);
+
+        env.events().publish(
+            ("vesting_checkpoints_updated", schedule_id),
+            CheckpointsUpdatedEvent {
+                id: schedule_id,
+                employer,
+                start_time: first_time,
+                end_time: last_time,
+            },
         );
+    }
+
+    /// @notice Claims any vested but unreleased tokens for a schedule.
+    /// @dev May be called by the beneficiary or by the schedule's claim delegate.
+    ///      Tokens are always transferred to the stored beneficiary. Delegates
+    ///      cannot claim on revoked schedules. If a protocol fee is configured
+    ///      it is deducted from the payout and sent to the treasury.
+    /// @param caller Beneficiary or claim delegate; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @return amount Gross claimed amount (beneficiary payout plus fee).
+    pub fn claim(env: Env, caller: Address, schedule_id: u128) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        caller.require_auth();
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        if schedule.beneficiary != caller {
+            let delegate = read_claim_delegate(&env, schedule_id);
+            assert!(
+                delegate == Some(caller.clone()),
+                "Only beneficiary can claim"
+            );
+            assert!(
+                schedule.status != VestingStatus::Revoked,
+                "Delegate cannot claim on revoked schedule"
+            );
+        }
         assert!(
             schedule.status != VestingStatus::Completed,
             "Schedule already completed"
         );
+        require_claim_eligible(&env, &schedule.beneficiary);
 
         let now = env.ledger().timestamp();
+        if let Some(grace_end) = revocation_grace_end(&schedule) {
+            assert!(now < grace_end, "Revocation grace period expired");
+        }
         let amount = compute_releasable(now, &schedule);
         assert!(amount > 0, "Nothing to claim");
 
@@ -491,14 +1353,15 @@ impl TokenVestingContract {
         }
 
         write_schedule(&env, &schedule);
-        let token_client = token::Client::new(&env, &schedule.token);
-        token_client.transfer(&env.current_contract_address(), &beneficiary, &amount);
+        adjust_total_locked(&env, &schedule.token, -amount);
+        pay_beneficiary(&env, &schedule.token, &schedule.beneficiary, amount);
 
+        emit_vesting_claimed(&env, &schedule, amount, now);
         env.events().publish(
             ("vesting_claimed", schedule_id),
             ClaimedEvent {
                 id: schedule_id,
-                beneficiary,
+                beneficiary: schedule.beneficiary,
                 amount,
             },
         );
@@ -506,129 +1369,971 @@ impl TokenVestingContract {
         amount
     }
 
-    /// @notice Approves an early release of unvested tokens.
-    /// @dev Only the contract owner (admin) can approve early releases.
-    /// @param admin Contract owner; must authenticate.
+    /// @notice Sets or clears the address allowed to claim on the beneficiary's behalf.
+    /// @dev The delegate can only trigger `claim`; tokens still go to the
+    ///      beneficiary and the delegate cannot change the beneficiary.
+    /// @param beneficiary Schedule beneficiary; must authenticate.
     /// @param schedule_id Vesting schedule identifier.
-    /// @param amount Maximum early release amount requested.
-    /// @return released Actual amount released (capped at remaining unvested).
-    pub fn approve_early_release(
+    /// @param delegate New delegate, or `None` to revoke delegation.
+    pub fn set_claim_delegate(
         env: Env,
-        admin: Address,
+        beneficiary: Address,
         schedule_id: u128,
-        amount: i128,
-    ) -> i128 {
+        delegate: Option<Address>,
+    ) {
         require_initialized(&env);
-        admin.require_auth();
-
-        let owner = read_owner(&env);
-        assert!(admin == owner, "Only owner can approve early release");
-        assert!(amount > 0, "Amount must be positive");
-
-        let mut schedule = read_schedule(&env, schedule_id);
-        assert!(
-            schedule.status == VestingStatus::Active,
-            "Schedule not active"
-        );
+        beneficiary.require_auth();
 
-        let now = env.ledger().timestamp();
-        let vested = compute_vested_amount(now, &schedule);
-        let unvested_remaining = schedule.total_amount.checked_sub(vested).unwrap_or(0);
+        let schedule = read_schedule(&env, schedule_id);
         assert!(
-            unvested_remaining > 0,
-            "No unvested tokens remain for early release"
+            schedule.beneficiary == beneficiary,
+            "Only beneficiary can set delegate"
         );
 
-        let release_amount = if amount > unvested_remaining {
-            unvested_remaining
-        } else {
-            amount
-        };
-
-        // Checks-effects-interactions: move accounting update before transfer.
-        schedule.released_amount = schedule
-            .released_amount
-            .checked_add(release_amount)
-            .expect("Released amount overflow");
-
-        if schedule.released_amount >= schedule.total_amount {
-            schedule.status = VestingStatus::Completed;
-        }
-
-        write_schedule(&env, &schedule);
-        let token_client = token::Client::new(&env, &schedule.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &schedule.beneficiary,
-            &release_amount,
-        );
+        write_claim_delegate(&env, schedule_id, &delegate);
 
         env.events().publish(
-            ("vesting_early_release", schedule_id),
-            EarlyReleaseEvent {
+            ("vesting_delegate_set", schedule_id),
+            ClaimDelegateSetEvent {
                 id: schedule_id,
-                admin,
-                amount: release_amount,
+                beneficiary,
+                delegate,
             },
         );
-
-        release_amount
     }
 
-    /// @notice Revokes a revocable schedule for a terminated employee.
-    /// @dev Employer recovers unvested tokens; vested portion remains claimable.
-    /// @param employer Employer that created the schedule; must authenticate.
+    /// @notice Transfers a schedule to a new beneficiary address.
+    /// @dev Only the current beneficiary can call this; any claim delegate or
+    ///      pending recovery is cleared so it does not carry over to the new
+    ///      beneficiary.
+    /// @param beneficiary Current schedule beneficiary; must authenticate.
     /// @param schedule_id Vesting schedule identifier.
-    /// @return refunded_amount Amount of unvested tokens refunded to employer.
-    pub fn revoke(env: Env, employer: Address, schedule_id: u128) -> i128 {
+    /// @param new_beneficiary Address that will receive future claims.
+    pub fn change_beneficiary(
+        env: Env,
+        beneficiary: Address,
+        schedule_id: u128,
+        new_beneficiary: Address,
+    ) {
         require_initialized(&env);
-        employer.require_auth();
+        beneficiary.require_auth();
 
-        let mut schedule = read_schedule(&env, schedule_id);
-        assert!(schedule.employer == employer, "Only employer can revoke");
-        assert!(schedule.revocable, "Schedule is not revocable");
+        let schedule = read_schedule(&env, schedule_id);
         assert!(
-            schedule.status == VestingStatus::Active,
-            "Schedule not active"
+            schedule.beneficiary == beneficiary,
+            "Only beneficiary can change beneficiary"
+        );
+        assert!(
+            schedule.status != VestingStatus::Completed,
+            "Schedule already completed"
         );
 
-        let now = env.ledger().timestamp();
-        let vested = compute_vested_amount(now, &schedule);
-        let unvested = schedule.total_amount.checked_sub(vested).unwrap_or(0);
-        assert!(unvested >= 0, "Invalid vesting state");
+        apply_beneficiary_change(&env, schedule, new_beneficiary);
+    }
 
-        schedule.status = VestingStatus::Revoked;
-        schedule.revoked_at = Some(now);
-        write_schedule(&env, &schedule);
+    /// @notice Starts an owner-assisted recovery for a beneficiary who lost their key.
+    /// @dev Records a pending change that can be finalized after the recovery
+    ///      timelock. A new call replaces any pending recovery.
+    /// @param owner Contract owner; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @param new_beneficiary Address that will replace the current beneficiary.
+    pub fn initiate_recovery(
+        env: Env,
+        owner: Address,
+        schedule_id: u128,
+        new_beneficiary: Address,
+    ) {
+        require_initialized(&env);
+        owner.require_auth();
+        assert!(
+            owner == read_owner(&env),
+            "Only owner can initiate recovery"
+        );
 
-        if unvested > 0 {
-            let token_client = token::Client::new(&env, &schedule.token);
-            token_client.transfer(&env.current_contract_address(), &employer, &unvested);
-        }
+        let schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.status != VestingStatus::Completed,
+            "Schedule already completed"
+        );
+
+        let executable_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(read_recovery_timelock(&env))
+            .expect("Recovery timelock overflow");
+        let pending = PendingRecovery {
+            schedule_id,
+            admin: owner.clone(),
+            new_beneficiary: new_beneficiary.clone(),
+            executable_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&StorageKey::PendingRecovery(schedule_id), &pending);
 
         env.events().publish(
-            ("vesting_revoked", schedule_id),
-            RevokedEvent {
+            ("vesting_recovery_initiated", schedule_id),
+            RecoveryInitiatedEvent {
                 id: schedule_id,
-                employer,
-                refunded: unvested,
-                at: now,
+                admin: owner,
+                new_beneficiary,
+                executable_at,
             },
         );
-
-        unvested
     }
 
-    /// @notice Reads a vesting schedule by id.
-    /// @param schedule_id Unique identifier of the schedule to look up.
-    /// @return `Option<VestingSchedule>` — `None` if `schedule_id` does not exist.
-    /// @dev Read-only; no authentication required.
-    pub fn get_schedule(env: Env, schedule_id: u128) -> Option<VestingSchedule> {
-        env.storage()
+    /// @notice Cancels a pending recovery, proving the beneficiary still holds their key.
+    /// @param beneficiary Current schedule beneficiary; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    pub fn cancel_recovery(env: Env, beneficiary: Address, schedule_id: u128) {
+        require_initialized(&env);
+        beneficiary.require_auth();
+
+        let schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.beneficiary == beneficiary,
+            "Only beneficiary can cancel recovery"
+        );
+        assert!(
+            read_pending_recovery(&env, schedule_id).is_some(),
+            "No pending recovery"
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::PendingRecovery(schedule_id));
+
+        env.events().publish(
+            ("vesting_recovery_cancelled", schedule_id),
+            RecoveryCancelledEvent {
+                id: schedule_id,
+                beneficiary,
+            },
+        );
+    }
+
+    /// @notice Applies a pending recovery once its timelock has elapsed.
+    /// @dev Permissionless: anyone may call after `executable_at`. Emits
+    ///      `BeneficiaryChangedEvent` like `change_beneficiary`.
+    /// @param schedule_id Vesting schedule identifier.
+    pub fn finalize_recovery(env: Env, schedule_id: u128) {
+        require_initialized(&env);
+
+        let pending = read_pending_recovery(&env, schedule_id).expect("No pending recovery");
+        assert!(
+            env.ledger().timestamp() >= pending.executable_at,
+            "Recovery timelock not elapsed"
+        );
+
+        let schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.status != VestingStatus::Completed,
+            "Schedule already completed"
+        );
+
+        apply_beneficiary_change(&env, schedule, pending.new_beneficiary);
+    }
+
+    /// @notice Sets the delay between `initiate_recovery` and `finalize_recovery`.
+    /// @dev Only affects recoveries initiated afterwards.
+    /// @param admin Contract owner; must authenticate.
+    /// @param timelock_seconds Timelock in seconds (must be > 0).
+    pub fn set_recovery_timelock(env: Env, admin: Address, timelock_seconds: u64) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can set recovery timelock");
+        assert!(timelock_seconds > 0, "Timelock must be positive");
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::RecoveryTimelock, &timelock_seconds);
+    }
+
+    /// @notice Approves an early release of unvested tokens.
+    /// @dev Only the contract owner (admin) can approve early releases. Nothing
+    ///      is transferred here: the approval is recorded as pending and the
+    ///      beneficiary must call `accept_early_release` within the configured
+    ///      window. A new approval replaces any existing pending one.
+    /// @param admin Contract owner; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @param amount Maximum early release amount requested.
+    /// @return approved Approved amount (capped at the tokens that have
+    ///         neither vested nor been released early).
+    pub fn approve_early_release(
+        env: Env,
+        admin: Address,
+        schedule_id: u128,
+        amount: i128,
+    ) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can approve early release");
+        assert!(amount > 0, "Amount must be positive");
+
+        let schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.status == VestingStatus::Active,
+            "Schedule not active"
+        );
+
+        let now = env.ledger().timestamp();
+        let unvested_remaining = compute_remaining_unvested(now, &schedule);
+        assert!(
+            unvested_remaining > 0,
+            "No unvested tokens remain for early release"
+        );
+
+        let approved_amount = if amount > unvested_remaining {
+            unvested_remaining
+        } else {
+            amount
+        };
+
+        let pending = PendingEarlyRelease {
+            schedule_id,
+            admin: admin.clone(),
+            amount: approved_amount,
+            approved_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&StorageKey::PendingEarlyRelease(schedule_id), &pending);
+
+        env.events().publish(
+            ("vesting_early_release_approved", schedule_id),
+            EarlyReleaseApprovedEvent {
+                id: schedule_id,
+                admin,
+                amount: approved_amount,
+                expires_at: now.saturating_add(read_early_release_window(&env)),
+            },
+        );
+
+        approved_amount
+    }
+
+    /// @notice Accepts a pending early release and receives the tokens.
+    /// @dev The released amount is re-capped at the unvested, unreleased
+    ///      remainder at acceptance time, since vesting may have progressed
+    ///      since approval.
+    ///      The protocol fee, if any, is deducted as in `claim`.
+    /// @param beneficiary Schedule beneficiary; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @return released Actual amount released.
+    pub fn accept_early_release(env: Env, beneficiary: Address, schedule_id: u128) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        beneficiary.require_auth();
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.beneficiary == beneficiary,
+            "Only beneficiary can accept early release"
+        );
+        assert!(
+            schedule.status == VestingStatus::Active,
+            "Schedule not active"
+        );
+        require_claim_eligible(&env, &beneficiary);
+
+        let pending =
+            read_pending_early_release(&env, schedule_id).expect("No pending early release");
+        let now = env.ledger().timestamp();
+        let expires_at = pending
+            .approved_at
+            .saturating_add(read_early_release_window(&env));
+        assert!(now <= expires_at, "Early release approval expired");
+
+        let unvested_remaining = compute_remaining_unvested(now, &schedule);
+        assert!(
+            unvested_remaining > 0,
+            "No unvested tokens remain for early release"
+        );
+
+        let release_amount = if pending.amount > unvested_remaining {
+            unvested_remaining
+        } else {
+            pending.amount
+        };
+
+        // Checks-effects-interactions: move accounting update before transfer.
+        schedule.released_amount = schedule
+            .released_amount
+            .checked_add(release_amount)
+            .expect("Released amount overflow");
+
+        if schedule.released_amount >= schedule.total_amount {
+            schedule.status = VestingStatus::Completed;
+        }
+
+        write_schedule(&env, &schedule);
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::PendingEarlyRelease(schedule_id));
+        adjust_total_locked(&env, &schedule.token, -release_amount);
+        pay_beneficiary(&env, &schedule.token, &schedule.beneficiary, release_amount);
+
+        emit_vesting_claimed(&env, &schedule, release_amount, now);
+        env.events().publish(
+            ("vesting_early_release", schedule_id),
+            EarlyReleaseEvent {
+                id: schedule_id,
+                admin: pending.admin,
+                amount: release_amount,
+            },
+        );
+
+        release_amount
+    }
+
+    /// @notice Cancels a pending early release approval.
+    /// @param admin Contract owner; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    pub fn cancel_early_release(env: Env, admin: Address, schedule_id: u128) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can cancel early release");
+        assert!(
+            read_pending_early_release(&env, schedule_id).is_some(),
+            "No pending early release"
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::PendingEarlyRelease(schedule_id));
+
+        env.events().publish(
+            ("vesting_early_release_cancelled", schedule_id),
+            EarlyReleaseCancelledEvent {
+                id: schedule_id,
+                admin,
+            },
+        );
+    }
+
+    /// @notice Adds or removes a contract from the `create_schedule_for` whitelist.
+    /// @param admin Contract owner; must authenticate.
+    /// @param creator Contract address allowed to create schedules.
+    /// @param authorized `true` to whitelist, `false` to remove.
+    pub fn set_authorized_creator(env: Env, admin: Address, creator: Address, authorized: bool) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can set authorized creators");
+
+        let key = StorageKey::AuthorizedCreator(creator.clone());
+        if authorized {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events().publish(
+            ("vesting_creator_set",),
+            AuthorizedCreatorSetEvent {
+                creator,
+                authorized,
+            },
+        );
+    }
+
+    /// @notice Turns per-beneficiary claim eligibility gating on or off.
+    /// @dev While enabled, `claim` and `accept_early_release` fail with
+    ///      `VestingError::BeneficiaryNotEligible` for beneficiaries not marked
+    ///      eligible via `set_claim_eligibility`. Vesting keeps accruing.
+    /// @param admin Contract owner; must authenticate.
+    /// @param required `true` to enforce eligibility, `false` to allow everyone.
+    pub fn set_require_eligibility(env: Env, admin: Address, required: bool) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can configure eligibility");
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::RequireEligibility, &required);
+
+        env.events()
+            .publish(("vesting_eligibility_required",), required);
+    }
+
+    /// @notice Marks a beneficiary as eligible or ineligible to claim (e.g. KYC status).
+    /// @param admin Contract owner; must authenticate.
+    /// @param beneficiary Beneficiary address.
+    /// @param eligible Whether the beneficiary may claim while gating is enabled.
+    pub fn set_claim_eligibility(env: Env, admin: Address, beneficiary: Address, eligible: bool) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can set eligibility");
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::ClaimEligible(beneficiary.clone()), &eligible);
+
+        env.events().publish(
+            ("vesting_eligibility",),
+            EligibilityChangedEvent {
+                beneficiary,
+                eligible,
+            },
+        );
+    }
+
+    /// @notice Pauses or unpauses all state-changing vesting operations.
+    /// @dev While paused, schedule creation, `claim`, early release approval and
+    ///      acceptance, and `revoke` fail with `VestingError::ContractPaused`.
+    ///      View functions keep working and vesting continues to accrue.
+    /// @param admin Contract owner; must authenticate.
+    /// @param paused `true` to pause, `false` to unpause.
+    pub fn set_paused(env: Env, admin: Address, paused: bool) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can pause");
+
+        env.storage().persistent().set(&StorageKey::Paused, &paused);
+
+        let topic = if paused {
+            "vesting_paused"
+        } else {
+            "vesting_unpaused"
+        };
+        env.events()
+            .publish((topic,), PauseChangedEvent { admin, paused });
+    }
+
+    /// @notice Configures the protocol fee taken from claims and early releases.
+    /// @dev `released_amount` always records the gross amount, so vesting math
+    ///      is unaffected by the fee. A `fee_bps` of 0 disables the fee.
+    /// @param admin Contract owner; must authenticate.
+    /// @param fee_bps Fee in basis points (max 500).
+    /// @param treasury Address receiving collected fees.
+    pub fn set_fee_config(env: Env, admin: Address, fee_bps: u32, treasury: Address) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can set fee config");
+        assert!(fee_bps <= MAX_FEE_BPS, "Fee exceeds maximum");
+
+        let config = FeeConfig {
+            fee_bps,
+            treasury: treasury.clone(),
+        };
+        env.storage()
+            .persistent()
+            .set(&StorageKey::FeeConfig, &config);
+
+        env.events().publish(
+            ("vesting_fee_config",),
+            FeeConfigUpdatedEvent { fee_bps, treasury },
+        );
+    }
+
+    /// @notice Sets how long a beneficiary has to accept an early release approval.
+    /// @param admin Contract owner; must authenticate.
+    /// @param window_seconds Acceptance window in seconds (must be > 0).
+    pub fn set_early_release_window(env: Env, admin: Address, window_seconds: u64) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can set early release window");
+        assert!(window_seconds > 0, "Window must be positive");
+
+        env.storage()
+            .persistent()
+            .set(&StorageKey::EarlyReleaseWindow, &window_seconds);
+    }
+
+    /// @notice Sets how long beneficiaries of revoked schedules may still claim.
+    /// @dev Applies to schedules revoked from now on, measured from
+    ///      `revoked_at`; schedules already revoked keep their `grace_end`.
+    ///      `None` (the default) leaves the vested remainder claimable forever.
+    /// @param admin Contract owner; must authenticate.
+    /// @param grace_seconds Grace window in seconds, or `None` to disable.
+    pub fn set_revocation_grace(env: Env, admin: Address, grace_seconds: Option<u64>) {
+        require_initialized(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can set revocation grace");
+
+        match grace_seconds {
+            Some(grace) => env
+                .storage()
+                .persistent()
+                .set(&StorageKey::RevocationGrace, &grace),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&StorageKey::RevocationGrace),
+        }
+    }
+
+    /// @notice Revokes a revocable schedule for a terminated employee.
+    /// @dev Callable by the employer or the schedule's configured revoker. The
+    ///      unvested refund (less anything released early) always goes to the
+    ///      employer; the vested portion
+    ///      remains claimable (until the revocation grace period, if configured,
+    ///      expires).
+    /// @param caller Employer or configured revoker; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @return refunded_amount Amount of unvested tokens refunded to employer.
+    pub fn revoke(env: Env, caller: Address, schedule_id: u128) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        caller.require_auth();
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.employer == caller || schedule.revoker == Some(caller),
+            "Only employer or revoker can revoke"
+        );
+        let employer = schedule.employer.clone();
+        assert!(schedule.revocable, "Schedule is not revocable");
+        assert!(
+            schedule.status == VestingStatus::Active,
+            "Schedule not active"
+        );
+
+        let now = env.ledger().timestamp();
+        // Tokens already released early are not refunded a second time.
+        let unvested = compute_remaining_unvested(now, &schedule);
+
+        schedule.status = VestingStatus::Revoked;
+        schedule.revoked_at = Some(now);
+        schedule.grace_end = read_revocation_grace(&env).map(|grace| {
+            now.checked_add(grace)
+                .expect("Grace end timestamp overflow")
+        });
+        write_schedule(&env, &schedule);
+
+        if unvested > 0 {
+            adjust_total_locked(&env, &schedule.token, -unvested);
+            require_escrow(&env, &schedule.token, unvested);
+            let token_client = token::Client::new(&env, &schedule.token);
+            token_client.transfer(&env.current_contract_address(), &employer, &unvested);
+        }
+
+        env.events().publish(
+            ("vesting_revoked", schedule_id),
+            RevokedEvent {
+                id: schedule_id,
+                employer,
+                refunded: unvested,
+                at: now,
+            },
+        );
+
+        unvested
+    }
+
+    /// @notice Proposes a mutually agreed cancellation of an active schedule.
+    /// @dev Works regardless of the `revocable` flag; nothing happens until the
+    ///      beneficiary calls `accept_cancellation`. A new proposal replaces any
+    ///      pending one.
+    /// @param employer Employer that created the schedule; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @param vested_to_beneficiary Total amount the beneficiary keeps, including
+    ///        anything already released (between `released_amount` and `total_amount`).
+    pub fn propose_cancellation(
+        env: Env,
+        employer: Address,
+        schedule_id: u128,
+        vested_to_beneficiary: i128,
+    ) {
+        require_initialized(&env);
+        require_not_paused(&env);
+        employer.require_auth();
+
+        let schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.employer == employer,
+            "Only employer can propose cancellation"
+        );
+        assert!(
+            schedule.status == VestingStatus::Active,
+            "Schedule not active"
+        );
+        assert!(
+            vested_to_beneficiary >= schedule.released_amount
+                && vested_to_beneficiary <= schedule.total_amount,
+            "Invalid settlement amount"
+        );
+
+        let pending = PendingCancellation {
+            schedule_id,
+            employer: employer.clone(),
+            vested_to_beneficiary,
+            proposed_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&StorageKey::PendingCancellation(schedule_id), &pending);
+
+        env.events().publish(
+            ("vesting_cancel_proposed", schedule_id),
+            CancellationProposedEvent {
+                id: schedule_id,
+                employer,
+                vested_to_beneficiary,
+            },
+        );
+    }
+
+    /// @notice Withdraws a pending cancellation proposal.
+    /// @param employer Employer that created the schedule; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    pub fn withdraw_cancellation(env: Env, employer: Address, schedule_id: u128) {
+        require_initialized(&env);
+        employer.require_auth();
+
+        let schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.employer == employer,
+            "Only employer can withdraw cancellation"
+        );
+        assert!(
+            read_pending_cancellation(&env, schedule_id).is_some(),
+            "No pending cancellation"
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::PendingCancellation(schedule_id));
+
+        env.events().publish(
+            ("vesting_cancel_withdrawn", schedule_id),
+            CancellationWithdrawnEvent {
+                id: schedule_id,
+                employer,
+            },
+        );
+    }
+
+    /// @notice Accepts the employer's cancellation proposal and settles the schedule.
+    /// @dev Pays `vested_to_beneficiary - released_amount` to the beneficiary and
+    ///      refunds `total_amount - vested_to_beneficiary` to the employer. The
+    ///      schedule becomes `Revoked` at `now` with `total_amount` reduced to the
+    ///      agreed amount, so nothing further can be claimed.
+    /// @param beneficiary Schedule beneficiary; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @return paid Amount paid to the beneficiary on acceptance.
+    pub fn accept_cancellation(env: Env, beneficiary: Address, schedule_id: u128) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        beneficiary.require_auth();
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.beneficiary == beneficiary,
+            "Only beneficiary can accept cancellation"
+        );
+        assert!(
+            schedule.status == VestingStatus::Active,
+            "Schedule not active"
+        );
+        let pending =
+            read_pending_cancellation(&env, schedule_id).expect("No pending cancellation");
+        assert!(
+            pending.vested_to_beneficiary >= schedule.released_amount,
+            "Released amount exceeds settlement"
+        );
+
+        let now = env.ledger().timestamp();
+        let paid = pending.vested_to_beneficiary - schedule.released_amount;
+        let refunded = schedule.total_amount - pending.vested_to_beneficiary;
+
+        schedule.released_amount = pending.vested_to_beneficiary;
+        schedule.total_amount = pending.vested_to_beneficiary;
+        schedule.status = VestingStatus::Revoked;
+        schedule.revoked_at = Some(now);
+        write_schedule(&env, &schedule);
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::PendingCancellation(schedule_id));
+        env.storage()
+            .persistent()
+            .remove(&StorageKey::PendingEarlyRelease(schedule_id));
+        adjust_total_locked(&env, &schedule.token, -(paid + refunded));
+
+        if paid > 0 {
+            require_claim_eligible(&env, &beneficiary);
+            pay_beneficiary(&env, &schedule.token, &beneficiary, paid);
+        }
+        if refunded > 0 {
+            require_escrow(&env, &schedule.token, refunded);
+            let token_client = token::Client::new(&env, &schedule.token);
+            token_client.transfer(
+                &env.current_contract_address(),
+                &schedule.employer,
+                &refunded,
+            );
+        }
+
+        env.events().publish(
+            ("vesting_cancelled", schedule_id),
+            CancellationAcceptedEvent {
+                id: schedule_id,
+                beneficiary,
+                paid,
+                refunded,
+                at: now,
+            },
+        );
+
+        paid
+    }
+
+    /// @notice Sets or clears the additional address allowed to revoke a schedule.
+    /// @param employer Employer that created the schedule; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @param revoker New revoker, or `None` to leave revocation to the employer only.
+    pub fn set_revoker(env: Env, employer: Address, schedule_id: u128, revoker: Option<Address>) {
+        require_initialized(&env);
+        employer.require_auth();
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        assert!(
+            schedule.employer == employer,
+            "Only employer can set revoker"
+        );
+
+        schedule.revoker = revoker.clone();
+        write_schedule(&env, &schedule);
+
+        env.events().publish(
+            ("vesting_revoker_set", schedule_id),
+            RevokerSetEvent {
+                id: schedule_id,
+                employer,
+                revoker,
+            },
+        );
+    }
+
+    /// @notice Moves an active schedule to a replacement token contract.
+    /// @dev Requires both the contract owner and the schedule employer. The
+    ///      unreleased remainder (`total_amount - released_amount`) is escrowed
+    ///      in `new_token` from the employer and the same amount of the old
+    ///      token is returned to the employer. Released amounts are kept as-is.
+    /// @param admin Contract owner; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @param new_token Replacement token contract address.
+    /// @return migrated Amount re-escrowed in the new token.
+    pub fn migrate_schedule_token(
+        env: Env,
+        admin: Address,
+        schedule_id: u128,
+        new_token: Address,
+    ) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        admin.require_auth();
+
+        let owner = read_owner(&env);
+        assert!(admin == owner, "Only owner can migrate tokens");
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        schedule.employer.require_auth();
+        assert!(
+            schedule.status == VestingStatus::Active,
+            "Schedule not active"
+        );
+        assert!(
+            schedule.token != new_token,
+            "Schedule already uses this token"
+        );
+
+        let remaining = schedule
+            .total_amount
+            .checked_sub(schedule.released_amount)
+            .expect("Invalid vesting state");
+        let old_token = schedule.token.clone();
+
+        schedule.token = new_token.clone();
+        write_schedule(&env, &schedule);
+        adjust_total_locked(&env, &old_token, -remaining);
+        adjust_total_locked(&env, &new_token, remaining);
+
+        let this = env.current_contract_address();
+        if remaining > 0 {
+            token::Client::new(&env, &new_token).transfer(&schedule.employer, &this, &remaining);
+            token::Client::new(&env, &old_token).transfer(&this, &schedule.employer, &remaining);
+        }
+
+        env.events().publish(
+            ("vesting_token_migrated", schedule_id),
+            TokenMigratedEvent {
+                id: schedule_id,
+                old_token,
+                new_token,
+                amount: remaining,
+            },
+        );
+
+        remaining
+    }
+
+    /// @notice Returns unclaimed vested tokens to the employer after the claim deadline.
+    /// @dev Only available on schedules created with a `claim_deadline`, once
+    ///      `now > end_time + claim_deadline`. The schedule is marked `Completed`
+    ///      and the swept amount is recorded in `swept_amount`, so any later
+    ///      `claim` fails with "Schedule already completed".
+    /// @param employer Employer that created the schedule; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @return swept Amount returned to the employer.
+    pub fn sweep_expired(env: Env, employer: Address, schedule_id: u128) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        employer.require_auth();
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        assert!(schedule.employer == employer, "Only employer can sweep");
+        assert!(
+            schedule.status != VestingStatus::Completed,
+            "Schedule already completed"
+        );
+        let deadline = schedule
+            .claim_deadline
+            .expect("Schedule has no claim deadline");
+
+        let now = env.ledger().timestamp();
+        let expires_at = schedule
+            .end_time
+            .checked_add(deadline)
+            .expect("Claim deadline overflow");
+        assert!(now > expires_at, "Claim deadline not reached");
+
+        let amount = compute_releasable(now, &schedule);
+        assert!(amount > 0, "Nothing to sweep");
+
+        schedule.swept_amount = amount;
+        schedule.status = VestingStatus::Completed;
+        write_schedule(&env, &schedule);
+        adjust_total_locked(&env, &schedule.token, -amount);
+
+        require_escrow(&env, &schedule.token, amount);
+        let token_client = token::Client::new(&env, &schedule.token);
+        token_client.transfer(&env.current_contract_address(), &employer, &amount);
+
+        env.events().publish(
+            ("vesting_swept", schedule_id),
+            SweptEvent {
+                id: schedule_id,
+                employer,
+                amount,
+            },
+        );
+
+        amount
+    }
+
+    /// @notice Returns the vested remainder of a revoked schedule to the employer
+    ///         once the revocation grace period has expired.
+    /// @dev Requires the schedule to have been revoked with a grace period and
+    ///      `now >= grace_end`.
+    ///      The schedule is marked `Completed` and the amount recorded in
+    ///      `swept_amount`.
+    /// @param employer Employer that created the schedule; must authenticate.
+    /// @param schedule_id Vesting schedule identifier.
+    /// @return reclaimed Amount returned to the employer.
+    pub fn reclaim_after_grace(env: Env, employer: Address, schedule_id: u128) -> i128 {
+        require_initialized(&env);
+        require_not_paused(&env);
+        employer.require_auth();
+
+        let mut schedule = read_schedule(&env, schedule_id);
+        assert!(schedule.employer == employer, "Only employer can reclaim");
+        assert!(
+            schedule.status == VestingStatus::Revoked,
+            "Schedule not revoked"
+        );
+        let grace_end = revocation_grace_end(&schedule).expect("Schedule has no revocation grace");
+
+        let now = env.ledger().timestamp();
+        assert!(now >= grace_end, "Grace period has not expired yet");
+
+        let amount = compute_releasable(now, &schedule);
+        assert!(amount > 0, "Nothing to reclaim");
+
+        schedule.swept_amount = amount;
+        schedule.status = VestingStatus::Completed;
+        write_schedule(&env, &schedule);
+        adjust_total_locked(&env, &schedule.token, -amount);
+
+        require_escrow(&env, &schedule.token, amount);
+        let token_client = token::Client::new(&env, &schedule.token);
+        token_client.transfer(&env.current_contract_address(), &employer, &amount);
+
+        env.events().publish(
+            ("vesting_grace_reclaimed", schedule_id),
+            GraceReclaimedEvent {
+                id: schedule_id,
+                employer,
+                amount,
+            },
+        );
+
+        amount
+    }
+
+    /// @notice Reads a vesting schedule by id.
+    /// @param schedule_id Unique identifier of the schedule to look up.
+    /// @return `Option<VestingSchedule>` — `None` if `schedule_id` does not exist.
+    /// @dev Read-only; no authentication required.
+    pub fn get_schedule(env: Env, schedule_id: u128) -> Option<VestingSchedule> {
+        env.storage()
             .persistent()
             .get(&StorageKey::Schedule(schedule_id))
     }
 
+    /// @notice Lists existing schedules in ascending id order.
+    /// @dev Read-only; no authentication required. Ids without a stored
+    ///      schedule are skipped. `limit` is capped at 50.
+    /// @param start_id First schedule id to consider (0 is treated as 1).
+    /// @param limit Maximum number of schedules to return.
+    /// @return Vec<VestingSchedule> Up to `limit` schedules with `id >= start_id`.
+    pub fn list_schedules(env: Env, start_id: u128, limit: u32) -> Vec<VestingSchedule> {
+        let mut result: Vec<VestingSchedule> = Vec::new(&env);
+        let limit = if limit > MAX_LIST_LIMIT {
+            MAX_LIST_LIMIT
+        } else {
+            limit
+        };
+        let last_id = env
+            .storage()
+            .persistent()
+            .get::<_, u128>(&StorageKey::NextScheduleId)
+            .unwrap_or(0);
+
+        let mut id = if start_id == 0 { 1 } else { start_id };
+        while id <= last_id && result.len() < limit {
+            if let Some(schedule) = env
+                .storage()
+                .persistent()
+                .get::<_, VestingSchedule>(&StorageKey::Schedule(id))
+            {
+                result.push_back(schedule);
+            }
+            id += 1;
+        }
+        result
+    }
+
     /// @notice Returns the cumulative amount vested so far for a schedule.
     /// @param schedule_id Unique identifier of the schedule.
     /// @dev Read-only; no authentication required.
@@ -638,6 +2343,26 @@ impl TokenVestingContract {
         compute_vested_amount(now, &schedule)
     }
 
+    /// @notice Evaluates the vesting curve of a schedule at arbitrary timestamps.
+    /// @dev Read-only; no authentication required. Uses the same math as
+    ///      `get_vested_amount`, including the frozen clock of revoked schedules.
+    /// @param schedule_id Unique identifier of the schedule.
+    /// @param timestamps Points to evaluate (at most 100).
+    /// @return Vec<i128> Cumulative vested amount at each input timestamp, in order.
+    pub fn preview_vesting(env: Env, schedule_id: u128, timestamps: Vec<u64>) -> Vec<i128> {
+        assert!(
+            timestamps.len() <= MAX_PREVIEW_POINTS,
+            "Too many timestamps"
+        );
+        let schedule = read_schedule(&env, schedule_id);
+
+        let mut amounts: Vec<i128> = Vec::new(&env);
+        for at in timestamps.iter() {
+            amounts.push_back(compute_vested_amount(at, &schedule));
+        }
+        amounts
+    }
+
     /// @notice Returns the currently releasable (claimable) amount.
     /// @param schedule_id Unique identifier of the schedule.
     /// @dev Read-only; no authentication required. Equals vested minus already released.
@@ -647,6 +2372,121 @@ impl TokenVestingContract {
         compute_releasable(now, &schedule)
     }
 
+    /// @notice Simulates the claimable amount at an arbitrary timestamp.
+    /// @dev Read-only; no authentication required. Uses the same math as
+    ///      `get_releasable_amount` (revoked schedules stay frozen at
+    ///      `revoked_at`); `Completed` schedules always return 0.
+    /// @param schedule_id Unique identifier of the schedule.
+    /// @param at Timestamp to evaluate, past or future.
+    /// @return i128 Vested at `at` minus already released, floored at 0.
+    pub fn get_claimable_at(env: Env, schedule_id: u128, at: u64) -> i128 {
+        let schedule = read_schedule(&env, schedule_id);
+        if schedule.status == VestingStatus::Completed {
+            return 0;
+        }
+        compute_releasable(at, &schedule)
+    }
+
+    /// @notice Returns the pending early release approval for a schedule, if any.
+    /// @dev Read-only; no authentication required. Expired approvals are still
+    ///      returned until replaced or cancelled.
+    pub fn get_pending_early_release(env: Env, schedule_id: u128) -> Option<PendingEarlyRelease> {
+        read_pending_early_release(&env, schedule_id)
+    }
+
+    /// @notice Returns the early release acceptance window in seconds.
+    /// @dev Read-only; no authentication required.
+    pub fn get_early_release_window(env: Env) -> u64 {
+        read_early_release_window(&env)
+    }
+
+    /// @notice Returns the revocation grace period in seconds, if configured.
+    /// @dev Read-only; no authentication required.
+    pub fn get_revocation_grace(env: Env) -> Option<u64> {
+        read_revocation_grace(&env)
+    }
+
+    /// @notice Returns the pending cancellation proposal for a schedule, if any.
+    /// @dev Read-only; no authentication required.
+    pub fn get_pending_cancellation(env: Env, schedule_id: u128) -> Option<PendingCancellation> {
+        read_pending_cancellation(&env, schedule_id)
+    }
+
+    /// @notice Returns the pending beneficiary recovery for a schedule, if any.
+    /// @dev Read-only; no authentication required.
+    pub fn get_pending_recovery(env: Env, schedule_id: u128) -> Option<PendingRecovery> {
+        read_pending_recovery(&env, schedule_id)
+    }
+
+    /// @notice Returns the beneficiary recovery timelock in seconds.
+    /// @dev Read-only; no authentication required.
+    pub fn get_recovery_timelock(env: Env) -> u64 {
+        read_recovery_timelock(&env)
+    }
+
+    /// @notice Returns the claim delegate configured for a schedule, if any.
+    /// @dev Read-only; no authentication required.
+    pub fn get_claim_delegate(env: Env, schedule_id: u128) -> Option<Address> {
+        read_claim_delegate(&env, schedule_id)
+    }
+
+    /// @notice Returns the total amount of `token` the contract still owes.
+    /// @dev Read-only; no authentication required. Incremented on schedule
+    ///      creation and decremented on claims, early releases and revocation refunds.
+    pub fn get_total_locked(env: Env, token: Address) -> i128 {
+        read_total_locked(&env, &token)
+    }
+
+    /// @notice Returns the tracked obligation for `token` across all schedules.
+    /// @dev Read-only; no authentication required. Alias of `get_total_locked`.
+    pub fn get_obligations(env: Env, token: Address) -> i128 {
+        Self::get_total_locked(env, token)
+    }
+
+    /// @notice Compares the tracked obligation for `token` with the actual balance.
+    /// @dev Read-only; no authentication required. The invariant holds while
+    ///      `balance >= obligation`.
+    /// @return (obligation, balance) Tracked obligation and contract token balance.
+    pub fn check_invariant(env: Env, token: Address) -> (i128, i128) {
+        (
+            read_total_locked(&env, &token),
+            escrow_balance(&env, &token),
+        )
+    }
+
+    /// @notice Checks that the contract holds enough `token` to cover its obligations.
+    /// @dev Read-only; no authentication required.
+    /// @return bool `true` if the contract balance is at least the tracked total locked,
+    ///         i.e. the invariant reported by `check_invariant` holds.
+    pub fn verify_solvency(env: Env, token: Address) -> bool {
+        let (obligation, balance) = Self::check_invariant(env, token);
+        balance >= obligation
+    }
+
+    /// @notice Returns the protocol fee configuration, if one has been set.
+    /// @dev Read-only; no authentication required.
+    pub fn get_fee_config(env: Env) -> Option<FeeConfig> {
+        read_fee_config(&env)
+    }
+
+    /// @notice Returns whether `creator` may call `create_schedule_for`.
+    /// @dev Read-only; no authentication required.
+    pub fn is_authorized_creator(env: Env, creator: Address) -> bool {
+        read_authorized_creator(&env, &creator)
+    }
+
+    /// @notice Returns whether `beneficiary` can currently claim.
+    /// @dev Read-only; always `true` while eligibility gating is disabled.
+    pub fn is_claim_eligible(env: Env, beneficiary: Address) -> bool {
+        read_claim_eligible(&env, &beneficiary)
+    }
+
+    /// @notice Returns whether the contract is paused.
+    /// @dev Read-only; no authentication required.
+    pub fn is_paused(env: Env) -> bool {
+        read_paused(&env)
+    }
+
     /// @notice Returns the contract owner/admin.
     /// @dev Read-only; no authentication required.
     pub fn get_owner(env: Env) -> Option<Address> {
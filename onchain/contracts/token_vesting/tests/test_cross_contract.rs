@@ -0,0 +1,194 @@
+#![cfg(test)]
+
+//! Integration test: the payroll contract turning an escrow agreement's
+//! completion bonus into a vesting grant through `create_schedule_for`.
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env,
+};
+
+use stello_pay_contract::storage::{AgreementStatus, DataKey, PayrollError};
+use stello_pay_contract::{PayrollContract, PayrollContractClient};
+use token_vesting::{TokenVestingContract, TokenVestingContractClient, VestingKind, VestingStatus};
+
+const PERIOD: u64 = 100;
+const PER_PERIOD: i128 = 500;
+const BONUS: i128 = 1_000;
+
+struct Setup<'a> {
+    env: Env,
+    vesting: TokenVestingContractClient<'a>,
+    payroll: PayrollContractClient<'a>,
+    owner: Address,
+    employer: Address,
+    contributor: Address,
+    token: TokenClient<'a>,
+    agreement_id: u128,
+}
+
+/// Registers both contracts and creates a funded two-period escrow agreement
+/// with a `BONUS` completion bonus vesting over 100 seconds.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 0);
+
+    let vesting_id = env.register(TokenVestingContract, ());
+    let vesting = TokenVestingContractClient::new(&env, &vesting_id);
+    let payroll_id = env.register(PayrollContract, ());
+    let payroll = PayrollContractClient::new(&env, &payroll_id);
+
+    let owner = Address::generate(&env);
+    let employer = Address::generate(&env);
+    let contributor = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token_addr = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let token = TokenClient::new(&env, &token_addr);
+
+    vesting.initialize(&owner);
+    payroll.initialize(&owner);
+    payroll.set_vesting_contract(&owner, &vesting_id);
+
+    let agreement_id = payroll.create_escrow_agreement(
+        &employer,
+        &contributor,
+        &token_addr,
+        &PER_PERIOD,
+        &PERIOD,
+        &2u32,
+    );
+    payroll.activate_agreement(&agreement_id);
+
+    // Fund the agreement escrow and the employer's bonus.
+    let minter = StellarAssetClient::new(&env, &token_addr);
+    minter.mint(&payroll_id, &(PER_PERIOD * 2));
+    env.as_contract(&payroll_id, || {
+        DataKey::set_agreement_escrow_balance(&env, agreement_id, &token_addr, PER_PERIOD * 2);
+    });
+    minter.mint(&employer, &BONUS);
+    payroll.set_completion_bonus(&agreement_id, &BONUS, &100u64);
+
+    Setup {
+        env,
+        vesting,
+        payroll,
+        owner,
+        employer,
+        contributor,
+        token,
+        agreement_id,
+    }
+}
+
+#[test]
+fn completion_bonus_becomes_vesting_schedule() {
+    let s = setup();
+    s.vesting
+        .set_authorized_creator(&s.owner, &s.payroll.address, &true);
+    assert_eq!(s.token.balance(&s.payroll.address), PER_PERIOD * 2 + BONUS);
+
+    // The first claim does not complete the agreement.
+    s.env.ledger().with_mut(|li| li.timestamp = PERIOD);
+    s.payroll.claim_time_based(&s.agreement_id);
+    assert!(s.vesting.get_schedule(&1).is_none());
+
+    s.env.ledger().with_mut(|li| li.timestamp = 2 * PERIOD);
+    s.payroll.claim_time_based(&s.agreement_id);
+    assert_eq!(
+        s.payroll.get_agreement(&s.agreement_id).unwrap().status,
+        AgreementStatus::Completed
+    );
+    assert_eq!(s.payroll.get_completion_bonus(&s.agreement_id), None);
+
+    let schedule = s.vesting.get_schedule(&1).unwrap();
+    assert_eq!(schedule.employer, s.employer);
+    assert_eq!(schedule.beneficiary, s.contributor);
+    assert_eq!(schedule.kind, VestingKind::Linear);
+    assert_eq!(schedule.total_amount, BONUS);
+    assert_eq!(schedule.start_time, 2 * PERIOD);
+    assert_eq!(schedule.end_time, 3 * PERIOD);
+    assert!(!schedule.revocable);
+    assert_eq!(schedule.status, VestingStatus::Active);
+
+    assert_eq!(s.token.balance(&s.payroll.address), 0);
+    assert_eq!(s.token.balance(&s.vesting.address), BONUS);
+    assert_eq!(s.vesting.get_total_locked(&s.token.address), BONUS);
+
+    s.env.ledger().with_mut(|li| li.timestamp = 2 * PERIOD + 50);
+    assert_eq!(s.vesting.claim(&s.contributor, &1), 500);
+    assert_eq!(s.token.balance(&s.contributor), PER_PERIOD * 2 + 500);
+}
+
+#[test]
+fn unauthorized_payroll_cannot_complete_with_bonus() {
+    let s = setup();
+
+    // The vesting contract rejects the grant, so the completing claim reverts.
+    s.env.ledger().with_mut(|li| li.timestamp = 2 * PERIOD);
+    assert!(s.payroll.try_claim_time_based(&s.agreement_id).is_err());
+    assert_eq!(
+        s.payroll.get_agreement(&s.agreement_id).unwrap().status,
+        AgreementStatus::Active
+    );
+    assert_eq!(s.token.balance(&s.payroll.address), PER_PERIOD * 2 + BONUS);
+
+    // Removing the whitelist entry revokes access again.
+    s.vesting
+        .set_authorized_creator(&s.owner, &s.payroll.address, &true);
+    s.vesting
+        .set_authorized_creator(&s.owner, &s.payroll.address, &false);
+    assert!(!s.vesting.is_authorized_creator(&s.payroll.address));
+    assert!(s.payroll.try_claim_time_based(&s.agreement_id).is_err());
+
+    let stranger = Address::generate(&s.env);
+    assert!(s
+        .vesting
+        .try_set_authorized_creator(&stranger, &s.payroll.address, &true)
+        .is_err());
+}
+
+#[test]
+fn completion_bonus_requires_vesting_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let payroll = PayrollContractClient::new(&env, &env.register(PayrollContract, ()));
+    let owner = Address::generate(&env);
+    payroll.initialize(&owner);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let agreement_id = payroll.create_escrow_agreement(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token,
+        &PER_PERIOD,
+        &PERIOD,
+        &2u32,
+    );
+
+    assert_eq!(
+        payroll.try_set_completion_bonus(&agreement_id, &BONUS, &100u64),
+        Err(Ok(PayrollError::VestingNotConfigured))
+    );
+    assert_eq!(
+        payroll.try_set_vesting_contract(&Address::generate(&env), &Address::generate(&env)),
+        Err(Ok(PayrollError::Unauthorized))
+    );
+}
+
+#[test]
+fn cancelled_agreement_returns_completion_bonus() {
+    let s = setup();
+    s.payroll.cancel_agreement(&s.agreement_id);
+    let grace_end = s.payroll.get_grace_period_end(&s.agreement_id).unwrap();
+    s.env.ledger().with_mut(|li| li.timestamp = grace_end);
+    s.payroll.finalize_grace_period(&s.agreement_id);
+
+    assert_eq!(s.payroll.get_completion_bonus(&s.agreement_id), None);
+    assert_eq!(s.token.balance(&s.employer), PER_PERIOD * 2 + BONUS);
+    assert_eq!(s.token.balance(&s.payroll.address), 0);
+}
@@ -1,14 +1,17 @@
 #![cfg(test)]
+#![allow(deprecated)]
 
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
     token::{Client as TokenClient, StellarAssetClient},
-    Address, Env, IntoVal, Symbol, Vec, vec,
+    vec, Address, Env, IntoVal, Vec,
 };
 
 use token_vesting::{
-    ClaimedEvent, CreatedEvent, CustomCheckpoint, EarlyReleaseEvent, RevokedEvent,
-    TokenVestingContract, TokenVestingContractClient, VestingKind, VestingStatus,
+    CancellationAcceptedEvent, ClaimedEvent, CreatedEvent, CustomCheckpoint, EarlyReleaseEvent,
+    EligibilityChangedEvent, GraceReclaimedEvent, PauseChangedEvent, RevokedEvent, ScheduleOptions,
+    TokenVestingContract, TokenVestingContractClient, VestingClaimedEvent, VestingError,
+    VestingKind, VestingStatus,
 };
 
 // ---------------------------------------------------------------------------
@@ -107,6 +110,7 @@ fn init_required_before_operations() {
         &60u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
     assert!(res.is_err());
 }
@@ -147,6 +151,7 @@ fn linear_vesting_claim_flow() {
         &60u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
 
     // before start: nothing vested
@@ -156,7 +161,7 @@ fn linear_vesting_claim_flow() {
     // halfway at t=30: ~300 vested
     set_time(&env, 30);
     let vested = client.get_vested_amount(&sid);
-    assert!(vested >= 290 && vested <= 310);
+    assert!((290..=310).contains(&vested));
 
     // claim once
     let claimed = client.claim(&beneficiary, &sid);
@@ -188,6 +193,7 @@ fn linear_at_exact_start_returns_zero() {
         &200u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     assert_eq!(client.get_vested_amount(&sid), 0);
@@ -208,6 +214,7 @@ fn linear_one_second_after_start() {
         &200u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 101);
@@ -231,6 +238,7 @@ fn linear_at_end_returns_total() {
         &200u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 200);
@@ -252,6 +260,7 @@ fn linear_past_end_capped_at_total() {
         &200u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 999);
@@ -274,6 +283,7 @@ fn linear_with_cliff_before_cliff_returns_zero() {
         &100u64,
         &Some(50u64),
         &false,
+        &ScheduleOptions::default(),
     );
 
     // At t=25, would be 250 linearly but cliff blocks it
@@ -296,6 +306,7 @@ fn linear_with_cliff_at_cliff_returns_proportional() {
         &100u64,
         &Some(50u64),
         &false,
+        &ScheduleOptions::default(),
     );
 
     // At t=50 (cliff), linear kicks in: 1000 * 50/100 = 500
@@ -318,6 +329,7 @@ fn linear_with_cliff_after_cliff_interpolates() {
         &100u64,
         &Some(50u64),
         &false,
+        &ScheduleOptions::default(),
     );
 
     // At t=75, past cliff: 1000 * 75/100 = 750
@@ -342,6 +354,7 @@ fn cliff_vesting_and_revocation() {
         &400i128,
         &100u64,
         &true,
+        &ScheduleOptions::default(),
     );
 
     // before cliff: nothing vested
@@ -368,6 +381,7 @@ fn cliff_one_second_before_cliff_returns_zero() {
         &500i128,
         &100u64,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 99);
@@ -387,6 +401,7 @@ fn cliff_at_exact_cliff_returns_total() {
         &500i128,
         &100u64,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 100);
@@ -406,6 +421,7 @@ fn cliff_full_claim_after_cliff() {
         &500i128,
         &100u64,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 200);
@@ -447,6 +463,7 @@ fn custom_schedule_and_early_release() {
         &500i128,
         &checkpoints,
         &true,
+        &ScheduleOptions::default(),
     );
 
     // at t=15, second checkpoint not reached, so 100 vested
@@ -457,6 +474,10 @@ fn custom_schedule_and_early_release() {
     let early = client.approve_early_release(&owner, &sid, &200i128);
     assert_eq!(early, 200i128);
 
+    // nothing moves until the beneficiary accepts
+    assert_eq!(client.get_schedule(&sid).unwrap().released_amount, 0);
+    assert_eq!(client.accept_early_release(&beneficiary, &sid), 200i128);
+
     let schedule = client.get_schedule(&sid).unwrap();
     assert!(schedule.released_amount >= 200);
 }
@@ -484,6 +505,7 @@ fn custom_before_first_checkpoint_returns_zero() {
         &500i128,
         &checkpoints,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 10);
@@ -513,6 +535,7 @@ fn custom_between_checkpoints() {
         &500i128,
         &checkpoints,
         &false,
+        &ScheduleOptions::default(),
     );
 
     // At t=75 — past first checkpoint, before second
@@ -543,6 +566,7 @@ fn custom_at_final_checkpoint() {
         &500i128,
         &checkpoints,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 100);
@@ -566,6 +590,7 @@ fn claim_non_beneficiary_fails() {
         &500i128,
         &100u64,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 200);
@@ -589,6 +614,7 @@ fn double_claim_same_timestamp_fails() {
         &100u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -612,6 +638,7 @@ fn claim_on_completed_schedule_fails() {
         &500i128,
         &10u64,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 10);
@@ -637,6 +664,7 @@ fn released_amount_accumulates_correctly() {
         &100u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 25);
@@ -670,6 +698,7 @@ fn claim_verifies_token_balances() {
         &500i128,
         &50u64,
         &false,
+        &ScheduleOptions::default(),
     );
 
     // Employer balance decreased by escrow
@@ -699,7 +728,8 @@ fn revoke_non_revocable_fails() {
         &0u64,
         &100u64,
         &None,
-        &false, // not revocable
+        &false, // not revocable,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -722,6 +752,7 @@ fn revoke_non_employer_fails() {
         &100u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -745,6 +776,7 @@ fn double_revoke_fails() {
         &100u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -772,6 +804,7 @@ fn revoke_partial_vesting_splits_correctly() {
         &100u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
 
     // Employer escrowed 1000
@@ -780,7 +813,7 @@ fn revoke_partial_vesting_splits_correctly() {
     set_time(&env, 50);
     let refunded = client.revoke(&employer, &sid);
     // ~500 vested, ~500 refunded
-    assert!(refunded >= 490 && refunded <= 510);
+    assert!((490..=510).contains(&refunded));
 
     // Employer got refund
     let employer_after = token.balance(&employer);
@@ -811,6 +844,7 @@ fn early_release_non_owner_fails() {
         &100u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -834,6 +868,7 @@ fn early_release_capped_at_unvested() {
         &100u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     // At t=80, 800 vested, 200 unvested. Request 500 → capped at 200.
@@ -857,6 +892,7 @@ fn early_release_on_revoked_schedule_fails() {
         &100u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -885,6 +921,7 @@ fn claim_after_revoke_gets_vested_remainder() {
         &100u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
 
     // At t=40, revoke — 400 vested, 600 refunded
@@ -912,6 +949,7 @@ fn schedule_ids_are_sequential() {
         &100i128,
         &10u64,
         &false,
+        &ScheduleOptions::default(),
     );
     let id2 = client.create_cliff_schedule(
         &employer,
@@ -920,6 +958,7 @@ fn schedule_ids_are_sequential() {
         &100i128,
         &10u64,
         &false,
+        &ScheduleOptions::default(),
     );
     let id3 = client.create_cliff_schedule(
         &employer,
@@ -928,6 +967,7 @@ fn schedule_ids_are_sequential() {
         &100i128,
         &10u64,
         &false,
+        &ScheduleOptions::default(),
     );
 
     assert_eq!(id1, 1);
@@ -954,6 +994,7 @@ fn create_linear_zero_amount_fails() {
         &100u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
     assert!(res.is_err());
 }
@@ -973,6 +1014,7 @@ fn create_linear_end_before_start_fails() {
         &50u64, // end < start
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
     assert!(res.is_err());
 }
@@ -992,6 +1034,7 @@ fn create_linear_cliff_outside_range_fails() {
         &200u64,
         &Some(300u64), // cliff > end
         &false,
+        &ScheduleOptions::default(),
     );
     assert!(res.is_err());
 }
@@ -1011,6 +1054,7 @@ fn create_custom_empty_checkpoints_fails() {
         &500i128,
         &checkpoints,
         &false,
+        &ScheduleOptions::default(),
     );
     assert!(res.is_err());
 }
@@ -1038,6 +1082,7 @@ fn create_custom_unsorted_checkpoints_fails() {
         &500i128,
         &checkpoints,
         &false,
+        &ScheduleOptions::default(),
     );
     assert!(res.is_err());
 }
@@ -1063,6 +1108,7 @@ fn linear_minimal_duration_vests_correctly() {
         &11u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     // At start: 0
@@ -1094,6 +1140,7 @@ fn custom_vested_never_exceeds_total() {
         &500i128,
         &checkpoints,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 9999);
@@ -1129,6 +1176,7 @@ fn test_create_event_emitted() {
         &200u64,
         &None,
         &true,
+        &ScheduleOptions::default(),
     );
 
     let events = env.events().all();
@@ -1169,6 +1217,7 @@ fn test_claim_event_emitted() {
         &100u64,
         &None,
         &false,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -1204,6 +1253,7 @@ fn test_revoke_event_emitted() {
         &400i128,
         &100u64,
         &true,
+        &ScheduleOptions::default(),
     );
 
     set_time(&env, 50);
@@ -1239,9 +1289,11 @@ fn test_early_release_event_emitted() {
         &500i128,
         &100u64,
         &true,
+        &ScheduleOptions::default(),
     );
 
     client.approve_early_release(&owner, &sid, &200i128);
+    client.accept_early_release(&beneficiary, &sid);
 
     let events = env.events().all();
     let last_event = events.last().unwrap();
@@ -1258,3 +1310,2506 @@ fn test_early_release_event_emitted() {
     assert_eq!(event.id, sid);
     assert_eq!(event.amount, 200);
 }
+
+// ===========================================================================
+// L. Claim delegation (4 tests)
+// ===========================================================================
+
+#[test]
+fn delegate_can_claim_to_beneficiary() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let delegate = Address::generate(&env);
+    client.set_claim_delegate(&beneficiary, &sid, &Some(delegate.clone()));
+    assert_eq!(client.get_claim_delegate(&sid), Some(delegate.clone()));
+
+    set_time(&env, 40);
+    let claimed = client.claim(&delegate, &sid);
+    assert_eq!(claimed, 400);
+    assert_eq!(token.balance(&beneficiary), 400);
+    assert_eq!(token.balance(&delegate), 0);
+}
+
+#[test]
+fn revoked_delegation_cannot_claim() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let delegate = Address::generate(&env);
+    client.set_claim_delegate(&beneficiary, &sid, &Some(delegate.clone()));
+    client.set_claim_delegate(&beneficiary, &sid, &None);
+    assert_eq!(client.get_claim_delegate(&sid), None);
+
+    set_time(&env, 40);
+    let res = client.try_claim(&delegate, &sid);
+    assert!(res.is_err());
+
+    // Beneficiary can still claim directly.
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+}
+
+#[test]
+fn delegate_cannot_change_beneficiary() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let delegate = Address::generate(&env);
+    client.set_claim_delegate(&beneficiary, &sid, &Some(delegate.clone()));
+
+    let res = client.try_change_beneficiary(&delegate, &sid, &delegate);
+    assert!(res.is_err());
+    assert_eq!(client.get_schedule(&sid).unwrap().beneficiary, beneficiary);
+
+    // The beneficiary can change it, and the delegation is cleared.
+    let new_beneficiary = Address::generate(&env);
+    client.change_beneficiary(&beneficiary, &sid, &new_beneficiary);
+    assert_eq!(
+        client.get_schedule(&sid).unwrap().beneficiary,
+        new_beneficiary
+    );
+    assert_eq!(client.get_claim_delegate(&sid), None);
+}
+
+#[test]
+fn delegate_cannot_claim_on_revoked_schedule() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+
+    let delegate = Address::generate(&env);
+    client.set_claim_delegate(&beneficiary, &sid, &Some(delegate.clone()));
+
+    set_time(&env, 50);
+    client.revoke(&employer, &sid);
+
+    let res = client.try_claim(&delegate, &sid);
+    assert!(res.is_err());
+
+    // The beneficiary keeps access to the vested remainder.
+    assert_eq!(client.claim(&beneficiary, &sid), 500);
+}
+
+// ===========================================================================
+// M. Bulk creation (3 tests)
+// ===========================================================================
+
+#[test]
+fn bulk_create_linear_schedules() {
+    let env = create_env();
+    let (client, _owner, employer, _beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let mut grants: Vec<(Address, i128)> = Vec::new(&env);
+    for i in 0..12i128 {
+        grants.push_back((Address::generate(&env), 100 + i * 10));
+    }
+
+    let ids = client.create_linear_schedules_bulk(
+        &employer,
+        &token.address,
+        &grants,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+
+    assert_eq!(ids.len(), 12);
+    let mut escrowed = 0i128;
+    for i in 0..grants.len() {
+        let (beneficiary, amount) = grants.get(i).unwrap();
+        let schedule = client.get_schedule(&ids.get(i).unwrap()).unwrap();
+        assert_eq!(schedule.id, (i + 1) as u128);
+        assert_eq!(schedule.beneficiary, beneficiary);
+        assert_eq!(schedule.total_amount, amount);
+        assert_eq!(schedule.kind, VestingKind::Linear);
+        escrowed += amount;
+    }
+    assert_eq!(token.balance(&client.address), escrowed);
+    assert_eq!(token.balance(&employer), 10_000 - escrowed);
+}
+
+#[test]
+fn bulk_create_invalid_amount_creates_nothing() {
+    let env = create_env();
+    let (client, _owner, employer, _beneficiary, token) = full_setup(&env);
+
+    let mut grants: Vec<(Address, i128)> = Vec::new(&env);
+    grants.push_back((Address::generate(&env), 100));
+    grants.push_back((Address::generate(&env), 0));
+
+    let res = client.try_create_linear_schedules_bulk(
+        &employer,
+        &token.address,
+        &grants,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    assert!(res.is_err());
+    assert_eq!(client.get_schedule(&1u128), None);
+    assert_eq!(token.balance(&employer), 10_000);
+}
+
+#[test]
+fn bulk_create_duplicate_beneficiary_fails() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    let mut grants: Vec<(Address, i128)> = Vec::new(&env);
+    grants.push_back((beneficiary.clone(), 100));
+    grants.push_back((beneficiary.clone(), 200));
+
+    let res = client.try_create_linear_schedules_bulk(
+        &employer,
+        &token.address,
+        &grants,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    assert!(res.is_err());
+    assert_eq!(client.get_schedule(&1u128), None);
+}
+
+// ===========================================================================
+// N. Two-step early release (5 tests)
+// ===========================================================================
+
+#[test]
+fn early_release_requires_acceptance() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 20);
+    client.approve_early_release(&owner, &sid, &300i128);
+    assert_eq!(token.balance(&beneficiary), 0);
+
+    let pending = client.get_pending_early_release(&sid).unwrap();
+    assert_eq!(pending.amount, 300);
+    assert_eq!(pending.approved_at, 20);
+
+    // Only the beneficiary can accept.
+    let stranger = Address::generate(&env);
+    assert!(client.try_accept_early_release(&stranger, &sid).is_err());
+
+    let released = client.accept_early_release(&beneficiary, &sid);
+    assert_eq!(released, 300);
+    assert_eq!(token.balance(&beneficiary), 300);
+    assert_eq!(client.get_pending_early_release(&sid), None);
+    assert_eq!(client.get_schedule(&sid).unwrap().released_amount, 300);
+}
+
+#[test]
+fn early_release_cancelled_by_owner() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    client.approve_early_release(&owner, &sid, &300i128);
+
+    let stranger = Address::generate(&env);
+    assert!(client.try_cancel_early_release(&stranger, &sid).is_err());
+
+    client.cancel_early_release(&owner, &sid);
+    assert_eq!(client.get_pending_early_release(&sid), None);
+    assert!(client.try_accept_early_release(&beneficiary, &sid).is_err());
+    assert_eq!(token.balance(&beneficiary), 0);
+}
+
+#[test]
+fn early_release_approval_expires() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &1_000_000u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    client.set_early_release_window(&owner, &3_600u64);
+    assert_eq!(client.get_early_release_window(), 3_600);
+
+    client.approve_early_release(&owner, &sid, &300i128);
+
+    set_time(&env, 3_601);
+    assert!(client.try_accept_early_release(&beneficiary, &sid).is_err());
+
+    // A fresh approval can still be accepted within the window.
+    client.approve_early_release(&owner, &sid, &300i128);
+    set_time(&env, 3_700);
+    assert_eq!(client.accept_early_release(&beneficiary, &sid), 300);
+}
+
+#[test]
+fn early_release_recapped_at_acceptance() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    // At t=50 only 500 unvested remain; the approval is for all of it.
+    set_time(&env, 50);
+    assert_eq!(client.approve_early_release(&owner, &sid, &1_000i128), 500);
+
+    // By t=90 only 100 remain unvested, so acceptance releases 100.
+    set_time(&env, 90);
+    assert_eq!(client.accept_early_release(&beneficiary, &sid), 100);
+
+    // Early releases count against released_amount, so the total paid out
+    // never exceeds total_amount.
+    assert_eq!(client.claim(&beneficiary, &sid), 800);
+    set_time(&env, 100);
+    assert_eq!(client.claim(&beneficiary, &sid), 100);
+    assert_eq!(token.balance(&beneficiary), 1_000);
+}
+
+#[test]
+fn second_early_release_capped_at_unreleased() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+    let other = client.create_linear_schedule(
+        &employer,
+        &Address::generate(&env),
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    client.approve_early_release(&owner, &sid, &600i128);
+    assert_eq!(client.accept_early_release(&beneficiary, &sid), 600);
+
+    // At t=50, 500 has vested but 600 is already out: only 400 remain.
+    set_time(&env, 50);
+    assert_eq!(client.approve_early_release(&owner, &sid, &500i128), 400);
+    assert_eq!(client.accept_early_release(&beneficiary, &sid), 400);
+    assert_eq!(token.balance(&beneficiary), 1_000);
+    assert_eq!(
+        client.get_schedule(&sid).unwrap().status,
+        VestingStatus::Completed
+    );
+
+    // The other schedule's escrow is untouched.
+    assert_eq!(token.balance(&client.address), 1_000);
+    assert_eq!(client.get_total_locked(&token.address), 1_000);
+    assert!(client.verify_solvency(&token.address));
+    set_time(&env, 100);
+    assert_eq!(
+        client.claim(&client.get_schedule(&other).unwrap().beneficiary, &other),
+        1_000
+    );
+}
+
+// ===========================================================================
+// O. Total locked accounting (3 tests)
+// ===========================================================================
+
+#[test]
+fn total_locked_tracks_full_lifecycle() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    assert_eq!(client.get_total_locked(&token.address), 0);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    assert_eq!(client.get_total_locked(&token.address), 1_000);
+    assert!(client.verify_solvency(&token.address));
+
+    let other_beneficiary = Address::generate(&env);
+    let other = client.create_linear_schedule(
+        &employer,
+        &other_beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+    assert_eq!(client.get_total_locked(&token.address), 2_000);
+
+    // Claim 200 at t=20.
+    set_time(&env, 20);
+    client.claim(&beneficiary, &sid);
+    assert_eq!(client.get_total_locked(&token.address), 1_800);
+
+    // Early release of 300 more, ahead of vesting.
+    client.approve_early_release(&owner, &sid, &300i128);
+    client.accept_early_release(&beneficiary, &sid);
+    assert_eq!(client.get_total_locked(&token.address), 1_500);
+
+    // Revoke at t=40: 400 has vested but 500 is already out, so only the
+    // other 500 is refunded and nothing remains owed.
+    set_time(&env, 40);
+    assert_eq!(client.revoke(&employer, &sid), 500);
+    assert_eq!(client.get_total_locked(&token.address), 1_000);
+    assert_eq!(token.balance(&client.address), 1_000);
+    assert!(client.verify_solvency(&token.address));
+
+    set_time(&env, 100);
+    client.claim(&other_beneficiary, &other);
+    assert_eq!(client.get_total_locked(&token.address), 0);
+    assert_eq!(token.balance(&client.address), 0);
+    assert!(client.verify_solvency(&token.address));
+}
+
+#[test]
+fn total_locked_is_per_token() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    let other_admin = Address::generate(&env);
+    let other = create_token_contract(&env, &other_admin);
+    StellarAssetClient::new(&env, &other.address).mint(&employer, &5_000i128);
+
+    set_time(&env, 0);
+    client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+    let mut grants: Vec<(Address, i128)> = Vec::new(&env);
+    grants.push_back((Address::generate(&env), 700));
+    grants.push_back((Address::generate(&env), 300));
+    client.create_linear_schedules_bulk(
+        &employer,
+        &other.address,
+        &grants,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    assert_eq!(client.get_total_locked(&token.address), 400);
+    assert_eq!(client.get_total_locked(&other.address), 1_000);
+}
+
+#[test]
+fn verify_solvency_detects_shortfall() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+    assert!(client.verify_solvency(&token.address));
+
+    // Simulate tokens leaving the contract out-of-band.
+    let drain = Address::generate(&env);
+    token.transfer(&client.address, &drain, &1i128);
+    assert!(!client.verify_solvency(&token.address));
+}
+
+// ===========================================================================
+// P. Schedule listing (2 tests)
+// ===========================================================================
+
+#[test]
+fn list_schedules_pages_through_all() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    for _ in 0..20 {
+        client.create_cliff_schedule(
+            &employer,
+            &beneficiary,
+            &token.address,
+            &10i128,
+            &100u64,
+            &false,
+            &ScheduleOptions::default(),
+        );
+    }
+
+    let mut seen: u128 = 0;
+    let mut next: u128 = 1;
+    let mut pages = 0;
+    loop {
+        let page = client.list_schedules(&next, &7u32);
+        if page.is_empty() {
+            break;
+        }
+        pages += 1;
+        for schedule in page.iter() {
+            seen += 1;
+            assert_eq!(schedule.id, seen);
+        }
+        next = page.last().unwrap().id + 1;
+    }
+
+    assert_eq!(seen, 20);
+    assert_eq!(pages, 3);
+}
+
+#[test]
+fn list_schedules_caps_limit_and_handles_bounds() {
+    let env = create_env();
+    let (client, _owner, employer, _beneficiary, token) = full_setup(&env);
+
+    assert_eq!(client.list_schedules(&0u128, &10u32).len(), 0);
+
+    set_time(&env, 0);
+    let mut grants: Vec<(Address, i128)> = Vec::new(&env);
+    for _ in 0..20 {
+        grants.push_back((Address::generate(&env), 10));
+    }
+    // 20 + 20 + 15 = 55 schedules.
+    for batch in [grants.clone(), grants.clone(), grants.slice(0..15)] {
+        client.create_linear_schedules_bulk(
+            &employer,
+            &token.address,
+            &batch,
+            &0u64,
+            &100u64,
+            &None,
+            &false,
+            &ScheduleOptions::default(),
+        );
+    }
+
+    assert_eq!(client.list_schedules(&0u128, &1_000u32).len(), 50);
+    assert_eq!(client.list_schedules(&51u128, &50u32).len(), 5);
+    assert_eq!(client.list_schedules(&56u128, &50u32).len(), 0);
+    assert_eq!(client.list_schedules(&1u128, &0u32).len(), 0);
+}
+
+// ===========================================================================
+// Q. Protocol fee (4 tests)
+// ===========================================================================
+
+#[test]
+fn fee_config_validation() {
+    let env = create_env();
+    let (client, owner, _employer, _beneficiary, _token) = full_setup(&env);
+    let treasury = Address::generate(&env);
+
+    assert_eq!(client.get_fee_config(), None);
+
+    let stranger = Address::generate(&env);
+    assert!(client
+        .try_set_fee_config(&stranger, &100u32, &treasury)
+        .is_err());
+    assert!(client
+        .try_set_fee_config(&owner, &501u32, &treasury)
+        .is_err());
+
+    client.set_fee_config(&owner, &500u32, &treasury);
+    let cfg = client.get_fee_config().unwrap();
+    assert_eq!(cfg.fee_bps, 500);
+    assert_eq!(cfg.treasury, treasury);
+}
+
+#[test]
+fn fee_split_always_sums_to_releasable() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+    let treasury = Address::generate(&env);
+    client.set_fee_config(&owner, &333u32, &treasury);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &9_997i128,
+        &0u64,
+        &97u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let mut prev_total = 0i128;
+    for t in [1u64, 7, 13, 29, 50, 61, 88, 97] {
+        set_time(&env, t);
+        let releasable = client.get_releasable_amount(&sid);
+        let claimed = client.claim(&beneficiary, &sid);
+        assert_eq!(claimed, releasable);
+
+        let total = token.balance(&beneficiary) + token.balance(&treasury);
+        assert_eq!(total - prev_total, releasable);
+        prev_total = total;
+    }
+
+    let schedule = client.get_schedule(&sid).unwrap();
+    assert_eq!(schedule.released_amount, 9_997);
+    assert_eq!(schedule.status, VestingStatus::Completed);
+    assert_eq!(
+        token.balance(&beneficiary) + token.balance(&treasury),
+        9_997
+    );
+    assert!(token.balance(&treasury) > 0);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn zero_fee_behaves_like_no_fee() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+    let treasury = Address::generate(&env);
+    client.set_fee_config(&owner, &0u32, &treasury);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 30);
+    assert_eq!(client.claim(&beneficiary, &sid), 300);
+    assert_eq!(token.balance(&beneficiary), 300);
+    assert_eq!(token.balance(&treasury), 0);
+}
+
+#[test]
+fn fee_applies_to_early_release() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+    let treasury = Address::generate(&env);
+    client.set_fee_config(&owner, &500u32, &treasury);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    client.approve_early_release(&owner, &sid, &399i128);
+    assert_eq!(client.accept_early_release(&beneficiary, &sid), 399);
+
+    // 5% of 399 = 19.95, rounded down to 19.
+    assert_eq!(token.balance(&treasury), 19);
+    assert_eq!(token.balance(&beneficiary), 380);
+    assert_eq!(client.get_schedule(&sid).unwrap().released_amount, 399);
+    assert_eq!(client.get_total_locked(&token.address), 601);
+}
+
+// ===========================================================================
+// R. Emergency pause (3 tests)
+// ===========================================================================
+
+#[test]
+fn claims_blocked_while_paused_and_resume_after() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    client.set_paused(&owner, &true);
+    assert!(client.is_paused());
+
+    set_time(&env, 40);
+    let res = client.try_claim(&beneficiary, &sid);
+    assert_eq!(
+        res.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            VestingError::ContractPaused as u32
+        )))
+    );
+
+    // Views keep working and vesting keeps accruing.
+    assert_eq!(client.get_vested_amount(&sid), 400);
+    assert_eq!(client.get_releasable_amount(&sid), 400);
+
+    client.set_paused(&owner, &false);
+    assert!(!client.is_paused());
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+    assert_eq!(token.balance(&beneficiary), 400);
+}
+
+#[test]
+fn pause_blocks_creation_revoke_and_early_release() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    client.approve_early_release(&owner, &sid, &100i128);
+
+    client.set_paused(&owner, &true);
+
+    assert!(client
+        .try_create_cliff_schedule(
+            &employer,
+            &beneficiary,
+            &token.address,
+            &100i128,
+            &50u64,
+            &false,
+            &ScheduleOptions::default(),
+        )
+        .is_err());
+    assert!(client.try_revoke(&employer, &sid).is_err());
+    assert!(client
+        .try_approve_early_release(&owner, &sid, &100i128)
+        .is_err());
+    assert!(client.try_accept_early_release(&beneficiary, &sid).is_err());
+    assert_eq!(
+        client.get_schedule(&sid).unwrap().status,
+        VestingStatus::Active
+    );
+}
+
+#[test]
+fn only_owner_can_pause_and_events_emitted() {
+    let env = create_env();
+    let (client, owner, _employer, _beneficiary, _token) = full_setup(&env);
+
+    let stranger = Address::generate(&env);
+    assert!(client.try_set_paused(&stranger, &true).is_err());
+    assert!(!client.is_paused());
+
+    client.set_paused(&owner, &true);
+    let events = env.events().all();
+    let last_event = events.last().unwrap();
+    assert_eq!(
+        last_event.1,
+        vec![
+            &env,
+            soroban_sdk::String::from_str(&env, "vesting_paused").into_val(&env)
+        ]
+    );
+    let event: PauseChangedEvent = last_event.2.into_val(&env);
+    assert_eq!(event.admin, owner);
+    assert!(event.paused);
+
+    client.set_paused(&owner, &false);
+    let events = env.events().all();
+    let last_event = events.last().unwrap();
+    assert_eq!(
+        last_event.1,
+        vec![
+            &env,
+            soroban_sdk::String::from_str(&env, "vesting_unpaused").into_val(&env)
+        ]
+    );
+}
+
+// ===========================================================================
+// S. Checkpoint validation and pre-start edits (5 tests)
+// ===========================================================================
+
+fn checkpoints_of(env: &Env, points: &[(u64, i128)]) -> Vec<CustomCheckpoint> {
+    let mut checkpoints = Vec::new(env);
+    for (time, cumulative_amount) in points {
+        checkpoints.push_back(CustomCheckpoint {
+            time: *time,
+            cumulative_amount: *cumulative_amount,
+        });
+    }
+    checkpoints
+}
+
+#[test]
+fn create_custom_duplicate_timestamps_fails() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let res = client.try_create_custom_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &500i128,
+        &checkpoints_of(&env, &[(50, 200), (50, 500)]),
+        &true,
+        &ScheduleOptions::default(),
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn create_custom_past_timestamp_fails() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 100);
+    let res = client.try_create_custom_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &500i128,
+        &checkpoints_of(&env, &[(99, 200), (200, 500)]),
+        &true,
+        &ScheduleOptions::default(),
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn create_custom_zero_first_amount_fails() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let res = client.try_create_custom_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &500i128,
+        &checkpoints_of(&env, &[(10, 0), (20, 500)]),
+        &true,
+        &ScheduleOptions::default(),
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn update_checkpoints_before_start() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_custom_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &500i128,
+        &checkpoints_of(&env, &[(50, 200), (100, 500)]),
+        &true,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 10);
+    // The new final amount must still equal total_amount.
+    assert!(client
+        .try_update_checkpoints(
+            &employer,
+            &sid,
+            &checkpoints_of(&env, &[(60, 100), (120, 400)])
+        )
+        .is_err());
+    // Only the employer may edit.
+    assert!(client
+        .try_update_checkpoints(
+            &beneficiary,
+            &sid,
+            &checkpoints_of(&env, &[(60, 100), (120, 500)])
+        )
+        .is_err());
+
+    client.update_checkpoints(
+        &employer,
+        &sid,
+        &checkpoints_of(&env, &[(60, 100), (120, 500)]),
+    );
+
+    let schedule = client.get_schedule(&sid).unwrap();
+    assert_eq!(schedule.start_time, 60);
+    assert_eq!(schedule.end_time, 120);
+    assert_eq!(schedule.checkpoints.len(), 2);
+
+    set_time(&env, 60);
+    assert_eq!(client.get_vested_amount(&sid), 100);
+    set_time(&env, 100);
+    assert_eq!(client.get_vested_amount(&sid), 100);
+    set_time(&env, 120);
+    assert_eq!(client.get_vested_amount(&sid), 500);
+}
+
+#[test]
+fn update_checkpoints_after_start_fails() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_custom_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &500i128,
+        &checkpoints_of(&env, &[(50, 200), (100, 500)]),
+        &true,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 50);
+    let res = client.try_update_checkpoints(
+        &employer,
+        &sid,
+        &checkpoints_of(&env, &[(60, 100), (120, 500)]),
+    );
+    assert!(res.is_err());
+}
+
+// ===========================================================================
+// T. Claim deadline sweep (5 tests)
+// ===========================================================================
+
+#[test]
+fn sweep_before_deadline_rejected() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions {
+            claim_deadline: Some(50u64),
+            ..Default::default()
+        },
+    );
+
+    set_time(&env, 150);
+    assert!(client.try_sweep_expired(&employer, &sid).is_err());
+
+    // Beneficiary can still claim right up to the deadline.
+    assert_eq!(client.claim(&beneficiary, &sid), 1_000);
+}
+
+#[test]
+fn sweep_after_deadline_returns_unclaimed_to_employer() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions {
+            claim_deadline: Some(50u64),
+            ..Default::default()
+        },
+    );
+
+    set_time(&env, 30);
+    client.claim(&beneficiary, &sid);
+
+    set_time(&env, 151);
+    let stranger = Address::generate(&env);
+    assert!(client.try_sweep_expired(&stranger, &sid).is_err());
+
+    let swept = client.sweep_expired(&employer, &sid);
+    assert_eq!(swept, 700);
+    assert_eq!(token.balance(&employer), 10_000 - 1_000 + 700);
+    assert_eq!(client.get_total_locked(&token.address), 0);
+
+    let schedule = client.get_schedule(&sid).unwrap();
+    assert_eq!(schedule.status, VestingStatus::Completed);
+    assert_eq!(schedule.swept_amount, 700);
+    assert_eq!(schedule.released_amount, 300);
+}
+
+#[test]
+fn claim_after_sweep_fails_cleanly() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions {
+            claim_deadline: Some(10u64),
+            ..Default::default()
+        },
+    );
+
+    set_time(&env, 111);
+    client.sweep_expired(&employer, &sid);
+
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+    assert!(client.try_sweep_expired(&employer, &sid).is_err());
+    assert_eq!(token.balance(&beneficiary), 0);
+}
+
+#[test]
+fn sweep_without_deadline_rejected() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 1_000_000);
+    assert!(client.try_sweep_expired(&employer, &sid).is_err());
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+}
+
+#[test]
+fn sweep_fails_with_insufficient_escrow_when_balance_drained() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions {
+            claim_deadline: Some(50u64),
+            ..Default::default()
+        },
+    );
+
+    let drain = Address::generate(&env);
+    token.transfer(&client.address, &drain, &150i128);
+
+    set_time(&env, 151);
+    let res = client.try_sweep_expired(&employer, &sid);
+    assert_eq!(
+        res.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            VestingError::InsufficientEscrow as u32
+        )))
+    );
+    assert_eq!(client.get_schedule(&sid).unwrap().swept_amount, 0);
+}
+
+// ===========================================================================
+// U. Vesting curve preview (5 tests)
+// ===========================================================================
+
+/// Asserts that `preview_vesting` matches `get_vested_amount` at each timestamp.
+fn assert_preview_matches(
+    env: &Env,
+    client: &TokenVestingContractClient<'static>,
+    sid: u128,
+    points: &[u64],
+) {
+    let mut timestamps: Vec<u64> = Vec::new(env);
+    for t in points {
+        timestamps.push_back(*t);
+    }
+    let preview = client.preview_vesting(&sid, &timestamps);
+    assert_eq!(preview.len(), timestamps.len());
+
+    for (i, t) in points.iter().enumerate() {
+        set_time(env, *t);
+        assert_eq!(
+            preview.get(i as u32).unwrap(),
+            client.get_vested_amount(&sid)
+        );
+    }
+}
+
+#[test]
+fn preview_matches_linear_with_cliff() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &100u64,
+        &200u64,
+        &Some(150u64),
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    assert_preview_matches(&env, &client, sid, &[0, 100, 149, 150, 175, 199, 200, 500]);
+}
+
+#[test]
+fn preview_matches_cliff() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    assert_preview_matches(&env, &client, sid, &[0, 99, 100, 101, 10_000]);
+}
+
+#[test]
+fn preview_matches_custom() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_custom_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &500i128,
+        &checkpoints_of(&env, &[(10, 100), (20, 300), (30, 500)]),
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    assert_preview_matches(&env, &client, sid, &[0, 9, 10, 15, 20, 29, 30, 31]);
+}
+
+#[test]
+fn preview_honors_revocation_time() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 40);
+    client.revoke(&employer, &sid);
+
+    let mut timestamps: Vec<u64> = Vec::new(&env);
+    timestamps.push_back(20);
+    timestamps.push_back(40);
+    timestamps.push_back(90);
+    let preview = client.preview_vesting(&sid, &timestamps);
+    // The clock is frozen at revoked_at regardless of the query timestamp.
+    assert_eq!(preview, vec![&env, 400i128, 400i128, 400i128]);
+
+    assert_preview_matches(&env, &client, sid, &[40, 90, 1_000]);
+}
+
+#[test]
+fn preview_rejects_too_many_timestamps() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let mut timestamps: Vec<u64> = Vec::new(&env);
+    for t in 0..100u64 {
+        timestamps.push_back(t);
+    }
+    assert_eq!(client.preview_vesting(&sid, &timestamps).len(), 100);
+
+    timestamps.push_back(100);
+    assert!(client.try_preview_vesting(&sid, &timestamps).is_err());
+}
+
+// ===========================================================================
+// V. Token migration (3 tests)
+// ===========================================================================
+
+#[test]
+fn claims_after_migration_pay_new_token() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    let new_admin = Address::generate(&env);
+    let new_token = create_token_contract(&env, &new_admin);
+    StellarAssetClient::new(&env, &new_token.address).mint(&employer, &10_000i128);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 30);
+    client.claim(&beneficiary, &sid);
+    assert_eq!(token.balance(&beneficiary), 300);
+
+    let migrated = client.migrate_schedule_token(&owner, &sid, &new_token.address);
+    assert_eq!(migrated, 700);
+
+    // Employer swapped 700 new tokens for the 700 old tokens left in escrow.
+    assert_eq!(token.balance(&employer), 9_700);
+    assert_eq!(new_token.balance(&employer), 9_300);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(new_token.balance(&client.address), 700);
+    assert_eq!(client.get_total_locked(&token.address), 0);
+    assert_eq!(client.get_total_locked(&new_token.address), 700);
+
+    let schedule = client.get_schedule(&sid).unwrap();
+    assert_eq!(schedule.token, new_token.address);
+    assert_eq!(schedule.released_amount, 300);
+
+    set_time(&env, 100);
+    assert_eq!(client.claim(&beneficiary, &sid), 700);
+    assert_eq!(new_token.balance(&beneficiary), 700);
+    assert_eq!(token.balance(&beneficiary), 300);
+}
+
+#[test]
+fn migration_requires_owner() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    let new_admin = Address::generate(&env);
+    let new_token = create_token_contract(&env, &new_admin);
+    StellarAssetClient::new(&env, &new_token.address).mint(&employer, &10_000i128);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let res = client.try_migrate_schedule_token(&employer, &sid, &new_token.address);
+    assert!(res.is_err());
+    assert_eq!(client.get_schedule(&sid).unwrap().token, token.address);
+}
+
+#[test]
+fn migration_of_revoked_or_completed_schedule_fails() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    let new_admin = Address::generate(&env);
+    let new_token = create_token_contract(&env, &new_admin);
+    StellarAssetClient::new(&env, &new_token.address).mint(&employer, &10_000i128);
+
+    set_time(&env, 0);
+    let revoked = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    let completed = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &50u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    client.revoke(&employer, &revoked);
+    set_time(&env, 60);
+    client.claim(&beneficiary, &completed);
+
+    assert!(client
+        .try_migrate_schedule_token(&owner, &revoked, &new_token.address)
+        .is_err());
+    assert!(client
+        .try_migrate_schedule_token(&owner, &completed, &new_token.address)
+        .is_err());
+}
+
+// ===========================================================================
+// W. Revocation admin (3 tests)
+// ===========================================================================
+
+#[test]
+fn revoker_can_revoke_and_refund_goes_to_employer() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+    let hr_ops = Address::generate(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions {
+            revoker: Some(hr_ops.clone()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        client.get_schedule(&sid).unwrap().revoker,
+        Some(hr_ops.clone())
+    );
+
+    set_time(&env, 25);
+    let refunded = client.revoke(&hr_ops, &sid);
+    assert_eq!(refunded, 750);
+
+    let event: RevokedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(event.employer, employer);
+    assert_eq!(event.refunded, 750);
+
+    assert_eq!(token.balance(&employer), 10_000 - 1_000 + 750);
+    assert_eq!(token.balance(&hr_ops), 0);
+}
+
+#[test]
+fn random_address_cannot_revoke() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+    let hr_ops = Address::generate(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &true,
+        &ScheduleOptions {
+            revoker: Some(hr_ops),
+            ..Default::default()
+        },
+    );
+
+    let stranger = Address::generate(&env);
+    assert!(client.try_revoke(&stranger, &sid).is_err());
+    assert!(client.try_revoke(&beneficiary, &sid).is_err());
+    assert_eq!(
+        client.get_schedule(&sid).unwrap().status,
+        VestingStatus::Active
+    );
+}
+
+#[test]
+fn employer_rotates_revoker() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+    let old_ops = Address::generate(&env);
+    let new_ops = Address::generate(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &true,
+        &ScheduleOptions {
+            revoker: Some(old_ops.clone()),
+            ..Default::default()
+        },
+    );
+
+    // Only the employer may rotate the revoker.
+    assert!(client
+        .try_set_revoker(&old_ops, &sid, &Some(old_ops.clone()))
+        .is_err());
+
+    client.set_revoker(&employer, &sid, &Some(new_ops.clone()));
+    assert!(client.try_revoke(&old_ops, &sid).is_err());
+
+    assert_eq!(client.revoke(&new_ops, &sid), 400);
+    assert_eq!(token.balance(&employer), 10_000);
+}
+
+// ===========================================================================
+// X. Claim eligibility (3 tests)
+// ===========================================================================
+
+#[test]
+fn ineligible_beneficiary_blocked_then_claims_accumulated() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    client.set_require_eligibility(&owner, &true);
+    assert!(!client.is_claim_eligible(&beneficiary));
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 30);
+    let res = client.try_claim(&beneficiary, &sid);
+    assert_eq!(
+        res.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            VestingError::BeneficiaryNotEligible as u32
+        )))
+    );
+
+    // Vesting keeps accruing while blocked.
+    set_time(&env, 70);
+    assert_eq!(client.get_releasable_amount(&sid), 700);
+
+    client.set_claim_eligibility(&owner, &beneficiary, &true);
+    let event: EligibilityChangedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(event.beneficiary, beneficiary);
+    assert!(event.eligible);
+
+    assert_eq!(client.claim(&beneficiary, &sid), 700);
+    assert_eq!(token.balance(&beneficiary), 700);
+
+    // Flipping back blocks further claims.
+    client.set_claim_eligibility(&owner, &beneficiary, &false);
+    set_time(&env, 100);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+}
+
+#[test]
+fn eligibility_not_enforced_by_default() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &10u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    // An explicit ineligible flag has no effect until gating is enabled.
+    client.set_claim_eligibility(&owner, &beneficiary, &false);
+    assert!(client.is_claim_eligible(&beneficiary));
+
+    set_time(&env, 10);
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+}
+
+#[test]
+fn only_owner_manages_eligibility() {
+    let env = create_env();
+    let (client, _owner, _employer, beneficiary, _token) = full_setup(&env);
+
+    let stranger = Address::generate(&env);
+    assert!(client
+        .try_set_require_eligibility(&stranger, &true)
+        .is_err());
+    assert!(client
+        .try_set_claim_eligibility(&stranger, &beneficiary, &true)
+        .is_err());
+}
+
+// ===========================================================================
+// Y. Escrow invariant (3 tests)
+// ===========================================================================
+
+#[test]
+fn obligations_match_balance_through_lifecycle() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    assert_eq!(client.get_obligations(&token.address), 1_000);
+    assert_eq!(client.check_invariant(&token.address), (1_000, 1_000));
+
+    set_time(&env, 40);
+    client.claim(&beneficiary, &sid);
+    assert_eq!(client.check_invariant(&token.address), (600, 600));
+
+    set_time(&env, 60);
+    client.revoke(&employer, &sid);
+    assert_eq!(client.check_invariant(&token.address), (200, 200));
+}
+
+#[test]
+fn claim_fails_with_insufficient_escrow_when_balance_drained() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    // Corrupt the escrow by moving tokens out-of-band.
+    let drain = Address::generate(&env);
+    token.transfer(&client.address, &drain, &150i128);
+    assert_eq!(client.check_invariant(&token.address), (400, 250));
+
+    set_time(&env, 100);
+    let res = client.try_claim(&beneficiary, &sid);
+    assert_eq!(
+        res.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            VestingError::InsufficientEscrow as u32
+        )))
+    );
+
+    // The failed claim left accounting untouched.
+    assert_eq!(client.get_obligations(&token.address), 400);
+    assert_eq!(client.get_schedule(&sid).unwrap().released_amount, 0);
+}
+
+#[test]
+fn revoke_fails_with_insufficient_escrow_when_balance_drained() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+
+    let drain = Address::generate(&env);
+    token.transfer(&client.address, &drain, &900i128);
+
+    set_time(&env, 50);
+    let res = client.try_revoke(&employer, &sid);
+    assert_eq!(
+        res.err(),
+        Some(Ok(soroban_sdk::Error::from_contract_error(
+            VestingError::InsufficientEscrow as u32
+        )))
+    );
+}
+
+// ===========================================================================
+// Z. Initial unlock (5 tests)
+// ===========================================================================
+
+#[test]
+fn zero_initial_unlock_keeps_plain_linear_curve() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &10u64,
+        &110u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 10);
+    assert_eq!(client.get_vested_amount(&sid), 0);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+
+    set_time(&env, 60);
+    assert_eq!(client.claim(&beneficiary, &sid), 500);
+}
+
+#[test]
+fn ten_percent_unlock_claimable_at_start_then_linear() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &10u64,
+        &110u64,
+        &None,
+        &false,
+        &ScheduleOptions {
+            initial_unlock_bps: 1_000u32,
+            ..Default::default()
+        },
+    );
+    assert_eq!(client.get_schedule(&sid).unwrap().initial_unlock_bps, 1_000);
+
+    set_time(&env, 9);
+    assert_eq!(client.get_vested_amount(&sid), 0);
+
+    // Exactly at start_time the 10% unlock is claimable.
+    set_time(&env, 10);
+    assert_eq!(client.claim(&beneficiary, &sid), 100);
+
+    // Halfway: 100 + 900 * 50 / 100 = 550 vested.
+    set_time(&env, 60);
+    assert_eq!(client.get_vested_amount(&sid), 550);
+    assert_eq!(client.claim(&beneficiary, &sid), 450);
+
+    set_time(&env, 110);
+    assert_eq!(client.claim(&beneficiary, &sid), 450);
+    assert_eq!(token.balance(&beneficiary), 1_000);
+}
+
+#[test]
+fn full_unlock_vests_everything_at_start() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &10u64,
+        &110u64,
+        &None,
+        &false,
+        &ScheduleOptions {
+            initial_unlock_bps: 10_000u32,
+            ..Default::default()
+        },
+    );
+
+    set_time(&env, 10);
+    assert_eq!(client.claim(&beneficiary, &sid), 1_000);
+    assert_eq!(
+        client.get_schedule(&sid).unwrap().status,
+        VestingStatus::Completed
+    );
+    assert_eq!(token.balance(&beneficiary), 1_000);
+
+    let res = client.try_create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &10u64,
+        &110u64,
+        &None,
+        &false,
+        &ScheduleOptions {
+            initial_unlock_bps: 10_001u32,
+            ..Default::default()
+        },
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn revoke_treats_initial_unlock_as_vested() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &10u64,
+        &110u64,
+        &None,
+        &true,
+        &ScheduleOptions {
+            initial_unlock_bps: 2_000u32,
+            ..Default::default()
+        },
+    );
+
+    // Revoked at start_time: only the 20% unlock stays with the beneficiary.
+    set_time(&env, 10);
+    assert_eq!(client.revoke(&employer, &sid), 800);
+
+    set_time(&env, 200);
+    assert_eq!(client.get_vested_amount(&sid), 200);
+    assert_eq!(client.claim(&beneficiary, &sid), 200);
+    assert_eq!(token.balance(&employer), 10_000 - 200);
+}
+
+#[test]
+fn initial_unlock_only_on_linear_schedules() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+    let unlock = ScheduleOptions {
+        initial_unlock_bps: 2_500,
+        ..Default::default()
+    };
+
+    set_time(&env, 0);
+    let ids = client.create_linear_schedules_bulk(
+        &employer,
+        &token.address,
+        &vec![&env, (beneficiary.clone(), 1_000i128)],
+        &10u64,
+        &110u64,
+        &None,
+        &false,
+        &unlock,
+    );
+    set_time(&env, 10);
+    assert_eq!(client.get_vested_amount(&ids.get(0).unwrap()), 250);
+
+    let res = client.try_create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &100u64,
+        &false,
+        &unlock,
+    );
+    assert!(res.is_err());
+}
+
+// ===========================================================================
+// AA. Revocation grace period (6 tests)
+// ===========================================================================
+
+/// Creates a revocable 1,000-token linear schedule over [0, 100] and revokes
+/// it at t=40 with a 50-second grace period configured.
+fn revoked_with_grace(
+    env: &Env,
+) -> (
+    TokenVestingContractClient<'static>,
+    Address,
+    Address,
+    TokenClient<'static>,
+    u128,
+) {
+    let (client, owner, employer, beneficiary, token) = full_setup(env);
+    client.set_revocation_grace(&owner, &Some(50u64));
+
+    set_time(env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    set_time(env, 40);
+    assert_eq!(client.revoke(&employer, &sid), 600);
+    (client, employer, beneficiary, token, sid)
+}
+
+#[test]
+fn claim_within_revocation_grace_succeeds() {
+    let env = create_env();
+    let (client, employer, beneficiary, token, sid) = revoked_with_grace(&env);
+
+    set_time(&env, 89);
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+    assert_eq!(token.balance(&beneficiary), 400);
+
+    // Nothing left for the employer to reclaim.
+    set_time(&env, 90);
+    assert!(client.try_reclaim_after_grace(&employer, &sid).is_err());
+}
+
+#[test]
+fn claim_after_revocation_grace_rejected() {
+    let env = create_env();
+    let (client, _employer, beneficiary, _token, sid) = revoked_with_grace(&env);
+
+    set_time(&env, 90);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+}
+
+#[test]
+fn reclaim_after_grace_sweeps_unclaimed_vested() {
+    let env = create_env();
+    let (client, employer, beneficiary, token, sid) = revoked_with_grace(&env);
+
+    // Still inside the grace window.
+    set_time(&env, 89);
+    assert!(client.try_reclaim_after_grace(&employer, &sid).is_err());
+
+    set_time(&env, 90);
+    assert!(client.try_reclaim_after_grace(&beneficiary, &sid).is_err());
+    assert_eq!(client.reclaim_after_grace(&employer, &sid), 400);
+    let event: GraceReclaimedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(event.id, sid);
+    assert_eq!(event.employer, employer);
+    assert_eq!(event.amount, 400);
+
+    let schedule = client.get_schedule(&sid).unwrap();
+    assert_eq!(schedule.status, VestingStatus::Completed);
+    assert_eq!(schedule.swept_amount, 400);
+    assert_eq!(token.balance(&employer), 10_000);
+    assert_eq!(client.get_total_locked(&token.address), 0);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+}
+
+#[test]
+fn no_grace_configured_keeps_remainder_claimable() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+    assert_eq!(client.get_revocation_grace(), None);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    set_time(&env, 40);
+    client.revoke(&employer, &sid);
+
+    set_time(&env, 10_000);
+    assert!(client.try_reclaim_after_grace(&employer, &sid).is_err());
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+}
+
+#[test]
+fn grace_change_does_not_affect_revoked_schedules() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+    set_time(&env, 40);
+    client.revoke(&employer, &sid);
+    assert_eq!(client.get_schedule(&sid).unwrap().grace_end, None);
+
+    // A grace period configured after the revocation does not start a sweep
+    // window for it.
+    client.set_revocation_grace(&owner, &Some(50u64));
+    set_time(&env, 10_000);
+    assert!(client.try_reclaim_after_grace(&employer, &sid).is_err());
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+    assert_eq!(token.balance(&beneficiary), 400);
+}
+
+#[test]
+fn revoked_schedule_keeps_its_grace_end() {
+    let env = create_env();
+    let (client, employer, beneficiary, _token, sid) = revoked_with_grace(&env);
+    assert_eq!(client.get_schedule(&sid).unwrap().grace_end, Some(90));
+
+    // Neither lengthening nor removing the grace moves the revoked schedule's
+    // deadline.
+    let owner = client.get_owner().unwrap();
+    client.set_revocation_grace(&owner, &Some(1_000u64));
+    set_time(&env, 90);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+    client.set_revocation_grace(&owner, &None);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+    assert_eq!(client.reclaim_after_grace(&employer, &sid), 400);
+}
+
+// ===========================================================================
+// AB. Claimable simulation (3 tests)
+// ===========================================================================
+
+#[test]
+fn claimable_at_matches_actual_claims() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &Some(20u64),
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    assert_eq!(client.get_claimable_at(&sid, &10u64), 0);
+    let predicted_30 = client.get_claimable_at(&sid, &30u64);
+    let predicted_75 = client.get_claimable_at(&sid, &75u64);
+    assert_eq!(predicted_30, 300);
+    // Nothing was mutated by the simulation.
+    assert_eq!(client.get_schedule(&sid).unwrap().released_amount, 0);
+
+    set_time(&env, 30);
+    assert_eq!(client.claim(&beneficiary, &sid), predicted_30);
+
+    // After a claim, the simulation accounts for the released amount.
+    assert_eq!(client.get_claimable_at(&sid, &75u64), predicted_75 - 300);
+    set_time(&env, 75);
+    assert_eq!(client.claim(&beneficiary, &sid), predicted_75 - 300);
+}
+
+#[test]
+fn claimable_at_respects_revocation_freeze() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &true,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 40);
+    client.revoke(&employer, &sid);
+    assert_eq!(client.get_claimable_at(&sid, &500u64), 400);
+
+    set_time(&env, 500);
+    assert_eq!(client.claim(&beneficiary, &sid), 400);
+    assert_eq!(client.get_claimable_at(&sid, &1_000u64), 0);
+}
+
+#[test]
+fn claimable_at_is_zero_for_completed_schedule() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &10u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 10);
+    client.claim(&beneficiary, &sid);
+    assert_eq!(
+        client.get_schedule(&sid).unwrap().status,
+        VestingStatus::Completed
+    );
+    assert_eq!(client.get_claimable_at(&sid, &10u64), 0);
+    assert_eq!(client.get_claimable_at(&sid, &1_000u64), 0);
+}
+
+// ===========================================================================
+// AC. Structured claim event (3 tests)
+// ===========================================================================
+
+/// Returns the `VestingClaimedEvent` of the last invocation, which is
+/// published right before the basic claim / early release event.
+fn last_claim_detail(env: &Env, schedule_id: u128) -> VestingClaimedEvent {
+    let events = env.events().all();
+    let detail = events.get(events.len() - 2).unwrap();
+    assert_eq!(
+        detail.1,
+        vec![
+            env,
+            soroban_sdk::String::from_str(env, "vesting_claim_detail").into_val(env),
+            schedule_id.into_val(env)
+        ]
+    );
+    detail.2.into_val(env)
+}
+
+#[test]
+fn linear_claim_event_reports_remaining_balances() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 30);
+    client.claim(&beneficiary, &sid);
+    let event = last_claim_detail(&env, sid);
+    assert_eq!(event.schedule_id, sid);
+    assert_eq!(event.beneficiary, beneficiary);
+    assert_eq!(event.amount, 300);
+    assert_eq!(event.total_released, 300);
+    assert_eq!(event.remaining_unvested, 700);
+    assert_eq!(event.timestamp, 30);
+
+    set_time(&env, 100);
+    client.claim(&beneficiary, &sid);
+    let event = last_claim_detail(&env, sid);
+    assert_eq!(event.amount, 700);
+    assert_eq!(event.total_released, 1_000);
+    assert_eq!(event.remaining_unvested, 0);
+    assert_eq!(event.timestamp, 100);
+}
+
+#[test]
+fn custom_claim_event_reports_remaining_balances() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_custom_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &600i128,
+        &checkpoints_of(&env, &[(10, 100), (20, 350), (30, 600)]),
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 25);
+    client.claim(&beneficiary, &sid);
+    let event = last_claim_detail(&env, sid);
+    assert_eq!(event.amount, 350);
+    assert_eq!(event.total_released, 350);
+    assert_eq!(event.remaining_unvested, 250);
+    assert_eq!(event.timestamp, 25);
+}
+
+#[test]
+fn early_release_event_reports_remaining_balances() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    set_time(&env, 20);
+    client.claim(&beneficiary, &sid);
+    client.approve_early_release(&owner, &sid, &300i128);
+    client.accept_early_release(&beneficiary, &sid);
+
+    let event = last_claim_detail(&env, sid);
+    assert_eq!(event.amount, 300);
+    assert_eq!(event.total_released, 500);
+    // 200 vested + 300 released early leaves 500 still unvested.
+    assert_eq!(event.remaining_unvested, 500);
+    assert_eq!(event.timestamp, 20);
+}
+
+// ===========================================================================
+// AD. Mutual-consent cancellation (3 tests)
+// ===========================================================================
+
+#[test]
+fn non_revocable_schedule_cancelled_halfway_by_consent() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+    assert!(client.try_revoke(&employer, &sid).is_err());
+
+    // Beneficiary claims 200 at t=20; the settlement at t=50 is 500 in total.
+    set_time(&env, 20);
+    client.claim(&beneficiary, &sid);
+    set_time(&env, 50);
+    client.propose_cancellation(&employer, &sid, &500i128);
+    assert_eq!(
+        client
+            .get_pending_cancellation(&sid)
+            .unwrap()
+            .vested_to_beneficiary,
+        500
+    );
+
+    assert_eq!(client.accept_cancellation(&beneficiary, &sid), 300);
+    let event: CancellationAcceptedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(event.paid, 300);
+    assert_eq!(event.refunded, 500);
+    assert_eq!(event.at, 50);
+
+    assert_eq!(token.balance(&beneficiary), 500);
+    assert_eq!(token.balance(&employer), 10_000 - 500);
+    assert_eq!(client.get_total_locked(&token.address), 0);
+
+    let schedule = client.get_schedule(&sid).unwrap();
+    assert_eq!(schedule.status, VestingStatus::Revoked);
+    assert_eq!(schedule.revoked_at, Some(50));
+    assert!(client.get_pending_cancellation(&sid).is_none());
+
+    // Nothing more can be claimed later.
+    set_time(&env, 100);
+    assert_eq!(client.get_releasable_amount(&sid), 0);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+}
+
+#[test]
+fn employer_can_withdraw_pending_cancellation() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    client.propose_cancellation(&employer, &sid, &100i128);
+    assert!(client
+        .try_withdraw_cancellation(&beneficiary, &sid)
+        .is_err());
+    client.withdraw_cancellation(&employer, &sid);
+    assert!(client.get_pending_cancellation(&sid).is_none());
+    assert!(client.try_accept_cancellation(&beneficiary, &sid).is_err());
+
+    // Schedule keeps vesting normally.
+    set_time(&env, 100);
+    assert_eq!(client.claim(&beneficiary, &sid), 1_000);
+}
+
+#[test]
+fn cancellation_rejects_invalid_terms_and_callers() {
+    let env = create_env();
+    let (client, _owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &100u64,
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    assert!(client
+        .try_propose_cancellation(&beneficiary, &sid, &500i128)
+        .is_err());
+    assert!(client
+        .try_propose_cancellation(&employer, &sid, &1_001i128)
+        .is_err());
+
+    client.propose_cancellation(&employer, &sid, &300i128);
+    assert!(client.try_accept_cancellation(&employer, &sid).is_err());
+
+    // Claims past the agreed amount make the proposal stale.
+    set_time(&env, 40);
+    client.claim(&beneficiary, &sid);
+    assert!(client.try_accept_cancellation(&beneficiary, &sid).is_err());
+}
+
+// ===========================================================================
+// AE. Beneficiary recovery (4 tests)
+// ===========================================================================
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+#[test]
+fn recovery_happy_path_moves_schedule_after_timelock() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_linear_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &1_000i128,
+        &0u64,
+        &(2 * WEEK),
+        &None,
+        &false,
+        &ScheduleOptions::default(),
+    );
+    let delegate = Address::generate(&env);
+    client.set_claim_delegate(&beneficiary, &sid, &Some(delegate));
+
+    let recovered = Address::generate(&env);
+    set_time(&env, 100);
+    client.initiate_recovery(&owner, &sid, &recovered);
+    let pending = client.get_pending_recovery(&sid).unwrap();
+    assert_eq!(pending.new_beneficiary, recovered);
+    assert_eq!(pending.executable_at, 100 + WEEK);
+
+    // Anyone can finalize once the timelock elapses.
+    set_time(&env, 100 + WEEK);
+    client.finalize_recovery(&sid);
+    assert_eq!(client.get_schedule(&sid).unwrap().beneficiary, recovered);
+    assert!(client.get_pending_recovery(&sid).is_none());
+    assert!(client.get_claim_delegate(&sid).is_none());
+
+    set_time(&env, 2 * WEEK);
+    assert!(client.try_claim(&beneficiary, &sid).is_err());
+    assert_eq!(client.claim(&recovered, &sid), 1_000);
+    assert_eq!(token.balance(&recovered), 1_000);
+}
+
+#[test]
+fn beneficiary_can_cancel_recovery_during_timelock() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let attacker = Address::generate(&env);
+    client.initiate_recovery(&owner, &sid, &attacker);
+
+    assert!(client.try_cancel_recovery(&attacker, &sid).is_err());
+    set_time(&env, WEEK - 1);
+    client.cancel_recovery(&beneficiary, &sid);
+    assert!(client.get_pending_recovery(&sid).is_none());
+
+    set_time(&env, WEEK);
+    assert!(client.try_finalize_recovery(&sid).is_err());
+    assert_eq!(client.get_schedule(&sid).unwrap().beneficiary, beneficiary);
+}
+
+#[test]
+fn finalize_before_timelock_rejected() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let recovered = Address::generate(&env);
+    set_time(&env, 10);
+    client.initiate_recovery(&owner, &sid, &recovered);
+
+    set_time(&env, 10 + WEEK - 1);
+    assert!(client.try_finalize_recovery(&sid).is_err());
+    assert_eq!(client.get_schedule(&sid).unwrap().beneficiary, beneficiary);
+
+    set_time(&env, 10 + WEEK);
+    client.finalize_recovery(&sid);
+    assert_eq!(client.get_schedule(&sid).unwrap().beneficiary, recovered);
+}
+
+#[test]
+fn recovery_timelock_is_owner_configurable() {
+    let env = create_env();
+    let (client, owner, employer, beneficiary, token) = full_setup(&env);
+    assert_eq!(client.get_recovery_timelock(), WEEK);
+
+    assert!(client
+        .try_set_recovery_timelock(&beneficiary, &60u64)
+        .is_err());
+    assert!(client.try_set_recovery_timelock(&owner, &0u64).is_err());
+    client.set_recovery_timelock(&owner, &60u64);
+    assert_eq!(client.get_recovery_timelock(), 60);
+
+    set_time(&env, 0);
+    let sid = client.create_cliff_schedule(
+        &employer,
+        &beneficiary,
+        &token.address,
+        &400i128,
+        &100u64,
+        &false,
+        &ScheduleOptions::default(),
+    );
+
+    let recovered = Address::generate(&env);
+    assert!(client
+        .try_initiate_recovery(&beneficiary, &sid, &recovered)
+        .is_err());
+    client.initiate_recovery(&owner, &sid, &recovered);
+    set_time(&env, 60);
+    client.finalize_recovery(&sid);
+    assert_eq!(client.get_schedule(&sid).unwrap().beneficiary, recovered);
+}